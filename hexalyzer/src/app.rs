@@ -2,11 +2,19 @@ use crate::byteedit::ByteEdit;
 use crate::events::EventState;
 use crate::loader::FileKind;
 use crate::selection::Selection;
+use crate::ui_bookmarks::Bookmarks;
 use crate::ui_converter::HexConverter;
+use crate::ui_entropy::EntropyWindow;
+use crate::ui_inspector::Inspector;
 use crate::ui_jumpto::JumpTo;
 use crate::ui_popup::Popup;
 use crate::ui_search::Search;
-use intelhexlib::IntelHex;
+use crate::ui_signatures::SignaturesWindow;
+use crate::ui_strings::StringsWindow;
+use crate::ui_symbols::SymbolsWindow;
+use crate::ui_template::TemplateView;
+use crate::ui_vectors::VectorTables;
+use intelhexlib::{IntelHex, Journal, Snapshot, SymbolTable};
 use std::ops::RangeInclusive;
 
 pub mod colors {
@@ -19,8 +27,36 @@ pub mod colors {
     pub const GRAY_210: Color32 = Color32::from_gray(210);
     pub const SHADOW: Color32 = Color32::from_black_alpha(150);
     pub const WARNING: Color32 = Color32::from_rgb(160, 160, 16);
+    pub const TEMPLATE_HOVER: Color32 = Color32::from_rgba_premultiplied(40, 110, 110, 60);
+
+    /// Background colors of the fields of an applied template, used in turn so that
+    /// neighboring fields can be told apart
+    pub const TEMPLATE: [Color32; 2] = [
+        Color32::from_rgba_premultiplied(25, 70, 70, 20),
+        Color32::from_rgba_premultiplied(45, 55, 90, 20),
+    ];
+
+    /// Background colors used to distinguish bytes of different source files
+    pub const SOURCES: [Color32; 6] = [
+        Color32::from_rgba_premultiplied(90, 40, 110, 20),
+        Color32::from_rgba_premultiplied(20, 90, 100, 20),
+        Color32::from_rgba_premultiplied(110, 60, 20, 20),
+        Color32::from_rgba_premultiplied(40, 100, 40, 20),
+        Color32::from_rgba_premultiplied(110, 30, 50, 20),
+        Color32::from_rgba_premultiplied(70, 70, 120, 20),
+    ];
+
+    /// Get the color of the source with the given id
+    pub const fn source(id: usize) -> Color32 {
+        SOURCES[id % SOURCES.len()]
+    }
 }
 
+/// Edit state of a session: recorded edits, undo and redo depth, size and start address.
+/// Views that derive data from the file (templates, vector tables, strings) compare it to
+/// decide whether to derive the data again.
+pub type DataState = (Option<u64>, usize, usize, usize, Option<usize>);
+
 #[derive(PartialEq, Eq)]
 pub enum Endianness {
     Little,
@@ -44,6 +80,16 @@ pub struct HexSession {
     pub search: Search,
     /// Handler for GUI feature to jump to selected address
     pub jump_to: JumpTo,
+    /// Cortex-M vector tables detected in the data
+    pub vectors: VectorTables,
+    /// Symbols loaded from a linker map or ELF file
+    pub symbols: SymbolTable,
+    /// Named address ranges with notes, stored next to the file
+    pub bookmarks: Bookmarks,
+    /// Structure template applied at an address, shown next to the hex view
+    pub template: TemplateView,
+    /// Value editing state of the data inspector
+    pub inspector: Inspector,
     /// Last modified time of the file. Used to detect file changes.
     pub last_modified: std::time::SystemTime,
     /// Last time the file modification was checked. Used to throttle `fs::metadata` calls.
@@ -53,6 +99,9 @@ pub struct HexSession {
     /// Whether the session has been modified by structural operations (relocate, insert/remove
     /// range, merge). Byte-level edits are tracked separately in `editor.modified`.
     pub dirty: bool,
+    /// Data as it was last loaded from or saved to the file (`None` for unsaved tabs).
+    /// Shares unchanged chunks with `ih`, so it only costs the memory of edited chunks.
+    pub saved: Option<Snapshot>,
     /// Detected file format at load time
     pub file_kind: FileKind,
     /// Scroll id that allows each tab to keep its own scroll position
@@ -77,6 +126,14 @@ pub struct HexViewerApp {
 
     /// Hex/Dec/Bin/ASCII converter tool
     pub converter: HexConverter,
+    /// Strings tool
+    pub strings: StringsWindow,
+    /// Entropy tool
+    pub entropy: EntropyWindow,
+    /// Embedded files (signatures) tool
+    pub signatures: SignaturesWindow,
+    /// Symbol list tool
+    pub symbols: SymbolsWindow,
 
     // -- UI states
     /// Per-frame state of user inputs
@@ -86,6 +143,8 @@ pub struct HexViewerApp {
 
     /// Gap fill byte used when exporting to binary (0x00 or 0xFF)
     pub gap_fill: u8,
+    /// Number of rotating `.bak` backups of the original file kept on save (0 = disabled)
+    pub backups: usize,
 }
 
 impl Default for HexSession {
@@ -99,16 +158,35 @@ impl Default for HexSession {
             selection: Selection::default(),
             search: Search::default(),
             jump_to: JumpTo::default(),
+            vectors: VectorTables::default(),
+            symbols: SymbolTable::new(),
+            bookmarks: Bookmarks::default(),
+            template: TemplateView::default(),
+            inspector: Inspector::default(),
             last_modified: std::time::SystemTime::UNIX_EPOCH,
             last_mod_check: std::time::Instant::now(),
             file_changed_on_disk: false,
             dirty: false,
+            saved: None,
             file_kind: FileKind::Unknown,
             scroll_id: 0,
         }
     }
 }
 
+impl HexSession {
+    /// Current edit state of the data. Changes with every edit, undo and redo.
+    pub(crate) fn data_state(&self) -> DataState {
+        (
+            self.ih.journal().map(Journal::recorded),
+            self.editor.undo_modified.len(),
+            self.editor.redo_modified.len(),
+            self.ih.size,
+            self.ih.get_min_addr(),
+        )
+    }
+}
+
 impl Default for HexViewerApp {
     fn default() -> Self {
         Self {
@@ -118,11 +196,16 @@ impl Default for HexViewerApp {
             bytes_per_row: 16,
             popup: Popup::default(),
             converter: HexConverter::default(),
+            strings: StringsWindow::default(),
+            entropy: EntropyWindow::default(),
+            signatures: SignaturesWindow::default(),
+            symbols: SymbolsWindow::default(),
             next_scroll_id: 1,
             side_panel_expanded: true,
             events: EventState::default(),
             error: None,
             gap_fill: 0x00,
+            backups: 0,
         }
     }
 }
@@ -0,0 +1,176 @@
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// Colors of new bookmarks, used in turn
+pub const PALETTE: [[u8; 3]; 6] = [
+    [224, 160, 48],
+    [64, 160, 224],
+    [96, 192, 96],
+    [208, 80, 160],
+    [160, 112, 224],
+    [224, 96, 80],
+];
+
+/// Extension appended to the name of a file to get the name of its bookmarks file
+const SIDECAR_EXTENSION: &str = ".hexalyzer.json";
+
+/// Named and colored address range with a free-text note
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Bookmark {
+    /// Name of the bookmark, e.g. "calibration block"
+    pub name: String,
+    /// First address of the range
+    #[serde(with = "hex_addr")]
+    pub start: usize,
+    /// Last address of the range (inclusive)
+    #[serde(with = "hex_addr")]
+    pub end: usize,
+    /// Highlight color as RGB
+    #[serde(with = "hex_color")]
+    pub color: [u8; 3],
+    /// Free-text note
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub note: String,
+}
+
+impl Bookmark {
+    /// Check if the address is within the bookmarked range
+    pub const fn contains(&self, addr: usize) -> bool {
+        self.start <= addr && addr <= self.end
+    }
+}
+
+/// Content of the bookmarks file
+#[derive(Default, Serialize, Deserialize)]
+struct Sidecar {
+    bookmarks: Vec<Bookmark>,
+}
+
+/// Path of the bookmarks file of a file, e.g. `firmware.hex.hexalyzer.json`
+pub fn sidecar_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(SIDECAR_EXTENSION);
+    PathBuf::from(name)
+}
+
+/// Load the bookmarks of a file from its bookmarks file. No bookmarks if there is none.
+pub fn load_bookmarks(path: &Path) -> Result<Vec<Bookmark>, String> {
+    let sidecar = sidecar_path(path);
+    if !sidecar.exists() {
+        return Ok(Vec::new());
+    }
+
+    let text = std::fs::read_to_string(&sidecar).map_err(|e| e.to_string())?;
+    let content: Sidecar = serde_json::from_str(&text)
+        .map_err(|e| format!("Invalid bookmarks file {}: {e}", sidecar.display()))?;
+    Ok(content.bookmarks)
+}
+
+/// Save the bookmarks of a file to its bookmarks file.
+/// The bookmarks file is removed if there are no bookmarks.
+pub fn save_bookmarks(path: &Path, bookmarks: &[Bookmark]) -> Result<(), String> {
+    let sidecar = sidecar_path(path);
+    if bookmarks.is_empty() {
+        if sidecar.exists() {
+            std::fs::remove_file(&sidecar).map_err(|e| e.to_string())?;
+        }
+        return Ok(());
+    }
+
+    let content = Sidecar {
+        bookmarks: bookmarks.to_vec(),
+    };
+    let text = serde_json::to_string_pretty(&content).map_err(|e| e.to_string())?;
+    intelhexlib::write_file_atomic(&sidecar, (text + "\n").as_bytes()).map_err(|e| e.to_string())
+}
+
+/// Addresses as hex strings, e.g. `"0x08004000"`, so the file is easy to read and diff
+mod hex_addr {
+    use serde::{Deserialize, Deserializer, Serializer, de::Error};
+
+    #[allow(clippy::trivially_copy_pass_by_ref)] // signature required by serde
+    pub fn serialize<S: Serializer>(addr: &usize, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&format!("0x{addr:08X}"))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<usize, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        let digits = text.trim_start_matches("0x").trim_start_matches("0X");
+        usize::from_str_radix(digits, 16)
+            .map_err(|_| D::Error::custom(format!("invalid address: {text}")))
+    }
+}
+
+/// Colors as hex strings, e.g. `"#E0A030"`
+mod hex_color {
+    use serde::{Deserialize, Deserializer, Serializer, de::Error};
+
+    #[allow(clippy::trivially_copy_pass_by_ref)] // signature required by serde
+    pub fn serialize<S: Serializer>(color: &[u8; 3], serializer: S) -> Result<S::Ok, S::Error> {
+        let [r, g, b] = color;
+        serializer.serialize_str(&format!("#{r:02X}{g:02X}{b:02X}"))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<[u8; 3], D::Error> {
+        let text = String::deserialize(deserializer)?;
+        let invalid = || D::Error::custom(format!("invalid color: {text}"));
+        let rgb = text
+            .strip_prefix('#')
+            .filter(|digits| digits.len() == 6)
+            .and_then(|digits| u32::from_str_radix(digits, 16).ok())
+            .ok_or_else(invalid)?;
+        let [_, r, g, b] = rgb.to_be_bytes();
+        Ok([r, g, b])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sidecar_path() {
+        // Act
+        let sidecar = sidecar_path(Path::new("build/firmware.hex"));
+
+        // Assert
+        assert_eq!(sidecar, PathBuf::from("build/firmware.hex.hexalyzer.json"));
+    }
+
+    #[test]
+    fn test_sidecar_roundtrip() {
+        // Arrange
+        let content = Sidecar {
+            bookmarks: vec![
+                Bookmark {
+                    name: "calibration block".to_string(),
+                    start: 0x0800_4000,
+                    end: 0x0800_40FF,
+                    color: [224, 160, 48],
+                    note: "Written at end of line".to_string(),
+                },
+                Bookmark {
+                    name: "header".to_string(),
+                    start: 0x0,
+                    end: 0xF,
+                    color: [64, 160, 224],
+                    note: String::new(),
+                },
+            ],
+        };
+
+        // Act
+        let text = serde_json::to_string_pretty(&content).unwrap_or_default();
+        let parsed: Sidecar = serde_json::from_str(&text).unwrap_or_default();
+        let invalid = serde_json::from_str::<Sidecar>(
+            r#"{ "bookmarks": [{ "name": "a", "start": "0x10", "end": "0x1F", "color": "E0A030" }] }"#,
+        );
+
+        // Assert - addresses and colors are hex strings, empty notes are left out
+        assert!(text.contains(r#""start": "0x08004000""#));
+        assert!(text.contains(r##""color": "#E0A030""##));
+        assert_eq!(text.matches("note").count(), 1);
+        assert_eq!(parsed.bookmarks, content.bookmarks);
+        assert!(invalid.is_err());
+    }
+}
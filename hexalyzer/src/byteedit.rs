@@ -1,4 +1,7 @@
-use crate::app::HexSession;
+use crate::app::{HexSession, HexViewerApp};
+use crate::events::HistoryAction;
+use eframe::egui;
+use intelhexlib::{Journal, Transform};
 use std::collections::HashMap;
 
 #[derive(Default)]
@@ -13,6 +16,10 @@ pub struct ByteEdit {
     pub(crate) modified: HashMap<usize, u8>,
     /// When `true`, `update_edit_buffer` is a no-op. In-progress edit is cleared.
     pub(crate) blocked: bool,
+    /// Snapshots of `modified` taken before each undoable edit (kept in sync with the journal)
+    pub(crate) undo_modified: Vec<HashMap<usize, u8>>,
+    /// Snapshots of `modified` taken before each undone edit
+    pub(crate) redo_modified: Vec<HashMap<usize, u8>>,
 }
 
 impl ByteEdit {
@@ -90,15 +97,17 @@ impl HexSession {
 
                         // Update the bytes in the map. If the byte is actually changed -
                         // insert its address into Vec that tracks modified bytes.
-                        for addr in s..=e {
-                            let prev_value = self.ih.read_byte(addr);
-                            if self.ih.update_byte(addr, value).ok() == Some(())
-                                && let Some(prev) = prev_value
-                                && value != prev
-                            {
-                                self.editor.modified.entry(addr).or_insert(prev);
+                        self.transaction("Edit bytes", |session| {
+                            for addr in s..=e {
+                                let prev_value = session.ih.read_byte(addr);
+                                if session.ih.update_byte(addr, value).ok() == Some(())
+                                    && let Some(prev) = prev_value
+                                    && value != prev
+                                {
+                                    session.editor.modified.entry(addr).or_insert(prev);
+                                }
                             }
-                        }
+                        });
 
                         // If there are search results - redo it
                         if !self.search.results.is_empty() {
@@ -111,16 +120,185 @@ impl HexSession {
         }
     }
 
+    /// Apply the transform to the selected bytes. Changed bytes are tracked as modified.
+    pub(crate) fn transform_selection(&mut self, transform: &Transform) -> Result<(), String> {
+        let Some([start, end]) = self.selection.range else {
+            return Ok(());
+        };
+
+        // Handle reversed range
+        let s = start.min(end);
+        let e = start.max(end);
+
+        let before = self.ih.read_range(s, e - s + 1);
+
+        self.transaction("Transform", |session| {
+            session
+                .ih
+                .transform_range(s, e, transform)
+                .map_err(|err| err.to_string())?;
+
+            // Track the original values of the bytes that actually changed
+            if let Some(before) = before {
+                for (addr, prev) in (s..=e).zip(before) {
+                    if session.ih.read_byte(addr) != Some(prev) {
+                        session.editor.modified.entry(addr).or_insert(prev);
+                    }
+                }
+            }
+
+            Ok::<(), String>(())
+        })?;
+
+        // If there are search results - redo it
+        if !self.search.results.is_empty() {
+            self.search.redo();
+        }
+
+        Ok(())
+    }
+
+    /// Overwrite the bytes at the address with `bytes` as a single undo step named `label`.
+    /// Changed bytes are tracked as modified.
+    pub(crate) fn write_bytes(
+        &mut self,
+        label: &str,
+        addr: usize,
+        bytes: &[u8],
+    ) -> Result<(), String> {
+        let before = self.ih.read_range(addr, bytes.len());
+
+        self.transaction(label, |session| {
+            session
+                .ih
+                .update_range(addr, bytes)
+                .map_err(|err| err.to_string())?;
+
+            // Track the original values of the bytes that actually changed
+            if let Some(before) = before {
+                for ((addr, prev), &value) in (addr..).zip(before).zip(bytes) {
+                    if value != prev {
+                        session.editor.modified.entry(addr).or_insert(prev);
+                    }
+                }
+            }
+
+            Ok::<(), String>(())
+        })?;
+
+        // If there are search results - redo it
+        if !self.search.results.is_empty() {
+            self.search.redo();
+        }
+
+        Ok(())
+    }
+
     /// Restore all modified bytes to their original values
     pub(crate) fn restore(&mut self) {
-        for (&addr, &orig_value) in &self.editor.modified {
-            let _ = self.ih.update_byte(addr, orig_value);
-        }
+        self.transaction("Restore bytes", |session| {
+            for (&addr, &orig_value) in &session.editor.modified {
+                let _ = session.ih.update_byte(addr, orig_value);
+            }
 
-        self.editor.modified.clear();
+            session.editor.modified.clear();
+        });
 
         if !self.search.results.is_empty() {
             self.search.redo();
         }
     }
 }
+
+impl HexSession {
+    /// Run the edit as a single undo step named `label`. The set of modified bytes is
+    /// snapshotted alongside, so that undo/redo also restores the byte highlighting.
+    pub(crate) fn transaction<T>(&mut self, label: &str, op: impl FnOnce(&mut Self) -> T) -> T {
+        let modified = self.editor.modified.clone();
+        let recorded = self.ih.journal().map(Journal::recorded);
+
+        self.ih.begin_transaction(label);
+        let res = op(self);
+        self.ih.commit_transaction();
+
+        // Only keep the snapshot if the edit actually added an undo step
+        if self.ih.journal().map(Journal::recorded) != recorded {
+            self.editor.undo_modified.push(modified);
+            self.editor.redo_modified.clear();
+
+            if self.editor.undo_modified.len() > Journal::DEFAULT_LIMIT {
+                self.editor.undo_modified.remove(0);
+            }
+        }
+
+        res
+    }
+
+    /// Undo the last edit of any kind (byte edits, relocate, insert/remove range, merge)
+    pub(crate) fn undo(&mut self) {
+        if !self.ih.undo() {
+            return;
+        }
+
+        let modified = self.editor.undo_modified.pop().unwrap_or_default();
+        let current = std::mem::replace(&mut self.editor.modified, modified);
+        self.editor.redo_modified.push(current);
+        self.after_history_change();
+    }
+
+    /// Redo the last undone edit
+    pub(crate) fn redo(&mut self) {
+        if !self.ih.redo() {
+            return;
+        }
+
+        let modified = self.editor.redo_modified.pop().unwrap_or_default();
+        let current = std::mem::replace(&mut self.editor.modified, modified);
+        self.editor.undo_modified.push(current);
+        self.after_history_change();
+    }
+
+    /// Bring back the data as it was last loaded or saved, as a single undo step
+    pub(crate) fn revert_to_saved(&mut self) {
+        let Some(saved) = self.saved.clone() else {
+            return;
+        };
+
+        self.transaction("Revert to saved", |session| {
+            session.ih.restore(&saved);
+            session.editor.modified.clear();
+        });
+
+        self.after_history_change();
+        self.dirty = false;
+    }
+
+    /// Refresh the session state after the data was changed by undo/redo
+    fn after_history_change(&mut self) {
+        self.editor.clear();
+        self.dirty = true;
+        self.addr = self.ih.get_min_addr().unwrap_or(0)..=self.ih.get_max_addr().unwrap_or(0);
+        self.search.redo();
+    }
+}
+
+impl HexViewerApp {
+    /// Handle the undo (Cmd+Z / Ctrl+Z) and redo (Shift+Cmd+Z / Ctrl+Shift+Z) shortcuts.
+    /// Ignored while a popup is open or a text field has focus.
+    pub(crate) fn handle_undo_shortcut(&mut self, ctx: &egui::Context) {
+        if self.popup.active || ctx.text_edit_focused() {
+            return;
+        }
+
+        let Some(action) = self.events.history_pressed else {
+            return;
+        };
+
+        if let Some(curr_session) = self.get_curr_session_mut() {
+            match action {
+                HistoryAction::Undo => curr_session.undo(),
+                HistoryAction::Redo => curr_session.redo(),
+            }
+        }
+    }
+}
@@ -15,6 +15,14 @@ pub struct EventState {
     pub(crate) enter_released: bool,
     pub(crate) shift_down: bool,
     pub(crate) arrow_key_released: Option<egui::Key>,
+    pub(crate) history_pressed: Option<HistoryAction>,
+}
+
+/// Undo/redo shortcut pressed this frame
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum HistoryAction {
+    Undo,
+    Redo,
 }
 
 #[allow(clippy::enum_glob_use)]
@@ -103,5 +111,14 @@ fn collect_inner(i: &egui::InputState) -> EventState {
     // Direct query for Escape pressed this frame
     state.escape_pressed = i.key_pressed(egui::Key::Escape);
 
+    // Undo (Cmd+Z / Ctrl+Z) and redo (Shift+Cmd+Z / Ctrl+Shift+Z)
+    if i.modifiers.command && i.key_pressed(egui::Key::Z) {
+        state.history_pressed = Some(if i.modifiers.shift {
+            HistoryAction::Redo
+        } else {
+            HistoryAction::Undo
+        });
+    }
+
     state
 }
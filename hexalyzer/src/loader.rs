@@ -1,6 +1,6 @@
 use crate::app::{HexSession, HexViewerApp};
 use crate::byteedit::ByteEdit;
-use intelhexlib::IntelHex;
+use intelhexlib::{IntelHex, SymbolTable, Template};
 use std::fs::File;
 use std::io::Read;
 use std::path::PathBuf;
@@ -58,6 +58,9 @@ pub fn kind_from_extension(path: &std::path::Path) -> FileKind {
     }
 }
 
+/// BIN files larger than this are memory-mapped instead of read into memory
+const MAPPED_BIN_THRESHOLD: u64 = 64 * 1024 * 1024;
+
 /// Load a file into an `IntelHex` instance. Returns the detected `FileKind` on success.
 fn load_file_into_ih(path: &PathBuf) -> Result<(IntelHex, FileKind), String> {
     let file_kind = detect_file_kind(path).map_err(|e| e.to_string())?;
@@ -65,6 +68,9 @@ fn load_file_into_ih(path: &PathBuf) -> Result<(IntelHex, FileKind), String> {
     let mut ih = IntelHex::new();
     match file_kind {
         FileKind::Hex => ih.load_hex(path).map_err(|e| e.to_string()),
+        FileKind::Bin if std::fs::metadata(path).is_ok_and(|m| m.len() > MAPPED_BIN_THRESHOLD) => {
+            ih.load_bin_mapped(path, 0).map_err(|e| e.to_string())
+        }
         FileKind::Bin => ih.load_bin(path, 0).map_err(|e| e.to_string()),
         FileKind::Elf => Err("ELF files are not yet supported".to_string()),
         FileKind::Unknown => Err("Could not determine the file type".to_string()),
@@ -87,6 +93,33 @@ pub fn write_ih_to_path(
     }
 }
 
+/// Path of the n-th backup of a file, e.g. `firmware.hex.1.bak`
+fn backup_path(path: &std::path::Path, n: usize) -> PathBuf {
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    path.with_file_name(format!("{name}.{n}.bak"))
+}
+
+/// Copy the file to `<file>.1.bak`, shifting older backups up to `<file>.<count>.bak`.
+/// The oldest backup is dropped. Does nothing if `count` is 0 or the file does not exist.
+pub fn rotate_backups(path: &std::path::Path, count: usize) -> std::io::Result<()> {
+    if count == 0 || !path.is_file() {
+        return Ok(());
+    }
+
+    for n in (1..count).rev() {
+        let older = backup_path(path, n);
+        if older.exists() {
+            std::fs::rename(&older, backup_path(path, n + 1))?;
+        }
+    }
+
+    std::fs::copy(path, backup_path(path, 1))?;
+    Ok(())
+}
+
 impl HexViewerApp {
     /// Load hex file from disk and add it to the list of opened sessions.
     /// If the file is already open, switch to it.
@@ -136,18 +169,113 @@ impl HexViewerApp {
             ..HexSession::default()
         };
 
-        // Load the IntelHex
+        // Load the IntelHex and record its edits for undo/redo
         new_session.ih = ih;
+        new_session.ih.enable_journal();
+        new_session.saved = Some(new_session.ih.snapshot());
 
         // Re-calculate address range
         new_session.addr =
             new_session.ih.get_min_addr().unwrap_or(0)..=new_session.ih.get_max_addr().unwrap_or(0);
 
+        // Load the bookmarks stored next to the file (the file opens even if they are invalid)
+        if let Err(msg) = new_session.load_bookmarks() {
+            self.error = Some(msg);
+        }
+
+        // Add the new session and switch to it
+        self.sessions.push(new_session);
+        self.active_index = Some(self.sessions.len() - 1);
+    }
+
+    /// Open the given bytes (e.g. a file embedded in the current one) in a new unsaved tab.
+    /// The data starts at address 0x0.
+    pub(crate) fn open_extracted(&mut self, name: &str, data: &[u8]) {
+        // Prevent opening more tabs than allowed by the app settings
+        if self.sessions.len() >= self.max_tabs {
+            self.error = Some("Maximum number of tabs reached".into());
+            return;
+        }
+        if data.is_empty() {
+            return;
+        }
+
+        let mut ih = IntelHex::new();
+        if let Err(err) = ih
+            .write_range(0, data.len() - 1)
+            .and_then(|()| ih.update_range(0, data))
+        {
+            self.error = Some(err.to_string());
+            return;
+        }
+
+        // Determine unique scroll widget id
+        let scroll_id = self.next_scroll_id;
+        self.next_scroll_id += 1;
+
+        let mut new_session = HexSession {
+            name: name.to_string(),
+            file_kind: FileKind::Bin,
+            scroll_id,
+            dirty: true,
+            ..HexSession::default()
+        };
+        new_session.ih = ih;
+        new_session.ih.enable_journal();
+        new_session.addr = 0..=data.len() - 1;
+
         // Add the new session and switch to it
         self.sessions.push(new_session);
         self.active_index = Some(self.sessions.len() - 1);
     }
 
+    /// Load symbols of the current session from a linker map or ELF file. Replaces the
+    /// symbols loaded before.
+    pub(crate) fn load_symbols(&mut self, path: &PathBuf) {
+        let symbols = match SymbolTable::from_file(path) {
+            Ok(symbols) => symbols,
+            Err(err) => {
+                self.error = Some(err.to_string());
+                return;
+            }
+        };
+        if symbols.is_empty() {
+            self.error = Some("No symbols found in the file".into());
+            return;
+        }
+
+        if let Some(curr_session) = self.get_curr_session_mut() {
+            curr_session.symbols = symbols;
+        }
+        self.symbols.refresh();
+    }
+
+    /// Load a structure template (C header with struct definitions) for the current session
+    /// and show the template panel.
+    pub(crate) fn load_template(&mut self, path: &PathBuf) {
+        let template = match std::fs::read_to_string(path)
+            .map_err(|err| err.to_string())
+            .and_then(|text| Template::parse(&text).map_err(|err| err.to_string()))
+        {
+            Ok(template) => template,
+            Err(err) => {
+                self.error = Some(err);
+                return;
+            }
+        };
+        if template.struct_names().is_empty() {
+            self.error = Some("No struct definitions found in the file".into());
+            return;
+        }
+
+        let name = path
+            .file_name()
+            .map_or_else(String::new, |n| n.to_string_lossy().into_owned());
+        if let Some(curr_session) = self.get_curr_session_mut() {
+            curr_session.set_template(template, name);
+        }
+    }
+
     /// Close the file with the given ID. When the file is closed, switch to the first one.
     pub(crate) fn close_file(&mut self, session_id: usize) {
         self.sessions.remove(session_id);
@@ -200,6 +328,8 @@ impl HexViewerApp {
         };
 
         session.ih = ih;
+        session.ih.enable_journal();
+        session.saved = Some(session.ih.snapshot());
         session.addr =
             session.ih.get_min_addr().unwrap_or(0)..=session.ih.get_max_addr().unwrap_or(0);
         session.last_modified = last_modified;
@@ -208,6 +338,10 @@ impl HexViewerApp {
         session.editor = ByteEdit::default();
         session.dirty = false;
         session.search.redo();
+        session.template.refresh();
+        if let Err(msg) = session.load_bookmarks() {
+            self.error = Some(msg);
+        }
     }
 
     /// Save the current session back to its original file path.
@@ -228,6 +362,16 @@ impl HexViewerApp {
 
         let file_kind = session.file_kind.clone();
 
+        // Keep a copy of the original file before overwriting it
+        if let Err(err) = rotate_backups(&path, self.backups) {
+            self.error = Some(format!("Failed to create backup: {err}"));
+            return;
+        }
+
+        let Some(session) = self.get_curr_session_mut() else {
+            return;
+        };
+
         if let Err(msg) = write_ih_to_path(&mut session.ih, &path, &file_kind, gap_fill) {
             self.error = Some(msg);
             return;
@@ -239,6 +383,7 @@ impl HexViewerApp {
 
         session.editor.modified.clear();
         session.dirty = false;
+        session.saved = Some(session.ih.snapshot());
         session.last_modified =
             get_last_modified(&path).unwrap_or(std::time::SystemTime::UNIX_EPOCH);
         session.file_changed_on_disk = false;
@@ -257,45 +402,49 @@ impl HexViewerApp {
         addr2: Option<usize>,
     ) {
         if let Some(cur_session) = self.get_curr_session_mut() {
-            // Relocate the current file to a new start address
-            if let Some(new_start_addr) = addr1 {
-                let old_start_addr = cur_session.ih.get_min_addr();
-
-                let res = cur_session.ih.relocate(new_start_addr);
-
-                if let Err(msg) = res {
-                    self.error = Some(msg.to_string());
-                    return;
+            let res = cur_session.transaction("Merge", |session| {
+                // Relocate the current file to a new start address
+                if let Some(new_start_addr) = addr1 {
+                    let old_start_addr = session.ih.get_min_addr();
+
+                    session
+                        .ih
+                        .relocate(new_start_addr)
+                        .map_err(|e| e.to_string())?;
+
+                    if let Some(old_start_addr) = old_start_addr {
+                        session
+                            .editor
+                            .remap_modified(new_start_addr, old_start_addr);
+                    }
                 }
 
-                if let Some(old_start_addr) = old_start_addr {
-                    cur_session
-                        .editor
-                        .remap_modified(new_start_addr, old_start_addr);
-                }
-            }
+                // Load the selected file into a new IntelHex instance
+                let (mut new_ih, _) = load_file_into_ih(path)?;
 
-            // Load the selected file into a new IntelHex instance
-            let (mut new_ih, _) = match load_file_into_ih(path) {
-                Ok(result) => result,
-                Err(msg) => {
-                    self.error = Some(msg);
-                    return;
+                // Relocate the selected file to a new start address
+                if let Some(new_start_addr) = addr2 {
+                    new_ih.relocate(new_start_addr).map_err(|e| e.to_string())?;
                 }
-            };
-
-            // Relocate the selected file to a new start address
-            if let Some(new_start_addr) = addr2 {
-                let res = new_ih.relocate(new_start_addr);
 
-                if let Err(msg) = res {
-                    self.error = Some(msg.to_string());
-                    return;
+                // Tag both instances with their file names to keep track of byte origins
+                if session.ih.provenance().is_none() {
+                    session.ih.tag_source(&session.name);
                 }
+                let new_name = path.file_name().map_or_else(
+                    || "Untitled".to_string(),
+                    |n| n.to_string_lossy().into_owned(),
+                );
+                new_ih.tag_source(&new_name);
+
+                // Merge the two IntelHex instances
+                session.ih.merge(&new_ih);
+                Ok::<(), String>(())
+            });
+
+            if let Err(msg) = res {
+                self.error = Some(msg);
             }
-
-            // Merge the two IntelHex instances
-            cur_session.ih.merge(&new_ih);
         } else {
             self.error = Some("Could not get current hex session".to_string());
         }
@@ -11,14 +11,18 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
 mod app;
+mod bookmarks;
 mod byteedit;
 mod clipboard;
 mod events;
 mod loader;
 mod selection;
+mod settings;
+mod ui_bookmarks;
 mod ui_button;
 mod ui_centralpanel;
 mod ui_converter;
+mod ui_entropy;
 mod ui_filedrop;
 mod ui_inspector;
 mod ui_jumpto;
@@ -27,7 +31,13 @@ mod ui_popup;
 mod ui_scrollarea;
 mod ui_search;
 mod ui_sidepanel;
+mod ui_signatures;
+mod ui_sources;
+mod ui_strings;
+mod ui_symbols;
 mod ui_tabs;
+mod ui_template;
+mod ui_vectors;
 
 use crate::ui_popup::PopupState;
 use app::HexViewerApp;
@@ -45,7 +55,7 @@ fn main() -> eframe::Result<()> {
     eframe::run_native(
         "Hexalyzer",
         options,
-        Box::new(|_cc| Ok(Box::new(HexViewerApp::default()))),
+        Box::new(|_cc| Ok(Box::new(HexViewerApp::with_saved_settings()))),
     )
 }
 
@@ -71,12 +81,34 @@ impl eframe::App for HexViewerApp {
         // Collect input events once per frame
         self.events = events::collect_ui_events_ctx(&ctx);
         self.handle_copy_shortcut(&ctx);
+        self.handle_undo_shortcut(&ctx);
 
         self.show_menu_bar(ui);
 
         // Render converter tool window if opened
         self.converter.show(&ctx);
 
+        // Render strings tool window of the current file if opened
+        let curr_session = self.active_index.and_then(|i| self.sessions.get_mut(i));
+        self.strings.show(&ctx, curr_session);
+
+        // Render entropy tool window of the current file if opened
+        let curr_session = self.active_index.and_then(|i| self.sessions.get_mut(i));
+        self.entropy.show(&ctx, curr_session);
+
+        // Render embedded files tool window of the current file if opened
+        let curr_session = self.active_index.and_then(|i| self.sessions.get_mut(i));
+        self.signatures.show(&ctx, curr_session);
+        match self.signatures.extract.take() {
+            Some(Ok((name, data))) => self.open_extracted(&name, &data),
+            Some(Err(msg)) => self.error = Some(msg),
+            None => {}
+        }
+
+        // Render symbol list tool window of the current file if opened
+        let curr_session = self.active_index.and_then(|i| self.sessions.get_mut(i));
+        self.symbols.show(&ctx, curr_session);
+
         if self.error.is_some() {
             let msg = self.error.clone().unwrap_or_default();
             self.popup.open(PopupState::Error(msg));
@@ -102,11 +134,25 @@ impl eframe::App for HexViewerApp {
         // Show the content of the active session
         if let Some(index) = self.active_index {
             if let Some(curr_session) = self.sessions.get_mut(index) {
-                curr_session.selection.blocked = self.popup.active || self.converter.has_focus();
-                curr_session.editor.blocked = self.popup.active || self.converter.has_focus();
+                let tool_focused = self.converter.has_focus()
+                    || self.strings.has_focus()
+                    || self.entropy.has_focus()
+                    || self.signatures.has_focus()
+                    || self.symbols.has_focus();
+                let inspector_focused =
+                    curr_session.inspector.is_editing() && ctx.text_edit_focused();
+                curr_session.selection.blocked =
+                    self.popup.active || tool_focused || inspector_focused;
+                curr_session.editor.blocked =
+                    self.popup.active || tool_focused || inspector_focused;
                 if self.converter.has_focus() {
                     curr_session.selection.clear();
                 }
+                // Keep repainting until a search in progress is done
+                if curr_session.continue_search() {
+                    ctx.request_repaint();
+                }
+                curr_session.show_template_panel(ui);
                 curr_session.show_central_panel(ui, self.bytes_per_row, &self.events);
             }
         } else {
@@ -0,0 +1,92 @@
+use crate::app::HexViewerApp;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// File menu settings kept between runs
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    /// Gap fill byte used when exporting to binary
+    pub gap_fill: u8,
+    /// Number of rotating `.bak` backups of the original file kept on save
+    pub backups: usize,
+}
+
+impl Settings {
+    /// Load the settings saved by the last run. Defaults are used if there are none
+    /// or the settings file is invalid.
+    pub fn load() -> Self {
+        settings_path()
+            .and_then(|path| std::fs::read_to_string(path).ok())
+            .and_then(|text| serde_json::from_str(&text).ok())
+            .unwrap_or_default()
+    }
+
+    /// Save the settings for the next run
+    pub fn save(self) -> Result<(), String> {
+        let path = settings_path().ok_or("No directory to save the settings to")?;
+        let text = serde_json::to_string_pretty(&self).map_err(|e| e.to_string())?;
+        intelhexlib::write_file_atomic(path, (text + "\n").as_bytes()).map_err(|e| e.to_string())
+    }
+}
+
+/// Path of the settings file in the user's config directory, e.g.
+/// `~/.config/hexalyzer/settings.json` on Linux
+fn settings_path() -> Option<PathBuf> {
+    let home = || std::env::var_os("HOME").map(PathBuf::from);
+    let config_dir = if cfg!(windows) {
+        std::env::var_os("APPDATA").map(PathBuf::from)
+    } else if cfg!(target_os = "macos") {
+        home().map(|h| h.join("Library").join("Application Support"))
+    } else {
+        std::env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .or_else(|| home().map(|h| h.join(".config")))
+    };
+    config_dir.map(|dir| dir.join("hexalyzer").join("settings.json"))
+}
+
+impl HexViewerApp {
+    /// Create the app with the settings saved by the last run
+    pub(crate) fn with_saved_settings() -> Self {
+        let settings = Settings::load();
+        Self {
+            gap_fill: settings.gap_fill,
+            backups: settings.backups,
+            ..Self::default()
+        }
+    }
+
+    /// Save the settings if they differ from `before`
+    pub(crate) fn save_settings_if_changed(&mut self, before: Settings) {
+        let settings = Settings {
+            gap_fill: self.gap_fill,
+            backups: self.backups,
+        };
+        if settings != before
+            && let Err(err) = settings.save()
+        {
+            self.error = Some(format!("Failed to save settings: {err}"));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_settings_missing_fields_use_defaults() {
+        // Act
+        let settings: Settings = serde_json::from_str(r#"{ "backups": 3 }"#).unwrap_or_default();
+
+        // Assert
+        assert_eq!(
+            settings,
+            Settings {
+                gap_fill: 0x00,
+                backups: 3
+            }
+        );
+    }
+}
@@ -0,0 +1,194 @@
+use crate::app::{HexSession, colors};
+use crate::bookmarks::{Bookmark, PALETTE, load_bookmarks, save_bookmarks};
+use eframe::egui;
+
+#[derive(Default)]
+pub struct Bookmarks {
+    /// Bookmarks of the file in the order they were added
+    pub(crate) list: Vec<Bookmark>,
+    /// Index of the bookmark being edited
+    editing: Option<usize>,
+    /// Error of the last save of the bookmarks file
+    error: Option<String>,
+    /// Color of a bookmark was changed and is saved once the color picker is released
+    color_changed: bool,
+}
+
+impl HexSession {
+    /// Load the bookmarks of the file from its bookmarks file (`<file>.hexalyzer.json`)
+    pub(crate) fn load_bookmarks(&mut self) -> Result<(), String> {
+        self.bookmarks = Bookmarks::default();
+        if self.ih.filepath.as_os_str().is_empty() {
+            return Ok(());
+        }
+        self.bookmarks.list = load_bookmarks(&self.ih.filepath)?;
+        Ok(())
+    }
+
+    /// Save the bookmarks to the bookmarks file. Bookmarks of files that were not
+    /// loaded from disk are only kept in memory.
+    fn save_bookmarks(&mut self) {
+        if self.ih.filepath.as_os_str().is_empty() {
+            return;
+        }
+        self.bookmarks.error = save_bookmarks(&self.ih.filepath, &self.bookmarks.list).err();
+    }
+
+    /// Bookmark containing the address. Of overlapping bookmarks the last added one is returned.
+    pub(crate) fn bookmark_at(&self, addr: usize) -> Option<&Bookmark> {
+        self.bookmarks.list.iter().rev().find(|b| b.contains(addr))
+    }
+
+    /// Displays the bookmarks: add the selection as bookmark, jump to, edit or delete one
+    pub(crate) fn show_bookmarks_contents(&mut self, ui: &mut egui::Ui) {
+        let selection = self
+            .selection
+            .range
+            .map(|r| [r[0].min(r[1]), r[0].max(r[1])]);
+        if ui
+            .add_enabled(selection.is_some(), egui::Button::new("Add selection"))
+            .on_hover_text("Bookmark the selected bytes")
+            .clicked()
+            && let Some([start, end]) = selection
+        {
+            let count = self.bookmarks.list.len();
+            self.bookmarks.list.push(Bookmark {
+                name: format!("Bookmark {}", count + 1),
+                start,
+                end,
+                color: PALETTE[count % PALETTE.len()],
+                note: String::new(),
+            });
+            self.bookmarks.editing = Some(count);
+            self.save_bookmarks();
+        }
+
+        if let Some(error) = &self.bookmarks.error {
+            ui.label(
+                egui::RichText::new(format!("Bookmarks not saved: {error}"))
+                    .color(colors::WARNING)
+                    .size(12.0),
+            );
+        }
+
+        ui.add_space(3.0);
+
+        let mut jump = None;
+        let mut delete = None;
+        let mut save = false;
+        let mut text_focused = false;
+
+        for (i, bookmark) in self.bookmarks.list.iter_mut().enumerate() {
+            ui.horizontal(|ui| {
+                // Color swatch
+                let (rect, _) =
+                    ui.allocate_exact_size(egui::vec2(12.0, 12.0), egui::Sense::hover());
+                let [r, g, b] = bookmark.color;
+                ui.painter()
+                    .rect_filled(rect, 2.0, egui::Color32::from_rgb(r, g, b));
+
+                let range = format!("0x{:X} - 0x{:X}", bookmark.start, bookmark.end);
+                let hover = if bookmark.note.is_empty() {
+                    range
+                } else {
+                    format!("{range}\n\n{}", bookmark.note)
+                };
+                if ui.link(&bookmark.name).on_hover_text(hover).clicked() {
+                    jump = Some([bookmark.start, bookmark.end]);
+                }
+
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if ui.small_button("Delete").clicked() {
+                        delete = Some(i);
+                    }
+                    if self.bookmarks.editing != Some(i) && ui.small_button("Edit").clicked() {
+                        save |= self.bookmarks.editing.is_some();
+                        self.bookmarks.editing = Some(i);
+                    }
+                });
+            });
+
+            if self.bookmarks.editing == Some(i) {
+                let (focused, edited, color_changed) = show_bookmark_editor(ui, i, bookmark);
+                text_focused |= focused;
+                save |= edited;
+                self.bookmarks.color_changed |= color_changed;
+
+                if ui.button("Done").clicked() {
+                    self.bookmarks.editing = None;
+                    save = true;
+                }
+                ui.add_space(5.0);
+            }
+        }
+
+        if self.bookmarks.list.is_empty() {
+            ui.label(egui::RichText::new("No bookmarks").color(colors::GRAY_160));
+        }
+
+        // Clear the selection to avoid modifying bytes while typing in the bookmark fields
+        if text_focused {
+            self.search.has_focus = false;
+            self.jump_to.has_focus = false;
+            self.selection.clear();
+        }
+
+        // Save a new color right away, but not on every frame of dragging in the color picker
+        if self.bookmarks.color_changed && !ui.input(|i| i.pointer.any_down()) {
+            self.bookmarks.color_changed = false;
+            save = true;
+        }
+
+        if let Some(i) = delete {
+            self.bookmarks.list.remove(i);
+            self.bookmarks.editing = None;
+            save = true;
+        }
+        if save {
+            self.save_bookmarks();
+        }
+
+        // Select the bookmarked range and jump to it
+        if let Some([start, end]) = jump {
+            self.selection.range = Some([start, end]);
+            self.jump_to.addr = Some(start);
+        }
+    }
+}
+
+/// Fields to edit the name, color and note of a bookmark. Returns whether a text field has
+/// focus, whether a text field was left (the bookmarks should be saved) and whether the
+/// color was changed.
+fn show_bookmark_editor(
+    ui: &mut egui::Ui,
+    idx: usize,
+    bookmark: &mut Bookmark,
+) -> (bool, bool, bool) {
+    egui::Grid::new(("bookmark_edit_grid", idx))
+        .num_columns(2)
+        .spacing([10.0, 4.0])
+        .show(ui, |ui| {
+            ui.label("Name");
+            let name = ui.text_edit_singleline(&mut bookmark.name);
+            ui.end_row();
+
+            ui.label("Color");
+            let color = ui.color_edit_button_srgb(&mut bookmark.color);
+            ui.end_row();
+
+            ui.label("Note");
+            let note = ui.add(
+                egui::TextEdit::multiline(&mut bookmark.note)
+                    .desired_rows(3)
+                    .desired_width(f32::INFINITY),
+            );
+            ui.end_row();
+
+            (
+                name.has_focus() || note.has_focus(),
+                name.lost_focus() || note.lost_focus(),
+                color.changed(),
+            )
+        })
+        .inner
+}
@@ -2,8 +2,12 @@ use crate::app::{HexSession, colors};
 use crate::events::EventState;
 use crate::ui_button;
 use eframe::egui;
+use intelhexlib::Symbol;
 use std::ops::Range;
 
+/// Width of the symbol name column next to the row addresses
+const SYMBOL_COLUMN_WIDTH: f32 = 140.0;
+
 impl HexSession {
     /// Displays the central panel of the UI for rendering the hex editor content.
     /// This function draws the main content area of the application. It uses the `egui::CentralPanel`
@@ -109,7 +113,9 @@ impl HexSession {
 
         // Handle arrow key events
         // TODO: jump over empty bytes and up down presses
-        if let Some(r) = self.selection.range.as_mut() {
+        if let Some(r) = self.selection.range.as_mut()
+            && !self.selection.blocked
+        {
             match events.arrow_key_released {
                 Some(egui::Key::ArrowLeft) => {
                     r[0] = r[0].saturating_sub(1);
@@ -155,6 +161,12 @@ impl HexSession {
             // Display address (fixed width, monospaced)
             ui.monospace(format!("{start:08X}"));
 
+            // Display the symbols starting in the row (only if symbols are loaded)
+            if !self.symbols.is_empty() {
+                ui.add_space(8.0);
+                self.draw_row_symbols(ui, start, end);
+            }
+
             // Add space before hex block
             ui.add_space(16.0);
 
@@ -210,6 +222,20 @@ impl HexSession {
                     }
                 }
 
+                // Show the symbol and bookmark the byte belongs to on hover
+                let button = if button.hovered()
+                    && let Some(text) = self.byte_tooltip(addr)
+                {
+                    button.on_hover_text(text)
+                } else {
+                    button
+                };
+
+                // Context menu of the byte
+                if byte.is_some() {
+                    button.context_menu(|ui| self.show_byte_context_menu(ui));
+                }
+
                 // Highlight byte if selected or modified
                 self.highlight_widget(ui, &button, addr, is_selected);
 
@@ -269,6 +295,77 @@ impl HexSession {
         });
     }
 
+    /// Fixed width column with the first symbol starting in the row `[start, end)`.
+    /// All of them are listed on hover.
+    fn draw_row_symbols(&self, ui: &mut egui::Ui, start: usize, end: usize) {
+        let symbols = self.symbols.starting_in(start, end);
+        let text = symbols.first().map_or("", |s| s.name.as_str());
+
+        let response = ui
+            .allocate_ui_with_layout(
+                egui::vec2(SYMBOL_COLUMN_WIDTH, ui.spacing().interact_size.y),
+                egui::Layout::right_to_left(egui::Align::Center),
+                |ui| {
+                    ui.set_min_width(SYMBOL_COLUMN_WIDTH);
+                    ui.add(
+                        egui::Label::new(
+                            egui::RichText::new(text)
+                                .color(colors::GRAY_160)
+                                .monospace(),
+                        )
+                        .truncate(),
+                    )
+                },
+            )
+            .inner;
+
+        if response.hovered() && !symbols.is_empty() {
+            let list: Vec<String> = symbols
+                .iter()
+                .map(|s| format!("0x{:08X}  {}", s.address, s.name))
+                .collect();
+            response.on_hover_text(list.join("\n"));
+        }
+    }
+
+    /// Tooltip of a byte: the symbol, bookmark and template field it belongs to
+    fn byte_tooltip(&self, addr: usize) -> Option<String> {
+        let symbol = self.symbols.symbol_at(addr).map(|s| symbol_offset(s, addr));
+        let bookmark = self
+            .bookmark_at(addr)
+            .map(|b| format!("Bookmark: {}", b.name));
+        let field = self
+            .template_field_at(addr)
+            .map(|idx| format!("Field: {}", self.template.spans[idx].path));
+
+        let lines: Vec<String> = [symbol, bookmark, field].into_iter().flatten().collect();
+        (!lines.is_empty()).then(|| lines.join("\n"))
+    }
+
+    /// Context menu of a byte in the hex view
+    fn show_byte_context_menu(&mut self, ui: &mut egui::Ui) {
+        let selection_start = self.selection.range.map(|r| r[0].min(r[1]));
+        let button = ui.add_enabled(
+            selection_start.is_some(),
+            egui::Button::new("Find references to selection start"),
+        );
+        if button.clicked()
+            && let Some(target) = selection_start
+        {
+            self.find_references(target);
+        }
+
+        let button = ui.add_enabled(
+            selection_start.is_some() && self.has_template(),
+            egui::Button::new("Apply template at selection start"),
+        );
+        if button.clicked()
+            && let Some(addr) = selection_start
+        {
+            self.apply_template_at(addr);
+        }
+    }
+
     fn highlight_widget(
         &self,
         ui: &egui::Ui,
@@ -283,10 +380,19 @@ impl HexSession {
             return;
         }
 
+        if let Some([start, end]) = self.template.hovered
+            && (start..=end).contains(&addr)
+        {
+            // If the template field is hovered in the tree -> highlight (2nd prio)
+            ui.painter()
+                .rect_filled(widget.rect, 0.0, colors::TEMPLATE_HOVER);
+            return;
+        }
+
         if !self.search.results.is_empty() {
-            // If search active -> highlight if inside search results (2nd prio)
-            let is_inside_match = self.search.results.iter().any(|&start| {
-                let end = start.saturating_add(self.search.length);
+            // If search active -> highlight if inside search results (3rd prio)
+            let is_inside_match = self.search.results.iter().enumerate().any(|(idx, &start)| {
+                let end = start.saturating_add(self.search.length_of(idx));
                 (start..end).contains(&addr)
             });
 
@@ -297,8 +403,49 @@ impl HexSession {
         }
 
         if self.editor.modified.contains_key(&addr) {
-            // If modified -> highlight (3rd prio)
+            // If modified -> highlight (4th prio)
             ui.painter().rect_filled(widget.rect, 0.0, colors::MUD);
+            return;
+        }
+
+        if let Some(bookmark) = self.bookmark_at(addr) {
+            // If bookmarked -> color of the bookmark (5th prio)
+            let [r, g, b] = bookmark.color;
+            ui.painter().rect_filled(
+                widget.rect,
+                0.0,
+                egui::Color32::from_rgba_unmultiplied(r, g, b, 48),
+            );
+            return;
         }
+
+        if let Some(idx) = self.template_field_at(addr) {
+            // If inside an applied template -> alternating colors per field (6th prio)
+            ui.painter().rect_filled(
+                widget.rect,
+                0.0,
+                colors::TEMPLATE[idx % colors::TEMPLATE.len()],
+            );
+            return;
+        }
+
+        if let Some(provenance) = self.ih.provenance()
+            && provenance.names().len() > 1
+            && let Some(source) = provenance.source_at(addr)
+        {
+            // If data is merged from multiple files -> color by origin (7th prio)
+            ui.painter()
+                .rect_filled(widget.rect, 0.0, colors::source(source));
+        }
+    }
+}
+
+/// Name of the symbol and the offset of the address in it, e.g. `g_calib_table + 0x10`
+fn symbol_offset(symbol: &Symbol, addr: usize) -> String {
+    let offset = addr - symbol.address;
+    if offset == 0 {
+        symbol.name.clone()
+    } else {
+        format!("{} + 0x{offset:X}", symbol.name)
     }
 }
@@ -0,0 +1,226 @@
+use crate::app::colors;
+use crate::app::{DataState, HexSession};
+use eframe::egui;
+
+/// Height of the entropy plot
+const PLOT_HEIGHT: f32 = 160.0;
+/// Height of the address axis below the plot
+const AXIS_HEIGHT: f32 = 18.0;
+/// Minimum distance between two address labels of the axis
+const AXIS_LABEL_SPACING: f32 = 90.0;
+/// Minimum width of a block's bar; the plot scrolls horizontally if the bars do not fit
+const MIN_BAR_WIDTH: f32 = 2.0;
+
+/// Tool window plotting the entropy of the current file over its addresses
+pub struct EntropyWindow {
+    pub active: bool,
+    /// Size of the analyzed blocks in bytes
+    block_size: usize,
+    /// Block start addresses and their entropy in bits per byte
+    blocks: Vec<(usize, f64)>,
+    /// Scroll id and edit state of the session and block size the entropy was computed with.
+    /// `None` forces a new computation.
+    computed_for: Option<(usize, DataState, usize)>,
+    /// Whether the entropy window or its widgets had focus this frame
+    focused: bool,
+}
+
+impl Default for EntropyWindow {
+    fn default() -> Self {
+        Self {
+            active: false,
+            block_size: 0x1000,
+            blocks: Vec::new(),
+            computed_for: None,
+            focused: false,
+        }
+    }
+}
+
+impl EntropyWindow {
+    /// Returns true if the entropy window was hovered or had keyboard focus
+    /// during the last `show()` call
+    pub const fn has_focus(&self) -> bool {
+        self.focused
+    }
+
+    /// Render the entropy window (if active). Clicking a block jumps to it in the hex view
+    /// of the session.
+    pub fn show(&mut self, ctx: &egui::Context, session: Option<&mut HexSession>) {
+        if !self.active {
+            self.focused = false;
+            return;
+        }
+
+        let mut is_open = self.active;
+        let response = egui::Window::new("Entropy")
+            .open(&mut is_open)
+            .collapsible(false)
+            .resizable(true)
+            .default_size([560.0, 260.0])
+            .show(ctx, |ui| {
+                egui::Frame::NONE
+                    .inner_margin(egui::Margin::symmetric(12, 8))
+                    .show(ui, |ui| {
+                        if let Some(session) = session {
+                            self.show_contents(ui, session);
+                        } else {
+                            ui.label("No file opened");
+                        }
+                    });
+            });
+        self.active = is_open;
+        self.focused = response.is_some_and(|r| r.response.hovered() || r.response.has_focus());
+    }
+
+    fn show_contents(&mut self, ui: &mut egui::Ui, session: &mut HexSession) {
+        ui.horizontal(|ui| {
+            ui.label("Block size:");
+            ui.add(
+                egui::DragValue::new(&mut self.block_size)
+                    .range(16..=0x10_0000)
+                    .hexadecimal(1, false, true)
+                    .prefix("0x"),
+            );
+            if ui
+                .button("Refresh")
+                .on_hover_text("Compute the entropy again")
+                .clicked()
+            {
+                self.computed_for = None;
+            }
+        });
+
+        self.update_blocks(session);
+
+        #[allow(clippy::cast_precision_loss)]
+        let average = if self.blocks.is_empty() {
+            0.0
+        } else {
+            self.blocks.iter().map(|(_, e)| e).sum::<f64>() / self.blocks.len() as f64
+        };
+        ui.label(format!(
+            "Blocks: {}, average entropy: {average:.2} bits per byte",
+            self.blocks.len()
+        ));
+        ui.separator();
+
+        if self.blocks.is_empty() {
+            return;
+        }
+
+        egui::ScrollArea::horizontal()
+            .auto_shrink([false, true])
+            .show(ui, |ui| self.plot(ui, session));
+    }
+
+    /// Draw a bar per block, scaled from 0 to 8 bits per byte, with block addresses below,
+    /// and jump to a block on click. Only the blocks in the visible part of the scroll area
+    /// are painted.
+    #[allow(
+        clippy::cast_precision_loss,
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss
+    )]
+    fn plot(&self, ui: &mut egui::Ui, session: &mut HexSession) {
+        let count = self.blocks.len() as f32;
+        let bar_width = (ui.available_width() / count).max(MIN_BAR_WIDTH);
+        let size = egui::vec2(bar_width * count, PLOT_HEIGHT + AXIS_HEIGHT);
+        let (response, painter) = ui.allocate_painter(size, egui::Sense::click());
+        let rect = egui::Rect::from_min_size(
+            response.rect.min,
+            egui::vec2(response.rect.width(), PLOT_HEIGHT),
+        );
+
+        // Guide lines at 0, 4 and 8 bits per byte
+        for level in [0.0, 4.0, 8.0] {
+            let y = rect.height().mul_add(-level / 8.0, rect.bottom());
+            painter.hline(
+                rect.x_range(),
+                y,
+                egui::Stroke::new(1.0, colors::GRAY_160.gamma_multiply(0.3)),
+            );
+        }
+
+        // Blocks in the visible part of the plot
+        let clip = painter.clip_rect();
+        let first = ((clip.left() - rect.left()) / bar_width).floor().max(0.0) as usize;
+        let last = (((clip.right() - rect.left()) / bar_width).ceil().max(0.0) as usize)
+            .min(self.blocks.len());
+
+        // Index of the block under the pointer
+        let hovered = response.hover_pos().and_then(|pos| {
+            let idx = ((pos.x - rect.left()) / bar_width) as usize;
+            (idx < self.blocks.len() && pos.y <= rect.bottom()).then_some(idx)
+        });
+
+        for (idx, (_, entropy)) in self.blocks.iter().enumerate().take(last).skip(first) {
+            let height = rect.height() * (*entropy as f32) / 8.0;
+            let left = bar_width.mul_add(idx as f32, rect.left());
+            let bar = egui::Rect::from_min_max(
+                egui::pos2(left, rect.bottom() - height.max(1.0)),
+                egui::pos2(left + (bar_width - 1.0).max(1.0), rect.bottom()),
+            );
+            let color = if hovered == Some(idx) {
+                colors::GRAY_210
+            } else {
+                entropy_color(*entropy)
+            };
+            painter.rect_filled(bar, 0.0, color);
+        }
+
+        // Start address of every n-th block, spaced at least `AXIS_LABEL_SPACING` apart
+        let every = (AXIS_LABEL_SPACING / bar_width).ceil().max(1.0) as usize;
+        for idx in (first / every * every..last).step_by(every) {
+            let x = bar_width.mul_add(idx as f32, rect.left());
+            painter.vline(
+                x,
+                rect.bottom()..=rect.bottom() + 4.0,
+                egui::Stroke::new(1.0, colors::GRAY_160),
+            );
+            painter.text(
+                egui::pos2(x, rect.bottom() + 5.0),
+                egui::Align2::LEFT_TOP,
+                format!("0x{:X}", self.blocks[idx].0),
+                egui::FontId::monospace(10.0),
+                colors::GRAY_160,
+            );
+        }
+
+        if let Some(idx) = hovered {
+            let (addr, entropy) = self.blocks[idx];
+            let response = response
+                .on_hover_text_at_pointer(format!("0x{addr:08X}: {entropy:.3} bits per byte"));
+            if response.clicked() {
+                session.jump_to.addr = Some(addr);
+            }
+        }
+    }
+
+    /// Compute the entropy if the session, its data or the block size changed
+    fn update_blocks(&mut self, session: &HexSession) {
+        let key = (session.scroll_id, session.data_state(), self.block_size);
+        if self.computed_for != Some(key) {
+            self.blocks = session.ih.entropy(self.block_size).unwrap_or_default();
+            self.computed_for = Some(key);
+        }
+    }
+}
+
+/// Color of a bar: gray for erased or padded data, blue for code and data, red for
+/// compressed or encrypted data
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn entropy_color(entropy: f64) -> egui::Color32 {
+    if entropy >= 7.5 {
+        return egui::Color32::from_rgb(200, 70, 60);
+    }
+    if entropy < 1.0 {
+        return colors::GRAY_160.gamma_multiply(0.6);
+    }
+    let t = ((entropy - 1.0) / 6.5) as f32;
+    egui::Color32::from_rgb(
+        140.0f32.mul_add(t, 60.0) as u8,
+        (-30.0f32).mul_add(t, 120.0) as u8,
+        (-110.0f32).mul_add(t, 190.0) as u8,
+    )
+}
@@ -1,8 +1,27 @@
-use crate::app::{Endianness, HexSession};
+use crate::app::{Endianness, HexSession, colors};
 use eframe::egui;
 use eframe::egui::Ui;
+use intelhexlib::ScalarType;
 use std::time::{Duration, Instant};
 
+/// Value being edited in the inspector: start address, type and entered text
+type InspectorEdit = (usize, ScalarType, String);
+
+#[derive(Default)]
+pub struct Inspector {
+    /// Value being edited
+    editing: Option<InspectorEdit>,
+    /// Error of the last edit
+    error: Option<String>,
+}
+
+impl Inspector {
+    /// Whether a value is being edited
+    pub(crate) const fn is_editing(&self) -> bool {
+        self.editing.is_some()
+    }
+}
+
 #[allow(clippy::needless_pass_by_value)]
 /// Format the number so that it has separators (for readability)
 pub fn format_with_separators<T: ToString>(n: T) -> String {
@@ -138,6 +157,20 @@ impl HexSession {
         ui.add_space(5.0);
         ui.separator();
 
+        // Drop the edit if the selection has changed since it started
+        let span = self
+            .selection
+            .range
+            .map(|[start, end]| (start.min(end), start.abs_diff(end) + 1));
+        let editing = &mut self.inspector.editing;
+        if editing
+            .as_ref()
+            .is_some_and(|(addr, scalar, _)| span != Some((*addr, scalar.size())))
+        {
+            *editing = None;
+        }
+
+        let mut commit: Option<InspectorEdit> = None;
         egui::Grid::new("data_inspector_grid")
             .num_columns(2) // two columns: label & value
             .spacing([20.0, 4.0]) // horizontal & vertical spacing
@@ -167,6 +200,21 @@ impl HexSession {
                     }
                 }
 
+                // Only values without gaps can be edited
+                let edit_addr = (bytes.len() == max - min + 1).then_some(min);
+                let mut value = |ui: &mut Ui, name: &str, scalar, display: &str, copy: &str| {
+                    ui.label(name);
+                    let Some(addr) = edit_addr else {
+                        copyable_value(ui, name, display, copy);
+                        return;
+                    };
+                    if let Some(input) =
+                        editable_value(ui, name, display, copy, (addr, scalar), editing)
+                    {
+                        commit = Some((addr, scalar, input));
+                    }
+                };
+
                 if self.endianness == Endianness::Big && bytes.len() > 1 {
                     bytes.reverse();
                 }
@@ -175,14 +223,12 @@ impl HexSession {
                     1 => {
                         let val_u8 = u8::from_le_bytes([bytes[0]]);
                         let v = val_u8.to_string();
-                        ui.label("u8");
-                        copyable_value(ui, "u8", &v, &v);
+                        value(ui, "u8", ScalarType::Unsigned(1), &v, &v);
                         ui.end_row();
 
                         let val_i8 = i8::from_le_bytes([bytes[0]]);
                         let v = val_i8.to_string();
-                        ui.label("i8");
-                        copyable_value(ui, "i8", &v, &v);
+                        value(ui, "i8", ScalarType::Signed(1), &v, &v);
                         ui.end_row();
 
                         let val_bin = format!("{val_u8:08b}");
@@ -192,10 +238,10 @@ impl HexSession {
                     2 => {
                         let val_u16 =
                             u16::from_le_bytes(bytes.as_slice().try_into().unwrap_or_default());
-                        ui.label("u16");
-                        copyable_value(
+                        value(
                             ui,
                             "u16",
+                            ScalarType::Unsigned(2),
                             &format_with_separators(val_u16),
                             &val_u16.to_string(),
                         );
@@ -203,10 +249,10 @@ impl HexSession {
 
                         let val_i16 =
                             i16::from_le_bytes(bytes.as_slice().try_into().unwrap_or_default());
-                        ui.label("i16");
-                        copyable_value(
+                        value(
                             ui,
                             "i16",
+                            ScalarType::Signed(2),
                             &format_with_separators(val_i16),
                             &val_i16.to_string(),
                         );
@@ -219,10 +265,10 @@ impl HexSession {
                     4 => {
                         let val_u32 =
                             u32::from_le_bytes(bytes.as_slice().try_into().unwrap_or_default());
-                        ui.label("u32");
-                        copyable_value(
+                        value(
                             ui,
                             "u32",
+                            ScalarType::Unsigned(4),
                             &format_with_separators(val_u32),
                             &val_u32.to_string(),
                         );
@@ -230,10 +276,10 @@ impl HexSession {
 
                         let val_i32 =
                             i32::from_le_bytes(bytes.as_slice().try_into().unwrap_or_default());
-                        ui.label("i32");
-                        copyable_value(
+                        value(
                             ui,
                             "i32",
+                            ScalarType::Signed(4),
                             &format_with_separators(val_i32),
                             &val_i32.to_string(),
                         );
@@ -241,10 +287,10 @@ impl HexSession {
 
                         let val_f32 =
                             f32::from_le_bytes(bytes.as_slice().try_into().unwrap_or_default());
-                        ui.label("f32");
-                        copyable_value(
+                        value(
                             ui,
                             "f32",
+                            ScalarType::Float(4),
                             &format_float(val_f32),
                             &format_float_plain(val_f32),
                         );
@@ -288,10 +334,10 @@ impl HexSession {
                     8 => {
                         let val_u64 =
                             u64::from_le_bytes(bytes.as_slice().try_into().unwrap_or_default());
-                        ui.label("u64");
-                        copyable_value(
+                        value(
                             ui,
                             "u64",
+                            ScalarType::Unsigned(8),
                             &format_with_separators(val_u64),
                             &val_u64.to_string(),
                         );
@@ -299,10 +345,10 @@ impl HexSession {
 
                         let val_i64 =
                             i64::from_le_bytes(bytes.as_slice().try_into().unwrap_or_default());
-                        ui.label("i64");
-                        copyable_value(
+                        value(
                             ui,
                             "i64",
+                            ScalarType::Signed(8),
                             &format_with_separators(val_i64),
                             &val_i64.to_string(),
                         );
@@ -310,10 +356,10 @@ impl HexSession {
 
                         let val_f64 =
                             f64::from_le_bytes(bytes.as_slice().try_into().unwrap_or_default());
-                        ui.label("f64");
-                        copyable_value(
+                        value(
                             ui,
                             "f64",
+                            ScalarType::Float(8),
                             &format_float(val_f64),
                             &format_float_plain(val_f64),
                         );
@@ -355,12 +401,96 @@ impl HexSession {
                     }
                 }
             });
+
+        if let Some(error) = &self.inspector.error {
+            ui.label(egui::RichText::new(error).color(colors::WARNING).size(12.0));
+        }
+
+        // Keep the typed value from going to the search and jump-to fields
+        if self.inspector.is_editing() {
+            self.inspector.error = None;
+            self.search.has_focus = false;
+            self.jump_to.has_focus = false;
+        }
+
+        if let Some((addr, scalar, input)) = commit {
+            self.inspector.error = self.write_value(addr, scalar, &input).err();
+        }
+    }
+
+    /// Encode the value entered for the type with the selected endianness and write it
+    /// at the address. Changed bytes are tracked as modified.
+    fn write_value(&mut self, addr: usize, scalar: ScalarType, input: &str) -> Result<(), String> {
+        let big_endian = self.endianness == Endianness::Big;
+        let bytes = scalar
+            .encode(input.trim().replace(',', "").as_str(), big_endian)
+            .ok_or_else(|| format!("Invalid value: {input}"))?;
+
+        self.write_bytes("Edit value", addr, &bytes)
+    }
+}
+
+/// Renders a copyable value (see `copyable_value`) of the type at the address that turns
+/// into a text field on double-click. Returns the entered text when committed with Enter.
+fn editable_value(
+    ui: &mut Ui,
+    type_label: &str,
+    display: &str,
+    copy: &str,
+    target: (usize, ScalarType),
+    editing: &mut Option<InspectorEdit>,
+) -> Option<String> {
+    let Some((_, _, input)) = editing
+        .as_mut()
+        .filter(|(addr, scalar, _)| (*addr, *scalar) == target)
+    else {
+        let response = copyable_label(
+            ui,
+            type_label,
+            display,
+            copy,
+            "Click to copy, double-click to edit",
+        );
+        if response.double_clicked() {
+            *editing = Some((target.0, target.1, copy.to_string()));
+        }
+        return None;
+    };
+
+    let text = ui.add(
+        egui::TextEdit::singleline(input)
+            .desired_width(140.0)
+            .font(egui::TextStyle::Monospace),
+    );
+    // Focus the text field when the editing starts
+    if !text.has_focus() && !text.lost_focus() {
+        text.request_focus();
     }
+    if !text.lost_focus() {
+        return None;
+    }
+
+    let committed = ui
+        .input(|i| i.key_pressed(egui::Key::Enter))
+        .then(|| input.clone());
+    *editing = None;
+    committed
 }
 
 /// Renders a clickable label that copies `copy` text to clipboard on click.
 /// Displays a "Copied!" tooltip for 1.2 seconds after clicking, or "Click to copy" on hover.
 fn copyable_value(ui: &mut Ui, type_label: &str, display: &str, copy: &str) {
+    copyable_label(ui, type_label, display, copy, "Click to copy");
+}
+
+/// Renders the label of `copyable_value` with the hover text
+fn copyable_label(
+    ui: &mut Ui,
+    type_label: &str,
+    display: &str,
+    copy: &str,
+    hover: &str,
+) -> egui::Response {
     let response = ui.add(
         egui::Label::new(display)
             .selectable(false)
@@ -373,19 +503,19 @@ fn copyable_value(ui: &mut Ui, type_label: &str, display: &str, copy: &str) {
     let copied_at: Option<Instant> = ui.data(|d| d.get_temp(id));
     let is_recently_copied = copied_at.is_some_and(|t| t.elapsed() < Duration::from_secs_f32(1.2));
 
+    if clicked {
+        ui.ctx().copy_text(copy.to_string());
+        ui.data_mut(|d| d.insert_temp(id, Instant::now()));
+        ui.ctx().request_repaint_after(Duration::from_secs_f32(1.2));
+    }
+
     if is_recently_copied {
         response.show_tooltip_text("Copied!");
-        response.on_hover_cursor(egui::CursorIcon::PointingHand);
+        response.on_hover_cursor(egui::CursorIcon::PointingHand)
     } else {
         response
             .on_hover_cursor(egui::CursorIcon::PointingHand)
-            .on_hover_text("Click to copy");
-    }
-
-    if clicked {
-        ui.ctx().copy_text(copy.to_string());
-        ui.data_mut(|d| d.insert_temp(id, Instant::now()));
-        ui.ctx().request_repaint_after(Duration::from_secs_f32(1.2));
+            .on_hover_text(hover)
     }
 }
 
@@ -23,8 +23,14 @@ impl JumpTo {
 impl HexSession {
     /// Displays the `JumpTo` panel for jumping to a specific address.
     pub(crate) fn show_jumpto_contents(&mut self, ui: &mut egui::Ui, events: &EventState) {
+        let hint = if self.symbols.is_empty() {
+            "address"
+        } else {
+            "address or symbol"
+        };
         let textedit = ui.add(
             egui::TextEdit::singleline(&mut self.jump_to.input)
+                .hint_text(hint)
                 .desired_width(ui.available_width() - 30.0),
         );
 
@@ -58,6 +64,16 @@ impl HexSession {
         }
 
         if events.enter_released && self.jump_to.has_focus {
+            // Select the whole symbol if the input is a symbol name
+            if let Some(symbol) = self.symbols.find(self.jump_to.input.trim()) {
+                self.jump_to.addr = Some(symbol.address);
+                if self.ih.read_byte(symbol.address).is_some() {
+                    let end = symbol.address + symbol.size.max(1) - 1;
+                    self.selection.range = Some([symbol.address, end]);
+                }
+                return;
+            }
+
             self.jump_to.addr = usize::from_str_radix(&self.jump_to.input, 16).ok();
 
             // Select the byte we just jumped to
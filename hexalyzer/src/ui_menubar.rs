@@ -1,8 +1,10 @@
 use crate::HexViewerApp;
 use crate::app::HexSession;
 use crate::loader;
+use crate::settings::Settings;
 use crate::ui_popup::PopupState;
 use eframe::egui;
+use intelhexlib::{Journal, Transform};
 
 impl HexViewerApp {
     /// Displays the top menu bar with File, Edit, View, and About buttons
@@ -58,15 +60,52 @@ impl HexViewerApp {
                 }
             }
 
-            // GAP FILL SUBMENU
-            ui.menu_button("Gap Fill", |ui| {
-                ui.label(egui::RichText::new(
-                    "Fill byte for gaps when\nexporting to BIN",
-                ));
-                ui.separator();
-                ui.radio_value(&mut self.gap_fill, 0x00, "0x00");
-                ui.radio_value(&mut self.gap_fill, 0xFF, "0xFF");
-            });
+            // EXPORT RANGE BUTTON
+            let has_data = self.get_curr_session().is_some_and(|s| s.ih.size != 0);
+            if ui
+                .add_enabled(has_data, egui::Button::new("Export BIN range..."))
+                .clicked()
+                && !self.popup.active
+                && let Some(curr_session) = self.get_curr_session()
+            {
+                let start = curr_session.ih.get_min_addr().unwrap_or(0);
+                let end = curr_session.ih.get_max_addr().unwrap_or(0);
+                self.popup.open(PopupState::ExportRange {
+                    start: format!("{start:X}"),
+                    end: format!("{end:X}"),
+                    strict: false,
+                });
+            }
+
+            // LOAD SYMBOLS BUTTON
+            if ui
+                .add_enabled(has_file, egui::Button::new("Load symbols..."))
+                .on_hover_text("Load symbols from a GNU ld / IAR / Keil map file or an ELF file")
+                .clicked()
+                && let Some(path) = rfd::FileDialog::new()
+                    .set_title("Load Symbols")
+                    .add_filter("Map / ELF", &["map", "elf", "axf", "out"])
+                    .add_filter("All files", &["*"])
+                    .pick_file()
+            {
+                self.load_symbols(&path);
+            }
+
+            // LOAD TEMPLATE BUTTON
+            if ui
+                .add_enabled(has_file, egui::Button::new("Load template..."))
+                .on_hover_text("Load a C header with struct definitions to decode at an address")
+                .clicked()
+                && let Some(path) = rfd::FileDialog::new()
+                    .set_title("Load Structure Template")
+                    .add_filter("C header", &["h", "hpp", "txt"])
+                    .add_filter("All files", &["*"])
+                    .pick_file()
+            {
+                self.load_template(&path);
+            }
+
+            self.file_settings_items(ui);
 
             // RELOAD BUTTON
             let has_filepath = self
@@ -106,20 +145,100 @@ impl HexViewerApp {
         });
     }
 
+    fn file_settings_items(&mut self, ui: &mut egui::Ui) {
+        let before = Settings {
+            gap_fill: self.gap_fill,
+            backups: self.backups,
+        };
+
+        // GAP FILL SUBMENU
+        ui.menu_button("Gap Fill", |ui| {
+            ui.label(egui::RichText::new(
+                "Fill byte for gaps when\nexporting to BIN",
+            ));
+            ui.separator();
+            ui.radio_value(&mut self.gap_fill, 0x00, "0x00");
+            ui.radio_value(&mut self.gap_fill, 0xFF, "0xFF");
+        });
+
+        // BACKUPS SUBMENU
+        ui.menu_button("Backups on Save", |ui| {
+            ui.label(egui::RichText::new(
+                "Number of <file>.N.bak copies of\nthe original kept when saving",
+            ));
+            ui.separator();
+            ui.radio_value(&mut self.backups, 0, "Off");
+            ui.radio_value(&mut self.backups, 1, "1");
+            ui.radio_value(&mut self.backups, 3, "3");
+            ui.radio_value(&mut self.backups, 5, "5");
+        });
+
+        self.save_settings_if_changed(before);
+    }
+
     fn edit_menu(&mut self, ui: &mut egui::Ui) {
         ui.menu_button("Edit", |ui| {
+            self.edit_history_items(ui);
+            ui.separator();
             self.edit_popup_items(ui);
             ui.separator();
+            self.edit_transform_items(ui);
+            ui.separator();
             self.edit_copy_items(ui);
         });
     }
 
+    fn edit_history_items(&mut self, ui: &mut egui::Ui) {
+        let journal = self.get_curr_session().and_then(|s| s.ih.journal());
+        let undo_label = journal.and_then(Journal::undo_label).map(str::to_string);
+        let redo_label = journal.and_then(Journal::redo_label).map(str::to_string);
+
+        let (undo_shortcut, redo_shortcut) = if cfg!(target_os = "macos") {
+            ("Cmd+Z", "Shift+Cmd+Z")
+        } else {
+            ("Ctrl+Z", "Ctrl+Shift+Z")
+        };
+
+        // UNDO BUTTON
+        let text = undo_label
+            .as_ref()
+            .map_or_else(|| "Undo".to_string(), |l| format!("Undo {l}"));
+        if ui
+            .add_enabled(
+                undo_label.is_some(),
+                egui::Button::new(text).shortcut_text(undo_shortcut),
+            )
+            .clicked()
+            && let Some(curr_session) = self.get_curr_session_mut()
+        {
+            curr_session.undo();
+        }
+
+        // REDO BUTTON
+        let text = redo_label
+            .as_ref()
+            .map_or_else(|| "Redo".to_string(), |l| format!("Redo {l}"));
+        if ui
+            .add_enabled(
+                redo_label.is_some(),
+                egui::Button::new(text).shortcut_text(redo_shortcut),
+            )
+            .clicked()
+            && let Some(curr_session) = self.get_curr_session_mut()
+        {
+            curr_session.redo();
+        }
+    }
+
     fn edit_popup_items(&mut self, ui: &mut egui::Ui) {
         let has_file = self.get_curr_session().is_some();
         let has_data = self.get_curr_session().is_some_and(|s| s.ih.size != 0);
         let has_modifications = self
             .get_curr_session()
             .is_some_and(|s| !s.editor.modified.is_empty());
+        let can_revert = self.active_index.is_some_and(|i| {
+            self.has_unsaved_changes(i) && self.sessions.get(i).is_some_and(|s| s.saved.is_some())
+        });
 
         // RELOCATE BUTTON
         if ui
@@ -184,6 +303,48 @@ impl HexViewerApp {
         {
             curr_session.restore();
         }
+
+        // REVERT BUTTON
+        if ui
+            .add_enabled(can_revert, egui::Button::new("Revert to saved"))
+            .clicked()
+            && let Some(curr_session) = self.get_curr_session_mut()
+        {
+            curr_session.revert_to_saved();
+        }
+    }
+
+    fn edit_transform_items(&mut self, ui: &mut egui::Ui) {
+        let has_selection = self
+            .get_curr_session()
+            .is_some_and(|s| s.selection.range.is_some());
+
+        ui.add_enabled_ui(has_selection, |ui| {
+            ui.menu_button("Transform Selection", |ui| {
+                let transforms = [
+                    ("Swap 16-bit words", Transform::Swap16),
+                    ("Swap 32-bit words", Transform::Swap32),
+                    ("Swap 64-bit words", Transform::Swap64),
+                    ("Reverse bits", Transform::ReverseBits),
+                    ("Invert", Transform::Invert),
+                ];
+
+                for (label, transform) in transforms {
+                    if ui.button(label).clicked()
+                        && let Some(curr_session) = self.get_curr_session_mut()
+                        && let Err(err) = curr_session.transform_selection(&transform)
+                    {
+                        self.error.replace(err);
+                    }
+                }
+
+                ui.separator();
+
+                if ui.button("XOR with Key...").clicked() && !self.popup.active {
+                    self.popup.open(PopupState::XorKey { key: String::new() });
+                }
+            });
+        });
     }
 
     fn edit_copy_items(&self, ui: &mut egui::Ui) {
@@ -251,6 +412,28 @@ impl HexViewerApp {
             if ui.button("Hex Converter").clicked() {
                 self.converter.active = true;
             }
+            if ui.button("Strings").clicked() {
+                self.strings.active = true;
+            }
+            if ui.button("Entropy").clicked() {
+                self.entropy.active = true;
+            }
+            if ui.button("Signatures").clicked() {
+                self.signatures.active = true;
+            }
+            if ui.button("Symbols").clicked() {
+                self.symbols.active = true;
+            }
+            if ui
+                .add_enabled(
+                    self.get_curr_session().is_some(),
+                    egui::Button::new("Structure Template"),
+                )
+                .clicked()
+                && let Some(curr_session) = self.get_curr_session_mut()
+            {
+                curr_session.template.open = true;
+            }
         });
     }
 
@@ -3,6 +3,7 @@ use crate::app::colors;
 use crate::events;
 use crate::loader;
 use eframe::egui;
+use intelhexlib::{IntelHexError, Transform};
 use std::path::PathBuf;
 
 //  ========================== Close Action ================================== //
@@ -36,6 +37,14 @@ pub enum PopupState {
         start: String,
         end: String,
     },
+    XorKey {
+        key: String,
+    },
+    ExportRange {
+        start: String,
+        end: String,
+        strict: bool,
+    },
     CloseConfirm {
         session_id: usize,
         reload_after: bool,
@@ -51,6 +60,8 @@ impl PopupState {
             Self::Merge { .. } => "Merge",
             Self::InsertRange { .. } => "Insert Range",
             Self::RemoveRange { .. } => "Remove Range",
+            Self::XorKey { .. } => "XOR Selection",
+            Self::ExportRange { .. } => "Export BIN Range",
             Self::CloseConfirm { .. } => "Unsaved Changes",
         }
     }
@@ -97,6 +108,17 @@ impl PopupState {
                 Self::show_hex_field(ui, "End address (inclusive):", end);
                 ui.button(" OK ").clicked() || events.enter_released
             }
+            Self::XorKey { key } => {
+                Self::show_hex_input(ui, "XOR key (repeats over the selection):", key, usize::MAX);
+                ui.button(" OK ").clicked() || events.enter_released
+            }
+            Self::ExportRange { start, end, strict } => {
+                Self::show_hex_field(ui, "Start address:", start);
+                Self::show_hex_field(ui, "End address (inclusive):", end);
+                ui.checkbox(strict, "Fail if data lies outside of the range");
+                ui.add_space(8.0);
+                ui.button(" OK ").clicked() || events.enter_released
+            }
             Self::CloseConfirm { .. } => {
                 ui.label("This file has unsaved changes. What would you like to do?");
                 ui.add_space(10.0);
@@ -142,7 +164,13 @@ impl PopupState {
         false
     }
 
+    /// Hex input of an address (up to 8 hex digits)
     fn show_hex_field(ui: &mut egui::Ui, label: &str, value: &mut String) {
+        Self::show_hex_input(ui, label, value, 8);
+    }
+
+    /// Hex input that accepts up to `max_len` hex digits
+    fn show_hex_input(ui: &mut egui::Ui, label: &str, value: &mut String, max_len: usize) {
         ui.vertical(|ui| {
             ui.add_space(3.0);
             ui.label(label);
@@ -157,7 +185,7 @@ impl PopupState {
 
                 if response.changed() {
                     value.retain(|c| c.is_ascii_hexdigit());
-                    value.truncate(8);
+                    value.truncate(max_len);
                 }
             });
         });
@@ -165,6 +193,7 @@ impl PopupState {
         ui.add_space(8.0);
     }
 
+    #[allow(clippy::too_many_lines)]
     /// Execute the action for a confirmed popup
     fn on_confirm(self, app: &mut HexViewerApp) {
         match self {
@@ -180,17 +209,21 @@ impl PopupState {
 
                 let old_start_addr = curr_session.ih.get_min_addr();
 
-                if let Err(err) = curr_session.ih.relocate(addr) {
+                let res = curr_session.transaction("Relocate", |session| {
+                    session.ih.relocate(addr)?;
+                    if let Some(old_start_addr) = old_start_addr {
+                        session.editor.remap_modified(addr, old_start_addr);
+                    }
+                    Ok::<(), IntelHexError>(())
+                });
+
+                if let Err(err) = res {
                     app.error.replace(err.to_string());
                     return;
                 }
 
                 curr_session.dirty = true;
 
-                if let Some(old_start_addr) = old_start_addr {
-                    curr_session.editor.remap_modified(addr, old_start_addr);
-                }
-
                 curr_session.addr = curr_session.ih.get_min_addr().unwrap_or(0)
                     ..=curr_session.ih.get_max_addr().unwrap_or(0);
                 curr_session.search.redo();
@@ -208,7 +241,9 @@ impl PopupState {
                     return;
                 };
 
-                if let Err(err) = curr_session.ih.write_range(start, end) {
+                if let Err(err) = curr_session
+                    .transaction("Insert range", |session| session.ih.write_range(start, end))
+                {
                     app.error.replace(err.to_string());
                     return;
                 }
@@ -234,7 +269,9 @@ impl PopupState {
 
                 let size_before = curr_session.ih.size;
 
-                if let Err(err) = curr_session.ih.remove_range(start, end) {
+                if let Err(err) = curr_session.transaction("Remove range", |session| {
+                    session.ih.remove_range(start, end)
+                }) {
                     app.error.replace(err.to_string());
                     return;
                 }
@@ -264,6 +301,30 @@ impl PopupState {
                     curr_session.search.redo();
                 }
             }
+            Self::XorKey { key } => {
+                let Ok(transform) = format!("xor:{key}").parse::<Transform>() else {
+                    app.error
+                        .replace("Invalid key format: expected whole bytes in hex".to_string());
+                    return;
+                };
+
+                if let Some(curr_session) = app.get_curr_session_mut()
+                    && let Err(err) = curr_session.transform_selection(&transform)
+                {
+                    app.error.replace(err);
+                }
+            }
+            Self::ExportRange { start, end, strict } => {
+                let start_addr = usize::from_str_radix(&start, 16).ok();
+                let end_addr = usize::from_str_radix(&end, 16).ok();
+
+                let Some((start, end)) = start_addr.zip(end_addr) else {
+                    app.error.replace("Invalid address format".to_string());
+                    return;
+                };
+
+                Self::export_range_dialog(app, start, end, strict);
+            }
             Self::Error(_) | Self::About => {}
             Self::CloseConfirm {
                 session_id,
@@ -310,6 +371,37 @@ impl PopupState {
         }
     }
 
+    /// Show the Save dialog and export the address window `[start, end]` of the current
+    /// session to a BIN file.
+    fn export_range_dialog(app: &mut HexViewerApp, start: usize, end: usize, strict: bool) {
+        let gap_fill = app.gap_fill;
+
+        let Some(session) = app.get_curr_session_mut() else {
+            return;
+        };
+
+        let Some(mut path) = rfd::FileDialog::new()
+            .set_title("Export BIN Range")
+            .set_file_name(session.name.clone())
+            .add_filter("Binary", &["bin"])
+            .save_file()
+        else {
+            return;
+        };
+
+        path.set_extension("bin");
+
+        let res = if strict {
+            session.ih.write_bin_range_safe(&path, start, end, gap_fill)
+        } else {
+            session.ih.write_bin_range(&path, start, end, gap_fill)
+        };
+
+        if let Err(err) = res {
+            app.error.replace(err.to_string());
+        }
+    }
+
     /// Show the Save-As dialog for the current session. Returns `true` on success.
     fn save_as_dialog(app: &mut HexViewerApp) -> bool {
         let path = app.get_curr_session().and_then(|s| {
@@ -1,21 +1,147 @@
 use crate::app::HexSession;
 use crate::events::EventState;
 use eframe::egui;
+use intelhexlib::{
+    MaskedPattern, NumericValue, POINTER_ENCODINGS, Reference, SearchOptions, ValueEncoding,
+};
+use std::fmt;
+use std::time::{Duration, Instant};
+
+/// Time a search may take per frame, so the UI stays responsive
+const SEARCH_FRAME_TIME: Duration = Duration::from_millis(8);
+/// Bytes of data the first step of a search scans. The step size then adapts to the
+/// frame time, between `SEARCH_STEP_MIN` and `SEARCH_STEP_MAX`.
+const SEARCH_STEP_INITIAL: usize = 1024 * 1024;
+const SEARCH_STEP_MIN: usize = 64 * 1024;
+const SEARCH_STEP_MAX: usize = 256 * 1024 * 1024;
 
 #[derive(Default, PartialEq, Clone)]
 enum SearchMode {
     #[default]
     Hex,
+    Masked,
+    Value,
     Ascii,
     Regex,
+    References,
 }
 
 #[derive(Default, PartialEq, Clone)]
 struct SearchState {
     /// User input
     input: String,
-    /// Search mode: byte / ASCII literals / ASCII regex
+    /// Search mode: byte / masked byte / numeric value / ASCII literals / ASCII regex /
+    /// references to an address
     mode: SearchMode,
+    /// Number of bytes a byte search match may differ from the pattern in
+    max_mismatches: usize,
+    /// First address of the search window (hex, empty for the start of the data)
+    from: String,
+    /// Last address of the search window (hex, empty for the end of the data)
+    to: String,
+    /// Maximum number of search results (0 for no limit)
+    max_results: usize,
+    /// Search across gaps treated as filled with `fill`
+    fill_gaps: bool,
+    /// Gap fill byte (hex, empty for 0xFF)
+    fill: String,
+}
+
+impl SearchState {
+    /// Search options of the inputs. Returns `None` if an input is not valid.
+    fn options(&self) -> Option<SearchOptions> {
+        let parse_addr = |s: &str| usize::from_str_radix(s.trim().trim_start_matches("0x"), 16);
+
+        let from = if self.from.trim().is_empty() {
+            None
+        } else {
+            Some(parse_addr(&self.from).ok()?)
+        };
+        let to = if self.to.trim().is_empty() {
+            None
+        } else {
+            Some(parse_addr(&self.to).ok()?)
+        };
+        let range = match (from, to) {
+            (None, None) => None,
+            (from, to) => Some((from.unwrap_or(0), to.unwrap_or(usize::MAX))),
+        };
+        if range.is_some_and(|(from, to)| from > to) {
+            return None;
+        }
+
+        let gap_fill = if self.fill_gaps && self.fill.trim().is_empty() {
+            Some(0xFF)
+        } else if self.fill_gaps {
+            Some(u8::from_str_radix(self.fill.trim().trim_start_matches("0x"), 16).ok()?)
+        } else {
+            None
+        };
+
+        Some(SearchOptions {
+            range,
+            max_results: (self.max_results > 0).then_some(self.max_results),
+            gap_fill,
+        })
+    }
+}
+
+/// Description of a search result, formatted only when it is shown
+#[derive(Clone, Copy)]
+enum ResultLabel {
+    /// Number of bytes an approximate match differs from the pattern in
+    Mismatches(usize),
+    /// Encoding a numeric value was found in
+    Value(ValueEncoding),
+    /// Encoding of a word pointing to the searched address
+    Pointer(ValueEncoding),
+}
+
+impl fmt::Display for ResultLabel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Mismatches(n) => write!(f, "{n} mismatches"),
+            Self::Value(encoding) => write!(f, "{encoding}"),
+            Self::Pointer(encoding) => write!(f, "{encoding} pointer"),
+        }
+    }
+}
+
+/// What a search in progress looks for
+enum PendingKind {
+    /// Exact byte pattern
+    Bytes(Vec<u8>),
+    /// Byte pattern with up to the given number of mismatching bytes
+    Hamming(Vec<u8>, usize),
+    /// Byte pattern with wildcards and masks
+    Masked(MaskedPattern),
+    /// Numeric value in all of its encodings, with the size of the longest one
+    Value(NumericValue, usize),
+}
+
+impl PendingKind {
+    /// Length of the longest match in bytes
+    const fn len(&self) -> usize {
+        match self {
+            Self::Bytes(pattern) | Self::Hamming(pattern, _) => pattern.len(),
+            Self::Masked(pattern) => pattern.len(),
+            Self::Value(_, len) => *len,
+        }
+    }
+}
+
+/// Search that is still in progress
+struct PendingSearch {
+    /// Pattern to search for
+    kind: PendingKind,
+    /// Search window, maximum number of results and gap fill of the whole search
+    options: SearchOptions,
+    /// Address to continue the search from
+    next: usize,
+    /// Bytes of data to scan in the next step
+    step: usize,
+    /// Scroll to the first result as soon as it is found
+    scroll_to_first: bool,
 }
 
 #[derive(Default)]
@@ -26,10 +152,16 @@ pub struct Search {
     pub(crate) results: Vec<usize>,
     /// Length of the search pattern in bytes
     pub(crate) length: usize,
+    /// Length of every search result in bytes, if they differ (otherwise `length` applies)
+    pub(crate) lengths: Vec<usize>,
+    /// Description of every search result, e.g. the encoding of a found value
+    labels: Vec<ResultLabel>,
     /// Does the search text field have focus
     pub(crate) has_focus: bool,
     /// Index of the current search result
     idx: usize,
+    /// Search in progress, continued every frame
+    pending: Option<PendingSearch>,
 
     // -- UI control flags
     /// Force the search to be performed even if the input is the same as the last one
@@ -51,9 +183,12 @@ impl Search {
         self.addr = None;
         self.results.clear();
         self.length = 0;
+        self.lengths.clear();
+        self.labels.clear();
         // Do not clear current to preserve text box content
         self.last = SearchState::default();
         self.idx = 0;
+        self.pending = None;
         self.force = false;
     }
 
@@ -73,25 +208,89 @@ impl Search {
     pub(crate) const fn loose_focus(&mut self) {
         self.loose_focus = true;
     }
+
+    /// Length of the search result at `idx` in bytes
+    pub(crate) fn length_of(&self, idx: usize) -> usize {
+        self.lengths.get(idx).copied().unwrap_or(self.length)
+    }
 }
 
 impl HexSession {
-    /// Show content of the search menu
-    pub(crate) fn show_search_contents(&mut self, ui: &mut egui::Ui, events: &EventState) {
-        // RadioButtons to select between byte and ascii search
-        ui.horizontal(|ui| {
+    /// Show the radio buttons to select the search mode
+    fn show_search_modes(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal_wrapped(|ui| {
             ui.radio_value(&mut self.search.current.mode, SearchMode::Hex, "Hex")
                 .on_hover_text("Search for a byte pattern");
             ui.add_space(5.0);
+            ui.radio_value(
+                &mut self.search.current.mode,
+                SearchMode::Masked,
+                "Masked hex",
+            )
+            .on_hover_text(
+                "Search for a byte pattern with wildcards\n\
+                '?' matches any nibble, e.g. DE ?? BE EF or 4? 00\n\
+                Explicit bit mask after '/', e.g. 8001 / C0FF",
+            );
+            ui.add_space(5.0);
+            ui.radio_value(&mut self.search.current.mode, SearchMode::Value, "Value")
+                .on_hover_text(
+                    "Search for an integer or float, e.g. 115200, -5, 0x1C200 or 3.3\n\
+                Finds it stored as u16 / u32 / u64 (i16 / i32 / i64 if negative),\n\
+                f32 or f64, in little- and big-endian",
+                );
+            ui.add_space(5.0);
             ui.radio_value(&mut self.search.current.mode, SearchMode::Ascii, "Ascii")
-                .on_hover_text("Search ASCII literals");
+                .on_hover_text(
+                    "Search ASCII literals\n\
+                Scans the whole file at once, which may take a while for large files",
+                );
             ui.add_space(5.0);
             ui.radio_value(&mut self.search.current.mode, SearchMode::Regex, "Regex")
                 .on_hover_text(
                     "Search ASCII with regex\n\
-                Highlights only the first byte of the match",
+                Highlights only the first byte of the match\n\
+                Scans the whole file at once, which may take a while for large files",
                 );
+            ui.add_space(5.0);
+            ui.radio_value(
+                &mut self.search.current.mode,
+                SearchMode::References,
+                "Refs",
+            )
+            .on_hover_text(
+                "Search for pointers to an address, e.g. 8000 or 0x8000\n\
+                Finds aligned u16 / u32 / u64 words in little- and big-endian\n\
+                whose value is the address",
+            );
         });
+    }
+
+    /// Show content of the search menu
+    pub(crate) fn show_search_contents(&mut self, ui: &mut egui::Ui, events: &EventState) {
+        self.show_search_modes(ui);
+
+        // Approximate byte search
+        if self.search.current.mode == SearchMode::Hex {
+            // With as many mismatches as bytes every window would match
+            let max = (self.search.current.input.trim().len() / 2).saturating_sub(1);
+            ui.horizontal(|ui| {
+                ui.label("Max mismatches:");
+                ui.add(
+                    egui::DragValue::new(&mut self.search.current.max_mismatches).range(0..=max),
+                )
+                .on_hover_text(
+                    "Also find byte windows that differ from the pattern\n\
+                    in up to this many bytes (Hamming distance)",
+                );
+            });
+        }
+        if matches!(
+            self.search.current.mode,
+            SearchMode::Hex | SearchMode::Masked | SearchMode::Value
+        ) {
+            self.show_search_options(ui);
+        }
 
         ui.add_space(3.0);
 
@@ -121,38 +320,8 @@ impl HexSession {
                     self.search.idx = (self.search.idx + 1) % self.search.results.len();
                 }
             } else {
-                let input = self.search.current.input.as_str();
-
                 // Search if pattern is valid
-                let valid = match self.search.current.mode {
-                    SearchMode::Hex => {
-                        if let Some(pattern) = parse_str_into_bytes(input) {
-                            self.search.results = self.ih.search_bytes(&pattern);
-                            self.search.length = pattern.len();
-                            true
-                        } else {
-                            false
-                        }
-                    }
-                    SearchMode::Ascii => {
-                        if input.is_empty() {
-                            false
-                        } else {
-                            self.search.results = self.ih.search_ascii(input, false);
-                            self.search.length = input.len();
-                            true
-                        }
-                    }
-                    SearchMode::Regex => {
-                        if input.is_empty() {
-                            false
-                        } else {
-                            self.search.results = self.ih.search_ascii(input, true);
-                            self.search.length = 1; // highlight the first byte of the match
-                            true
-                        }
-                    }
-                };
+                let valid = self.search_current_input();
 
                 // Clear the result if pattern is not valid
                 if !valid {
@@ -167,6 +336,9 @@ impl HexSession {
             // Set address to scroll to (only if not forced)
             if !self.search.force {
                 self.search.addr = self.search.results.get(self.search.idx).copied();
+                if let Some(pending) = &mut self.search.pending {
+                    pending.scroll_to_first = self.search.addr.is_none();
+                }
             }
 
             self.search.force = false;
@@ -174,9 +346,21 @@ impl HexSession {
 
         ui.add_space(5.0);
 
-        // Show matches count if any
+        // Show matches count (and the label of the current match) if any
         let label_text = if self.search.results.is_empty() {
             "No results".to_string()
+        } else if self.search.pending.is_some() {
+            format!(
+                "Hits: {}+ (Current: {}, searching...)",
+                self.search.results.len(),
+                self.search.idx + 1
+            )
+        } else if let Some(label) = self.search.labels.get(self.search.idx) {
+            format!(
+                "Hits: {} (Current: {}, {label})",
+                self.search.results.len(),
+                self.search.idx + 1
+            )
         } else {
             format!(
                 "Hits: {} (Current: {})",
@@ -187,6 +371,285 @@ impl HexSession {
 
         ui.label(label_text);
     }
+
+    /// Show the options of the byte, masked and value search: address window, maximum number
+    /// of results and gap fill
+    fn show_search_options(&mut self, ui: &mut egui::Ui) {
+        let current = &mut self.search.current;
+        ui.collapsing("Options", |ui| {
+            egui::Grid::new("search_options_grid")
+                .num_columns(2)
+                .show(ui, |ui| {
+                    ui.label("From: 0x");
+                    ui.add(
+                        egui::TextEdit::singleline(&mut current.from)
+                            .hint_text("start")
+                            .desired_width(80.0),
+                    );
+                    ui.end_row();
+
+                    ui.label("To: 0x");
+                    ui.add(
+                        egui::TextEdit::singleline(&mut current.to)
+                            .hint_text("end")
+                            .desired_width(80.0),
+                    );
+                    ui.end_row();
+
+                    ui.label("Max results:");
+                    ui.add(egui::DragValue::new(&mut current.max_results))
+                        .on_hover_text("0 for no limit");
+                    ui.end_row();
+
+                    let exact = current.mode == SearchMode::Hex && current.max_mismatches == 0;
+                    ui.add_enabled(
+                        exact,
+                        egui::Checkbox::new(&mut current.fill_gaps, "Fill gaps: 0x"),
+                    )
+                    .on_hover_text("Find matches spanning gaps, treated as filled with the byte")
+                    .on_disabled_hover_text("Gaps are only filled for the exact byte search");
+                    ui.add_enabled(
+                        exact && current.fill_gaps,
+                        egui::TextEdit::singleline(&mut current.fill)
+                            .hint_text("FF")
+                            .char_limit(2)
+                            .desired_width(30.0),
+                    );
+                    ui.end_row();
+                });
+        });
+    }
+
+    /// Search for the words referring to `target` and jump to the first of them
+    pub(crate) fn find_references(&mut self, target: usize) {
+        self.search.current.mode = SearchMode::References;
+        self.search.current.input = format!("{target:X}");
+
+        if !self.search_current_input() {
+            self.search.results.clear();
+        }
+        self.search.idx = 0;
+        self.search.last = self.search.current.clone();
+        self.search.addr = self.search.results.first().copied();
+    }
+
+    /// Continue a search in progress for about `SEARCH_FRAME_TIME`. Each step scans the
+    /// next `step` bytes of data (gaps are skipped for free), the step size adapts to how long
+    /// the previous step took. Returns `true` if the search is not finished yet.
+    pub(crate) fn continue_search(&mut self) -> bool {
+        let Some(mut pending) = self.search.pending.take() else {
+            return false;
+        };
+        let started = Instant::now();
+
+        // Find where the data of this step ends
+        let mut budget = pending.step;
+        let mut step_end = None;
+        for (&start, data) in &self.ih {
+            let from = start.max(pending.next);
+            let chunk_end = start + data.len();
+            if chunk_end <= from {
+                continue;
+            }
+            if chunk_end - from >= budget {
+                step_end = Some(from + budget);
+                break;
+            }
+            budget -= chunk_end - from;
+        }
+
+        // Matches have to start before the step end, but may end after it
+        let window_end = pending.options.range.map_or(usize::MAX, |(_, end)| end);
+        let step_end = step_end.filter(|&end| end <= window_end);
+        let step_last = step_end.map_or(window_end, |end| {
+            (end + pending.kind.len()).saturating_sub(2).min(window_end)
+        });
+        let max_results = pending.options.max_results.unwrap_or(usize::MAX);
+        let options = SearchOptions {
+            range: Some((pending.next, step_last)),
+            max_results: Some(max_results.saturating_sub(self.search.results.len())),
+            gap_fill: pending.options.gap_fill,
+        };
+
+        let mut next = step_end;
+        match &pending.kind {
+            PendingKind::Bytes(pattern) => {
+                // Stop early if the matches alone take up the frame (e.g. a pattern of the
+                // gap fill byte)
+                for addr in self.ih.search_bytes_iter(pattern, &options) {
+                    self.search.results.push(addr);
+                    if started.elapsed() > SEARCH_FRAME_TIME {
+                        next = Some(addr + 1);
+                        break;
+                    }
+                }
+            }
+            PendingKind::Hamming(pattern, max_mismatches) => {
+                for (addr, n) in self.ih.search_hamming(pattern, *max_mismatches, &options) {
+                    self.search.results.push(addr);
+                    self.search.labels.push(ResultLabel::Mismatches(n));
+                }
+            }
+            PendingKind::Masked(pattern) => {
+                let matches = self.ih.search_masked(pattern, &options);
+                self.search.results.extend(matches);
+            }
+            PendingKind::Value(value, _) => {
+                // Shorter encodings past the step end fit into this step too, they are left
+                // to the next one
+                let before_end = |addr: usize| step_end.is_none_or(|end| addr < end);
+                for (addr, encoding) in self.ih.search_value(value, &options) {
+                    if before_end(addr) {
+                        self.search.results.push(addr);
+                        self.search.lengths.push(encoding.kind.size());
+                        self.search.labels.push(ResultLabel::Value(encoding));
+                    }
+                }
+            }
+        }
+        if self.search.results.len() >= max_results {
+            next = None;
+        }
+
+        let found = !self.search.results.is_empty();
+        if pending.scroll_to_first && found {
+            self.search.addr = self.search.results.first().copied();
+        }
+
+        // Scan more data per frame while steps are quick, less once they get slow
+        let elapsed = started.elapsed();
+        if elapsed < SEARCH_FRAME_TIME / 2 {
+            pending.step = (pending.step * 2).min(SEARCH_STEP_MAX);
+        } else if elapsed > SEARCH_FRAME_TIME {
+            pending.step = (pending.step / 2).max(SEARCH_STEP_MIN);
+        }
+
+        self.search.pending = next.map(|next| PendingSearch {
+            next,
+            scroll_to_first: pending.scroll_to_first && !found,
+            ..pending
+        });
+        self.search.pending.is_some()
+    }
+
+    /// Start a search with the current options, continued every frame.
+    /// Returns `false` if the options are not valid.
+    fn start_search(&mut self, kind: PendingKind) -> bool {
+        let Some(mut options) = self.search.current.options() else {
+            return false;
+        };
+        if !matches!(kind, PendingKind::Bytes(_)) {
+            options.gap_fill = None;
+        }
+        let data_start = self.ih.get_min_addr().unwrap_or(0);
+
+        self.search.results.clear();
+        self.search.pending = Some(PendingSearch {
+            kind,
+            next: options.range.map_or(data_start, |(from, _)| from),
+            options,
+            step: SEARCH_STEP_INITIAL,
+            scroll_to_first: false,
+        });
+        self.continue_search();
+        true
+    }
+
+    /// Search for the current input in the selected mode.
+    /// Returns `false` if the input is not a valid pattern.
+    fn search_current_input(&mut self) -> bool {
+        let input = self.search.current.input.as_str();
+
+        self.search.lengths.clear();
+        self.search.labels.clear();
+        self.search.pending = None;
+
+        match self.search.current.mode {
+            SearchMode::Hex => {
+                if let Some(pattern) = parse_str_into_bytes(input)
+                    && !pattern.is_empty()
+                {
+                    // With as many mismatches as bytes every window would match
+                    let max_mismatches = self.search.current.max_mismatches.min(pattern.len() - 1);
+                    self.search.length = pattern.len();
+                    if max_mismatches == 0 {
+                        self.start_search(PendingKind::Bytes(pattern))
+                    } else {
+                        self.start_search(PendingKind::Hamming(pattern, max_mismatches))
+                    }
+                } else {
+                    false
+                }
+            }
+            SearchMode::Masked => {
+                if let Ok(pattern) = input.parse::<MaskedPattern>() {
+                    self.search.length = pattern.len();
+                    self.start_search(PendingKind::Masked(pattern))
+                } else {
+                    false
+                }
+            }
+            SearchMode::Value => {
+                if let Ok(value) = input.parse::<NumericValue>() {
+                    let longest = value
+                        .encodings()
+                        .iter()
+                        .map(|(encoding, _)| encoding.kind.size())
+                        .max()
+                        .unwrap_or(0);
+                    self.search.length = longest;
+                    self.start_search(PendingKind::Value(value, longest))
+                } else {
+                    false
+                }
+            }
+            SearchMode::Ascii => {
+                if input.is_empty() {
+                    false
+                } else {
+                    self.search.results = self.ih.search_ascii(input, false);
+                    self.search.length = input.len();
+                    true
+                }
+            }
+            SearchMode::Regex => {
+                if input.is_empty() {
+                    false
+                } else {
+                    self.search.results = self.ih.search_ascii(input, true);
+                    self.search.length = 1; // highlight the first byte of the match
+                    true
+                }
+            }
+            SearchMode::References => {
+                let digits = input.trim_start_matches("0x");
+                if let Ok(target) = usize::from_str_radix(digits, 16) {
+                    // Keep the widest word per address, e.g. a u32 pointer and not its low half
+                    let mut refs: Vec<Reference> = Vec::new();
+                    for r in self.ih.references_to(target, &POINTER_ENCODINGS, true) {
+                        match refs.last_mut() {
+                            Some(last) if last.address == r.address => {
+                                if r.encoding.kind.size() > last.encoding.kind.size() {
+                                    *last = r;
+                                }
+                            }
+                            _ => refs.push(r),
+                        }
+                    }
+                    self.search.results = refs.iter().map(|r| r.address).collect();
+                    self.search.lengths = refs.iter().map(|r| r.encoding.kind.size()).collect();
+                    self.search.labels = refs
+                        .iter()
+                        .map(|r| ResultLabel::Pointer(r.encoding))
+                        .collect();
+                    self.search.length = self.search.lengths.iter().copied().max().unwrap_or(0);
+                    true
+                } else {
+                    false
+                }
+            }
+        }
+    }
 }
 
 fn parse_str_into_bytes(s: &str) -> Option<Vec<u8>> {
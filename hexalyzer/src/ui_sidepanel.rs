@@ -4,6 +4,7 @@ use crate::ui_inspector::format_with_separators;
 use eframe::egui;
 
 impl HexViewerApp {
+    #[allow(clippy::too_many_lines)]
     /// Show the side panel with the file information, jump to address, search, and data inspector.
     pub(crate) fn show_side_panel(&mut self, ui: &mut egui::Ui) {
         if !self.side_panel_expanded {
@@ -99,6 +100,47 @@ impl HexViewerApp {
 
                 ui.add_space(3.0);
 
+                // SOURCES (only shown for images merged from multiple files)
+                if curr_session
+                    .ih
+                    .provenance()
+                    .is_some_and(|p| p.names().len() > 1)
+                {
+                    egui::CollapsingHeader::new("Sources")
+                        .default_open(true)
+                        .show(ui, |ui| {
+                            ui.add_space(5.0);
+                            curr_session.show_sources_contents(ui);
+                            ui.add_space(5.0);
+                        });
+
+                    ui.add_space(3.0);
+                }
+
+                // VECTOR TABLE (only shown for Cortex-M images)
+                curr_session.update_vector_tables();
+                if !curr_session.vectors.tables.is_empty() {
+                    egui::CollapsingHeader::new("Vector Table")
+                        .default_open(true)
+                        .show(ui, |ui| {
+                            ui.add_space(5.0);
+                            curr_session.show_vectors_contents(ui);
+                        });
+
+                    ui.add_space(3.0);
+                }
+
+                // BOOKMARKS
+                egui::CollapsingHeader::new("Bookmarks")
+                    .default_open(true)
+                    .show(ui, |ui| {
+                        ui.add_space(5.0);
+                        curr_session.show_bookmarks_contents(ui);
+                        ui.add_space(5.0);
+                    });
+
+                ui.add_space(3.0);
+
                 // JUMP TO ADDRESS
                 egui::CollapsingHeader::new("Jump To Address")
                     .default_open(true)
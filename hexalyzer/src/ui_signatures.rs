@@ -0,0 +1,153 @@
+use crate::app::{DataState, HexSession};
+use eframe::egui;
+use intelhexlib::{SignatureHit, SignatureTable};
+
+/// Embedded file found in the data and the number of bytes to extract for it
+type FoundFile = (SignatureHit, usize);
+
+/// Tool window listing the files embedded in the current file (gzip, PNG, ELF, ...)
+pub struct SignaturesWindow {
+    pub active: bool,
+    /// Signatures to scan for
+    table: SignatureTable,
+    /// Embedded files of the session, scanned once per file and edit
+    found: Vec<FoundFile>,
+    /// Scroll id and edit state of the session the files were scanned in. `None` forces a
+    /// new scan.
+    scanned_for: Option<(usize, DataState)>,
+    /// Name and bytes of an embedded file to open in a new tab, or why it can not be read
+    pub(crate) extract: Option<Result<(String, Vec<u8>), String>>,
+    /// Whether the signatures window or its widgets had focus this frame
+    focused: bool,
+}
+
+impl Default for SignaturesWindow {
+    fn default() -> Self {
+        Self {
+            active: false,
+            table: SignatureTable::new(),
+            found: Vec::new(),
+            scanned_for: None,
+            extract: None,
+            focused: false,
+        }
+    }
+}
+
+impl SignaturesWindow {
+    /// Returns true if the signatures window was hovered or had keyboard focus
+    /// during the last `show()` call
+    pub const fn has_focus(&self) -> bool {
+        self.focused
+    }
+
+    /// Render the signatures window (if active). Clicking a file selects it in the hex view
+    /// of the session, `Extract` requests to open it in a new tab (see `extract`).
+    pub fn show(&mut self, ctx: &egui::Context, session: Option<&mut HexSession>) {
+        if !self.active {
+            self.focused = false;
+            return;
+        }
+
+        let mut is_open = self.active;
+        let response = egui::Window::new("Signatures")
+            .open(&mut is_open)
+            .collapsible(false)
+            .resizable(true)
+            .default_size([620.0, 320.0])
+            .show(ctx, |ui| {
+                egui::Frame::NONE
+                    .inner_margin(egui::Margin::symmetric(12, 8))
+                    .show(ui, |ui| {
+                        if let Some(session) = session {
+                            self.show_contents(ui, session);
+                        } else {
+                            ui.label("No file opened");
+                        }
+                    });
+            });
+        self.active = is_open;
+        self.focused = response.is_some_and(|r| r.response.hovered() || r.response.has_focus());
+    }
+
+    fn show_contents(&mut self, ui: &mut egui::Ui, session: &mut HexSession) {
+        self.update_found(session);
+
+        ui.horizontal(|ui| {
+            ui.label(format!("Embedded files: {}", self.found.len()));
+            if ui
+                .button("Refresh")
+                .on_hover_text("Scan the file again")
+                .clicked()
+            {
+                self.scanned_for = None;
+            }
+        });
+
+        ui.separator();
+
+        let row_height = ui.spacing().interact_size.y;
+        egui::ScrollArea::vertical().auto_shrink(false).show_rows(
+            ui,
+            row_height,
+            self.found.len(),
+            |ui, row_range| {
+                for (hit, len) in &self.found[row_range] {
+                    ui.horizontal(|ui| {
+                        if ui
+                            .small_button("Extract")
+                            .on_hover_text(format!("Open {len} bytes in a new tab"))
+                            .clicked()
+                        {
+                            let name = format!("{}_{:08X}.bin", hit.name, hit.address);
+                            let data = session.ih.read_range(hit.address, *len).ok_or_else(|| {
+                                format!("Failed to extract {len} bytes at 0x{:08X}", hit.address)
+                            });
+                            self.extract = Some(data.map(|data| (name, data)));
+                        }
+
+                        let row = format!(
+                            "0x{:08X}  {:<16}  {}",
+                            hit.address, hit.name, hit.info.description
+                        );
+                        let label = egui::RichText::new(row).monospace();
+                        if ui.selectable_label(false, label).clicked() {
+                            // Select the embedded file and jump to it
+                            session.selection.range = Some([hit.address, hit.address + len - 1]);
+                            session.jump_to.addr = Some(hit.address);
+                        }
+                    });
+                }
+            },
+        );
+    }
+
+    /// Scan the session for embedded files if it or its data changed. Files of unknown length are
+    /// extracted up to the end of their data chunk.
+    fn update_found(&mut self, session: &HexSession) {
+        let key = (session.scroll_id, session.data_state());
+        if self.scanned_for == Some(key) {
+            return;
+        }
+
+        self.found = session
+            .ih
+            .scan_signatures(&self.table)
+            .into_iter()
+            .map(|hit| {
+                let chunk_end = session
+                    .ih
+                    .iter()
+                    .find(|(start, data)| (**start..**start + data.len()).contains(&hit.address))
+                    .map_or(hit.address + 1, |(start, data)| start + data.len());
+                let available = chunk_end - hit.address;
+                let len = hit
+                    .info
+                    .length
+                    .map_or(available, |len| len.clamp(1, available));
+                (hit, len)
+            })
+            .collect();
+        self.scanned_for = Some(key);
+    }
+}
@@ -0,0 +1,39 @@
+use crate::app::{HexSession, colors};
+use crate::ui_inspector::format_with_separators;
+use eframe::egui;
+
+impl HexSession {
+    /// Displays the legend of source files the current data was merged from.
+    /// Each source is listed with its highlight color and the number of bytes it contributes.
+    pub(crate) fn show_sources_contents(&self, ui: &mut egui::Ui) {
+        let Some(provenance) = self.ih.provenance() else {
+            return;
+        };
+
+        let sizes = provenance.sizes();
+
+        egui::Grid::new("sources_grid")
+            .num_columns(3) // three columns: color + name + size
+            .spacing([10.0, 4.0]) // horizontal & vertical spacing
+            .show(ui, |ui| {
+                for (id, name) in provenance.names().iter().enumerate() {
+                    // Sources whose bytes were all overwritten or removed are not listed
+                    let size = sizes.get(id).copied().unwrap_or_default();
+                    if size == 0 {
+                        continue;
+                    }
+
+                    // Color swatch (drawn with full opacity to be visible on the panel)
+                    let (rect, _) =
+                        ui.allocate_exact_size(egui::vec2(12.0, 12.0), egui::Sense::hover());
+                    ui.painter()
+                        .rect_filled(rect, 2.0, colors::source(id).gamma_multiply(6.0));
+
+                    ui.add(egui::Label::new(name).truncate());
+
+                    ui.label(format!("{} bytes", format_with_separators(size)));
+                    ui.end_row();
+                }
+            });
+    }
+}
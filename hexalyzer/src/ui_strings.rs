@@ -0,0 +1,183 @@
+use crate::app::{DataState, HexSession};
+use eframe::egui;
+use intelhexlib::StringEncoding;
+
+/// String found in the data: address, length in bytes, encoding and text
+type FoundString = (usize, usize, StringEncoding, String);
+
+/// Tool window listing the strings of the current file
+pub struct StringsWindow {
+    pub active: bool,
+    /// Minimum length of listed strings in characters
+    min_len: usize,
+    /// Encodings to extract strings in
+    encodings: [(StringEncoding, bool); 4],
+    /// Case-insensitive filter of listed strings
+    filter: String,
+    /// Strings of the file, extracted once per file and settings
+    strings: Vec<FoundString>,
+    /// Indices of the strings that pass the filter
+    filtered: Vec<usize>,
+    /// Scroll id and edit state of the session, minimum length and encodings the strings
+    /// were extracted with. `None` forces a new extraction.
+    extracted_for: Option<(usize, DataState, usize, Vec<StringEncoding>)>,
+    /// Filter `filtered` was computed with
+    filtered_with: Option<String>,
+    /// Whether the strings window or its widgets had focus this frame
+    focused: bool,
+}
+
+impl Default for StringsWindow {
+    fn default() -> Self {
+        Self {
+            active: false,
+            min_len: 4,
+            encodings: StringEncoding::ALL.map(|e| (e, true)),
+            filter: String::new(),
+            strings: Vec::new(),
+            filtered: Vec::new(),
+            extracted_for: None,
+            filtered_with: None,
+            focused: false,
+        }
+    }
+}
+
+impl StringsWindow {
+    /// Returns true if the strings window was hovered or had keyboard focus
+    /// during the last `show()` call
+    pub const fn has_focus(&self) -> bool {
+        self.focused
+    }
+
+    /// Render the strings window (if active). Clicking a string selects it and jumps
+    /// to it in the hex view of the session.
+    pub fn show(&mut self, ctx: &egui::Context, session: Option<&mut HexSession>) {
+        if !self.active {
+            self.focused = false;
+            return;
+        }
+
+        let mut is_open = self.active;
+        let response = egui::Window::new("Strings")
+            .open(&mut is_open)
+            .collapsible(false)
+            .resizable(true)
+            .default_size([480.0, 420.0])
+            .show(ctx, |ui| {
+                egui::Frame::NONE
+                    .inner_margin(egui::Margin::symmetric(12, 8))
+                    .show(ui, |ui| {
+                        if let Some(session) = session {
+                            self.show_contents(ui, session);
+                        } else {
+                            ui.label("No file opened");
+                        }
+                    });
+            });
+        self.active = is_open;
+        self.focused = response.is_some_and(|r| r.response.hovered() || r.response.has_focus());
+    }
+
+    fn show_contents(&mut self, ui: &mut egui::Ui, session: &mut HexSession) {
+        ui.horizontal_wrapped(|ui| {
+            ui.label("Min length:");
+            ui.add(egui::DragValue::new(&mut self.min_len).range(1..=256));
+            ui.add_space(8.0);
+            for (encoding, enabled) in &mut self.encodings {
+                ui.checkbox(enabled, encoding.to_string());
+            }
+            if ui
+                .button("Refresh")
+                .on_hover_text("Extract the strings again, e.g. after editing bytes")
+                .clicked()
+            {
+                self.extracted_for = None;
+            }
+        });
+
+        ui.horizontal(|ui| {
+            ui.label("Filter:");
+            ui.add(egui::TextEdit::singleline(&mut self.filter).desired_width(f32::INFINITY));
+        });
+
+        self.update_strings(session);
+
+        ui.label(format!(
+            "Strings: {} (of {})",
+            self.filtered.len(),
+            self.strings.len()
+        ));
+        ui.separator();
+
+        let row_height = ui.text_style_height(&egui::TextStyle::Monospace);
+        egui::ScrollArea::vertical().auto_shrink(false).show_rows(
+            ui,
+            row_height,
+            self.filtered.len(),
+            |ui, row_range| {
+                for &idx in &self.filtered[row_range] {
+                    let (addr, len, encoding, text) = &self.strings[idx];
+                    let row = format!(
+                        "0x{addr:08X}  {:<8}  {}",
+                        encoding.to_string(),
+                        text.escape_debug()
+                    );
+                    let label = egui::RichText::new(row).monospace();
+                    if ui.selectable_label(false, label).clicked() {
+                        // Select the string and jump to it
+                        session.selection.range = Some([*addr, addr + len - 1]);
+                        session.jump_to.addr = Some(*addr);
+                    }
+                }
+            },
+        );
+    }
+
+    /// Extract the strings if the session, its data or the settings changed, and filter them if the
+    /// filter changed
+    fn update_strings(&mut self, session: &HexSession) {
+        let encodings: Vec<StringEncoding> = self
+            .encodings
+            .iter()
+            .filter(|(_, enabled)| *enabled)
+            .map(|(encoding, _)| *encoding)
+            .collect();
+        let key = (
+            session.scroll_id,
+            session.data_state(),
+            self.min_len,
+            encodings,
+        );
+
+        if self.extracted_for.as_ref() != Some(&key) {
+            self.strings = session
+                .ih
+                .strings(self.min_len, &key.3)
+                .map(|(addr, encoding, text)| {
+                    let len = match encoding {
+                        StringEncoding::Ascii | StringEncoding::Utf8 => text.len(),
+                        StringEncoding::Utf16Le | StringEncoding::Utf16Be => {
+                            text.chars().count() * 2
+                        }
+                    };
+                    (addr, len, encoding, text)
+                })
+                .collect();
+            self.extracted_for = Some(key);
+            self.filtered_with = None;
+        }
+
+        if self.filtered_with.as_ref() != Some(&self.filter) {
+            let filter = self.filter.to_lowercase();
+            self.filtered = self
+                .strings
+                .iter()
+                .enumerate()
+                .filter(|(_, (_, _, _, text))| text.to_lowercase().contains(&filter))
+                .map(|(idx, _)| idx)
+                .collect();
+            self.filtered_with = Some(self.filter.clone());
+        }
+    }
+}
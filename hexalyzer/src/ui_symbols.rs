@@ -0,0 +1,126 @@
+use crate::app::HexSession;
+use eframe::egui;
+
+/// Tool window listing the symbols of the current file, filtered by name
+#[derive(Default)]
+pub struct SymbolsWindow {
+    pub active: bool,
+    /// Case-insensitive part of the symbol names to show
+    filter: String,
+    /// Indices of the symbols matching the filter
+    matches: Vec<usize>,
+    /// Scroll id of the session and filter the matches were collected for.
+    /// `None` forces a new collection.
+    filtered_for: Option<(usize, String)>,
+    /// Whether the symbols window or its widgets had focus this frame
+    focused: bool,
+}
+
+impl SymbolsWindow {
+    /// Returns true if the symbols window was hovered or had keyboard focus
+    /// during the last `show()` call
+    pub const fn has_focus(&self) -> bool {
+        self.focused
+    }
+
+    /// Filter the symbols again, e.g. after new symbols were loaded
+    pub(crate) fn refresh(&mut self) {
+        self.filtered_for = None;
+    }
+
+    /// Render the symbols window (if active). Clicking a symbol selects it in the hex view
+    /// of the session.
+    pub fn show(&mut self, ctx: &egui::Context, session: Option<&mut HexSession>) {
+        if !self.active {
+            self.focused = false;
+            return;
+        }
+
+        let mut is_open = self.active;
+        let response = egui::Window::new("Symbols")
+            .open(&mut is_open)
+            .collapsible(false)
+            .resizable(true)
+            .default_size([520.0, 360.0])
+            .show(ctx, |ui| {
+                egui::Frame::NONE
+                    .inner_margin(egui::Margin::symmetric(12, 8))
+                    .show(ui, |ui| {
+                        if let Some(session) = session {
+                            self.show_contents(ui, session);
+                        } else {
+                            ui.label("No file opened");
+                        }
+                    });
+            });
+        self.active = is_open;
+        self.focused = response.is_some_and(|r| r.response.hovered() || r.response.has_focus());
+    }
+
+    fn show_contents(&mut self, ui: &mut egui::Ui, session: &mut HexSession) {
+        if session.symbols.is_empty() {
+            ui.label("No symbols loaded. Use File > Load symbols... to load a map or ELF file.");
+            return;
+        }
+
+        ui.horizontal(|ui| {
+            ui.label("Filter:");
+            ui.add(
+                egui::TextEdit::singleline(&mut self.filter)
+                    .hint_text("symbol name")
+                    .desired_width(200.0),
+            );
+        });
+
+        self.update_matches(session);
+
+        ui.label(format!(
+            "Symbols: {} of {}",
+            self.matches.len(),
+            session.symbols.len()
+        ));
+        ui.separator();
+
+        let row_height = ui.spacing().interact_size.y;
+        egui::ScrollArea::vertical().auto_shrink(false).show_rows(
+            ui,
+            row_height,
+            self.matches.len(),
+            |ui, row_range| {
+                for &idx in &self.matches[row_range] {
+                    let symbol = &session.symbols.symbols()[idx];
+                    let row = format!(
+                        "0x{:08X}  {:>8}  {}",
+                        symbol.address, symbol.size, symbol.name
+                    );
+                    let label = egui::RichText::new(row).monospace();
+                    if ui.selectable_label(false, label).clicked() {
+                        // Select the symbol and jump to it
+                        let end = symbol.address.saturating_add(symbol.size.max(1) - 1);
+                        session.selection.range = Some([symbol.address, end]);
+                        session.jump_to.addr = Some(symbol.address);
+                    }
+                }
+            },
+        );
+    }
+
+    /// Collect the symbols matching the filter if the session or filter changed
+    fn update_matches(&mut self, session: &HexSession) {
+        let key = (session.scroll_id, self.filter.clone());
+        if self.filtered_for.as_ref() == Some(&key) {
+            return;
+        }
+
+        let filter = self.filter.to_lowercase();
+        self.matches = session
+            .symbols
+            .symbols()
+            .iter()
+            .enumerate()
+            .filter(|(_, s)| s.name.to_lowercase().contains(&filter))
+            .map(|(idx, _)| idx)
+            .collect();
+        self.filtered_for = Some(key);
+    }
+}
@@ -0,0 +1,510 @@
+use crate::app::{DataState, Endianness, HexSession, colors};
+use eframe::egui;
+use intelhexlib::{FieldKind, FieldValue, ScalarType, Template, TemplateField};
+
+/// Bytes of a decoded scalar or text field
+pub struct FieldSpan {
+    pub start: usize,
+    /// Last address of the field (inclusive)
+    pub end: usize,
+    /// Path of the field in the struct, e.g. `sections[1].offset`
+    pub path: String,
+}
+
+/// Structure template applied at an address of the file, shown as a tree next to the hex view
+#[derive(Default)]
+pub struct TemplateView {
+    /// Whether the template panel is shown
+    pub(crate) open: bool,
+    /// Parsed template and the name of its file
+    template: Option<(Template, String)>,
+    /// Name of the struct to decode
+    struct_name: String,
+    /// Address to decode the struct at, as typed by the user
+    addr_input: String,
+    /// Struct and address the template is applied at
+    applied: Option<(String, usize)>,
+    /// Fields of the applied struct
+    decoded: Option<TemplateField>,
+    /// Scalar and text fields of the applied struct in address order
+    pub(crate) spans: Vec<FieldSpan>,
+    /// State of the data and byte order the fields were decoded for. `None` forces a new decode.
+    decoded_for: Option<(DataState, bool)>,
+    /// Address of the field being edited and the entered value
+    editing: Option<(usize, String)>,
+    /// Field hovered in the tree (first and last address)
+    pub(crate) hovered: Option<[usize; 2]>,
+    /// Error of the last apply or edit
+    error: Option<String>,
+}
+
+/// User interactions with the field tree, applied after it is drawn
+#[derive(Default)]
+struct TreeActions {
+    /// Field hovered in the tree (first and last address)
+    hovered: Option<[usize; 2]>,
+    /// Field clicked in the tree (first and last address)
+    select: Option<[usize; 2]>,
+    /// Field (address) to write the entered value to
+    commit: Option<(usize, String)>,
+    /// Whether the value text field has focus
+    text_focused: bool,
+}
+
+impl TemplateView {
+    /// Decode the applied struct again, e.g. after the file was reloaded
+    pub(crate) const fn refresh(&mut self) {
+        self.decoded_for = None;
+    }
+}
+
+impl HexSession {
+    /// Use the template for this file. Its last struct is applied at the selection start
+    /// (or the first address) on the next "Apply".
+    pub(crate) fn set_template(&mut self, template: Template, name: String) {
+        let view = &mut self.template;
+        view.struct_name = template
+            .struct_names()
+            .last()
+            .map(ToString::to_string)
+            .unwrap_or_default();
+        view.template = Some((template, name));
+        view.applied = None;
+        view.decoded = None;
+        view.spans.clear();
+        view.error = None;
+        view.open = true;
+
+        if view.addr_input.is_empty() {
+            let start = self
+                .selection
+                .range
+                .map_or_else(|| *self.addr.start(), |r| r[0].min(r[1]));
+            view.addr_input = format!("{start:X}");
+        }
+    }
+
+    /// Whether a template is loaded, so it can be applied
+    pub(crate) const fn has_template(&self) -> bool {
+        self.template.template.is_some()
+    }
+
+    /// Apply the selected struct of the template at the address
+    pub(crate) fn apply_template_at(&mut self, addr: usize) {
+        let view = &mut self.template;
+        view.addr_input = format!("{addr:X}");
+        view.applied = Some((view.struct_name.clone(), addr));
+        view.decoded_for = None;
+        view.editing = None;
+        view.open = true;
+    }
+
+    /// Decode the applied struct again if the data or byte order changed
+    pub(crate) fn update_template(&mut self) {
+        let key = (self.data_state(), self.endianness == Endianness::Big);
+        if self.template.decoded_for == Some(key) {
+            return;
+        }
+        self.template.decoded_for = Some(key);
+
+        let view = &mut self.template;
+        view.decoded = match (&view.template, &view.applied) {
+            (Some((template, _)), Some((name, addr))) => {
+                self.ih.decode_template(template, name, *addr, key.1)
+            }
+            _ => None,
+        };
+
+        view.spans.clear();
+        if let Some(decoded) = &view.decoded {
+            collect_spans(decoded, "", &mut view.spans);
+        }
+    }
+
+    /// Index of the template field containing the address. Fields are only highlighted
+    /// while the template panel is shown.
+    pub(crate) fn template_field_at(&self, addr: usize) -> Option<usize> {
+        if !self.template.open {
+            return None;
+        }
+        let spans = &self.template.spans;
+        let idx = spans.partition_point(|s| s.start <= addr);
+        (idx > 0 && addr <= spans[idx - 1].end).then(|| idx - 1)
+    }
+
+    /// Displays the template panel on the right of the hex view: template selection,
+    /// the decoded field tree and editing of field values
+    pub(crate) fn show_template_panel(&mut self, ui: &mut egui::Ui) {
+        if !self.template.open {
+            self.template.hovered = None;
+            return;
+        }
+
+        egui::Panel::right("template_panel")
+            .resizable(true)
+            .default_size(340.0)
+            .show_inside(ui, |ui| {
+                ui.add_space(3.0);
+                ui.horizontal(|ui| {
+                    ui.strong("Structure Template");
+                    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                        if ui.small_button("Close").clicked() {
+                            self.template.open = false;
+                        }
+                    });
+                });
+                ui.separator();
+
+                if self.template.template.is_none() {
+                    ui.label(
+                        "No template loaded. Use File > Load template... to load a C header \
+                        with struct definitions.",
+                    );
+                    return;
+                }
+
+                self.show_template_controls(ui);
+
+                if let Some(error) = &self.template.error {
+                    ui.label(egui::RichText::new(error).color(colors::WARNING).size(12.0));
+                }
+                ui.separator();
+
+                self.update_template();
+                self.show_template_tree(ui);
+            });
+    }
+
+    /// Struct and address selection, byte order and the apply button
+    fn show_template_controls(&mut self, ui: &mut egui::Ui) {
+        let Some((template, file_name)) = &self.template.template else {
+            return;
+        };
+        ui.label(format!("Template: {file_name}"));
+
+        let names: Vec<String> = template
+            .struct_names()
+            .into_iter()
+            .map(ToString::to_string)
+            .collect();
+        let view = &mut self.template;
+        let endianness = &mut self.endianness;
+        let mut text_focused = false;
+        egui::Grid::new("template_controls_grid")
+            .num_columns(2)
+            .spacing([10.0, 4.0])
+            .show(ui, |ui| {
+                ui.label("Struct");
+                egui::ComboBox::from_id_salt("template_struct")
+                    .selected_text(view.struct_name.as_str())
+                    .show_ui(ui, |ui| {
+                        for name in names {
+                            ui.selectable_value(&mut view.struct_name, name.clone(), name);
+                        }
+                    });
+                ui.end_row();
+
+                ui.label("Address");
+                let input = ui.add(
+                    egui::TextEdit::singleline(&mut view.addr_input)
+                        .hint_text("hex address")
+                        .desired_width(120.0),
+                );
+                text_focused = input.has_focus();
+                ui.end_row();
+
+                ui.label("Byte order");
+                ui.horizontal(|ui| {
+                    ui.radio_value(endianness, Endianness::Little, "Little");
+                    ui.radio_value(endianness, Endianness::Big, "Big");
+                })
+                .response
+                .on_hover_text("Byte order of the fields without __le / __be type or pragma");
+                ui.end_row();
+            });
+
+        let digits = view.addr_input.trim();
+        let digits = digits
+            .strip_prefix("0x")
+            .or_else(|| digits.strip_prefix("0X"))
+            .unwrap_or(digits);
+        let addr = usize::from_str_radix(digits, 16).ok();
+
+        let (apply, clear) = ui
+            .horizontal(|ui| {
+                let apply = ui
+                    .add_enabled(
+                        addr.is_some() && !view.struct_name.is_empty(),
+                        egui::Button::new("Apply"),
+                    )
+                    .clicked();
+                let clear = ui
+                    .add_enabled(view.applied.is_some(), egui::Button::new("Clear"))
+                    .clicked();
+                (apply, clear)
+            })
+            .inner;
+
+        if apply && let Some(addr) = addr {
+            self.template.error = None;
+            self.apply_template_at(addr);
+        }
+        if clear {
+            self.template.applied = None;
+            self.template.decoded_for = None;
+        }
+
+        // Clear the selection to avoid modifying bytes while typing the address
+        if text_focused {
+            self.search.has_focus = false;
+            self.jump_to.has_focus = false;
+            self.selection.clear();
+        }
+    }
+
+    /// The decoded fields as a tree. Hovering a field highlights its bytes, clicking it
+    /// selects them and double-clicking a value edits it.
+    fn show_template_tree(&mut self, ui: &mut egui::Ui) {
+        let Some(decoded) = &self.template.decoded else {
+            if let Some((name, addr)) = &self.template.applied {
+                ui.label(format!("No struct '{name}' to apply at 0x{addr:X}"));
+            }
+            self.template.hovered = None;
+            return;
+        };
+
+        let mut actions = TreeActions::default();
+        egui::ScrollArea::vertical()
+            .auto_shrink(false)
+            .show(ui, |ui| {
+                show_field(ui, decoded, true, &mut self.template.editing, &mut actions);
+            });
+        self.template.hovered = actions.hovered;
+
+        // Clear the selection to avoid modifying bytes while typing the value
+        if actions.text_focused {
+            self.search.has_focus = false;
+            self.jump_to.has_focus = false;
+            self.selection.clear();
+        }
+
+        if let Some(range) = actions.select {
+            self.selection.range = Some(range);
+            self.jump_to.addr = Some(range[0]);
+        }
+
+        if let Some((addr, input)) = actions.commit {
+            self.template.error = self.write_template_field(addr, &input).err();
+        }
+    }
+
+    /// Encode the value of the field at the address and write it to the data. Of a text
+    /// field only the bytes that differ from the current ones are written.
+    fn write_template_field(&mut self, addr: usize, input: &str) -> Result<(), String> {
+        let field = self
+            .template
+            .decoded
+            .as_ref()
+            .and_then(|d| d.leaves().into_iter().find(|f| f.address == addr))
+            .ok_or("Field not found")?;
+        let label = format!("Edit field {}", field.name);
+
+        if let Some(FieldValue::Text(shown)) = &field.value {
+            let current = self.ih.read_range(addr, field.size).unwrap_or_default();
+            let bytes = field
+                .encode(input)
+                .map(|bytes| edited_text(&current, shown, &bytes))
+                .ok_or_else(|| format!("Invalid {} value: {input}", field.type_name))?;
+            let Some(first) = bytes.iter().zip(&current).position(|(new, old)| new != old) else {
+                return Ok(());
+            };
+            let last = bytes
+                .iter()
+                .zip(&current)
+                .rposition(|(new, old)| new != old);
+            let last = last.unwrap_or(first);
+            return self.write_bytes(&label, addr + first, &bytes[first..=last]);
+        }
+
+        let bytes = field
+            .encode(input.trim())
+            .ok_or_else(|| format!("Invalid {} value: {input}", field.type_name))?;
+        self.write_bytes(&label, addr, &bytes)
+    }
+}
+
+/// Bytes of a text field after editing: the `encoded` input where it differs from the
+/// `shown` text, the `current` bytes elsewhere. Keeps the bytes shown as '.' (non-printable)
+/// and the bytes after the terminating NUL.
+fn edited_text(current: &[u8], shown: &str, encoded: &[u8]) -> Vec<u8> {
+    let mut bytes = current.to_vec();
+    for (i, (byte, shown)) in encoded
+        .iter()
+        .zip(shown.bytes().map(Some).chain(std::iter::repeat(None)))
+        .enumerate()
+    {
+        if shown != Some(*byte) && i < bytes.len() {
+            bytes[i] = *byte;
+        }
+    }
+    bytes
+}
+
+/// Collect the spans of the scalar and text fields with their path in the struct
+fn collect_spans(field: &TemplateField, path: &str, spans: &mut Vec<FieldSpan>) {
+    for child in &field.children {
+        let child_path = if path.is_empty() {
+            child.name.clone()
+        } else if field.kind == FieldKind::Array {
+            // Elements are named `name[i]` already
+            format!("{}{}", path, &child.name[field.name.len()..])
+        } else {
+            format!("{path}.{}", child.name)
+        };
+
+        match child.kind {
+            FieldKind::Scalar(_) | FieldKind::Text if child.size > 0 => spans.push(FieldSpan {
+                start: child.address,
+                end: child.address + child.size - 1,
+                path: child_path,
+            }),
+            FieldKind::Scalar(_) | FieldKind::Text => {}
+            FieldKind::Struct | FieldKind::Array => collect_spans(child, &child_path, spans),
+        }
+    }
+}
+
+/// First and last address of the field
+const fn field_range(field: &TemplateField) -> [usize; 2] {
+    [field.address, field.address + field.size.saturating_sub(1)]
+}
+
+/// Displays a field of the tree: structs and arrays as collapsible headers, scalar and
+/// text fields as a row with name, value and type
+fn show_field(
+    ui: &mut egui::Ui,
+    field: &TemplateField,
+    is_root: bool,
+    editing: &mut Option<(usize, String)>,
+    actions: &mut TreeActions,
+) {
+    if matches!(field.kind, FieldKind::Struct | FieldKind::Array) {
+        let title = format!("{}: {}", field.name, field.type_name);
+        let response = egui::CollapsingHeader::new(egui::RichText::new(title).monospace())
+            .id_salt(("template_field", field.address, &field.name))
+            .default_open(is_root)
+            .show(ui, |ui| {
+                for child in &field.children {
+                    show_field(ui, child, false, editing, actions);
+                }
+            });
+        let header = response
+            .header_response
+            .on_hover_text(format!("0x{:X}, {} bytes", field.address, field.size));
+        if header.hovered() {
+            actions.hovered = Some(field_range(field));
+        }
+        return;
+    }
+
+    ui.horizontal(|ui| {
+        let name = ui
+            .add(
+                egui::Label::new(egui::RichText::new(&field.name).monospace())
+                    .sense(egui::Sense::click()),
+            )
+            .on_hover_text(format!("{} at 0x{:X}", field.type_name, field.address));
+        if name.clicked() {
+            actions.select = Some(field_range(field));
+        }
+        let mut hovered = name.hovered();
+
+        match editing {
+            Some((addr, input)) if *addr == field.address => {
+                let text = ui.add(
+                    egui::TextEdit::singleline(input)
+                        .desired_width(140.0)
+                        .font(egui::TextStyle::Monospace),
+                );
+                // Focus the text field when the editing starts
+                if !text.has_focus() && !text.lost_focus() {
+                    text.request_focus();
+                }
+                actions.text_focused = true;
+
+                if text.lost_focus() {
+                    if ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+                        actions.commit = Some((*addr, input.clone()));
+                    }
+                    *editing = None;
+                }
+            }
+            _ => {
+                let value = ui
+                    .add(
+                        egui::Label::new(egui::RichText::new(format_value(field)).monospace())
+                            .sense(egui::Sense::click()),
+                    )
+                    .on_hover_text("Double-click to edit");
+                hovered |= value.hovered();
+                if value.clicked() {
+                    actions.select = Some(field_range(field));
+                }
+                if value.double_clicked()
+                    && let Some(current) = &field.value
+                {
+                    *editing = Some((field.address, current.to_string()));
+                }
+            }
+        }
+
+        ui.label(egui::RichText::new(&field.type_name).color(colors::GRAY_160));
+
+        if hovered {
+            actions.hovered = Some(field_range(field));
+        }
+    });
+}
+
+/// Value of a scalar or text field for display, e.g. `4096 (0x00001000)` or `"BOOT"`
+fn format_value(field: &TemplateField) -> String {
+    match (&field.value, field.kind) {
+        (None, _) => "--".to_string(),
+        (Some(FieldValue::Unsigned(v)), FieldKind::Scalar(ScalarType::Char)) => {
+            let c = u8::try_from(*v).ok().filter(u8::is_ascii_graphic);
+            c.map_or_else(
+                || format!("0x{v:02X}"),
+                |c| format!("'{}' (0x{v:02X})", c as char),
+            )
+        }
+        (Some(FieldValue::Unsigned(v)), _) => {
+            format!("{v} (0x{v:0width$X})", width = field.size * 2)
+        }
+        (Some(FieldValue::Text(text)), _) => format!("\"{text}\""),
+        (Some(value), _) => value.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_edited_text_keeps_unchanged_bytes() {
+        // Arrange - "AB", a non-printable byte shown as '.', the NUL and bytes after it
+        let current = [b'A', b'B', 0x01, 0x00, 0x55, 0x66];
+        let shown = "AB.";
+
+        // Act
+        let unchanged = edited_text(&current, shown, b"AB.\0");
+        let renamed = edited_text(&current, shown, b"XB.\0");
+        let shortened = edited_text(&current, shown, b"A\0");
+        let extended = edited_text(&current, shown, b"AB.C\0");
+
+        // Assert
+        assert_eq!(unchanged, current);
+        assert_eq!(renamed, [b'X', b'B', 0x01, 0x00, 0x55, 0x66]);
+        assert_eq!(shortened, [b'A', 0x00, 0x01, 0x00, 0x55, 0x66]);
+        assert_eq!(extended, [b'A', b'B', 0x01, b'C', 0x00, 0x66]);
+    }
+}
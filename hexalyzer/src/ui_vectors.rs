@@ -0,0 +1,87 @@
+use crate::app::{DataState, HexSession};
+use eframe::egui;
+use intelhexlib::VectorTable;
+
+#[derive(Default)]
+pub struct VectorTables {
+    /// Cortex-M vector tables found in the data
+    pub(crate) tables: Vec<VectorTable>,
+    /// State of the data the tables were detected in. `None` forces a new detection.
+    detected_for: Option<DataState>,
+}
+
+impl HexSession {
+    /// Detect the vector tables again if the data was edited since the last detection
+    pub(crate) fn update_vector_tables(&mut self) {
+        let state = self.data_state();
+        if self.vectors.detected_for != Some(state) {
+            self.vectors.tables = self.ih.vector_tables();
+            self.vectors.detected_for = Some(state);
+        }
+    }
+
+    /// Displays the detected Cortex-M vector tables: the initial stack pointer and the
+    /// handlers by exception name. Clicking an address jumps to it.
+    pub(crate) fn show_vectors_contents(&mut self, ui: &mut egui::Ui) {
+        let mut jump = None;
+
+        for (i, table) in self.vectors.tables.iter().enumerate() {
+            if ui
+                .link(format!(
+                    "Table at 0x{:08X} ({} entries)",
+                    table.address,
+                    table.entry_count()
+                ))
+                .clicked()
+            {
+                jump = Some(table.address);
+            }
+            ui.add_space(3.0);
+
+            let (system, irqs): (Vec<_>, Vec<_>) =
+                table.handlers().partition(|(index, _, _)| *index < 16);
+
+            egui::Grid::new(("vectors_grid", i))
+                .num_columns(2) // two columns: exception name + handler address
+                .spacing([30.0, 4.0]) // horizontal & vertical spacing
+                .show(ui, |ui| {
+                    ui.label("Initial SP");
+                    ui.monospace(format!("0x{:08X}", table.initial_sp));
+                    ui.end_row();
+
+                    for (_, name, handler) in &system {
+                        ui.label(name);
+                        if ui.link(format!("0x{handler:08X}")).clicked() {
+                            jump = Some(*handler);
+                        }
+                        ui.end_row();
+                    }
+                });
+
+            if !irqs.is_empty() {
+                egui::CollapsingHeader::new(format!("IRQ handlers ({})", irqs.len()))
+                    .id_salt(("vectors_irqs", i))
+                    .default_open(false)
+                    .show(ui, |ui| {
+                        egui::Grid::new(("vectors_irqs_grid", i))
+                            .num_columns(2)
+                            .spacing([30.0, 4.0])
+                            .show(ui, |ui| {
+                                for (_, name, handler) in &irqs {
+                                    ui.label(name);
+                                    if ui.link(format!("0x{handler:08X}")).clicked() {
+                                        jump = Some(*handler);
+                                    }
+                                    ui.end_row();
+                                }
+                            });
+                    });
+            }
+            ui.add_space(5.0);
+        }
+
+        if jump.is_some() {
+            self.jump_to.addr = jump;
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use criterion::{Criterion, criterion_group, criterion_main};
+use criterion::{Criterion, Throughput, criterion_group, criterion_main};
 use intelhexlib::IntelHex;
 
 #[allow(clippy::expect_used)]
@@ -22,6 +22,36 @@ fn bench_intelhex_parsing(c: &mut Criterion) {
         });
     });
 
+    c.bench_function("intelhex_parse_1mb_single_thread", |b| {
+        let hex_bytes = std::fs::read(hex_1mb).expect("Failed to read IntelHex file");
+
+        b.iter(|| {
+            #[cfg(feature = "benchmarking")]
+            let mut ih = IntelHex::new();
+            IntelHex::bench_priv_parse_with_threads(
+                std::hint::black_box(&mut ih),
+                std::hint::black_box(&hex_bytes),
+                1,
+            );
+            std::hint::black_box(&ih);
+        });
+    });
+
+    c.bench_function("intelhex_parse_1mb_4_threads", |b| {
+        let hex_bytes = std::fs::read(hex_1mb).expect("Failed to read IntelHex file");
+
+        b.iter(|| {
+            #[cfg(feature = "benchmarking")]
+            let mut ih = IntelHex::new();
+            IntelHex::bench_priv_parse_with_threads(
+                std::hint::black_box(&mut ih),
+                std::hint::black_box(&hex_bytes),
+                4,
+            );
+            std::hint::black_box(&ih);
+        });
+    });
+
     c.bench_function("intelhex_parse_sparse", |b| {
         let hex_bytes = std::fs::read(hex_sparse).expect("Failed to read IntelHex file");
 
@@ -66,9 +96,51 @@ fn bench_intelhex_parsing(c: &mut Criterion) {
     });
 }
 
+#[allow(clippy::expect_used)]
+fn bench_intelhex_writing(c: &mut Criterion) {
+    let hex_1mb = "../build/random_data_1MB.hex";
+    let hex_sparse = "../build/random_data_sparse.hex";
+
+    let mut group = c.benchmark_group("intelhex_write");
+
+    // Throughput is measured in bytes of the generated hex text
+    for (name, path) in [("1mb", hex_1mb), ("sparse", hex_sparse)] {
+        let ih = IntelHex::from_hex(path).expect("Failed to load IntelHex file");
+        let hex_len = std::fs::metadata(path)
+            .expect("Failed to read file size")
+            .len();
+        group.throughput(Throughput::Bytes(hex_len));
+
+        group.bench_function(format!("records_{name}"), |b| {
+            let mut out = Vec::with_capacity(usize::try_from(hex_len).unwrap_or_default());
+
+            b.iter(|| {
+                out.clear();
+                #[cfg(feature = "benchmarking")]
+                IntelHex::bench_priv_write_hex_records(
+                    std::hint::black_box(&ih),
+                    std::hint::black_box(&mut out),
+                );
+                std::hint::black_box(&out);
+            });
+        });
+    }
+
+    group.bench_function("write_hex_1mb", |b| {
+        let mut ih = IntelHex::from_hex(hex_1mb).expect("Failed to load IntelHex file");
+
+        b.iter(|| {
+            ih.write_hex(std::hint::black_box("../build/bench/random_data_1MB.hex"))
+                .expect("Failed to write IntelHex file");
+        });
+    });
+
+    group.finish();
+}
+
 criterion_group!(
     name = intelhexlib_benches;
     config = Criterion::default().sample_size(20);
-    targets = bench_intelhex_parsing
+    targets = bench_intelhex_parsing, bench_intelhex_writing
 );
 criterion_main!(intelhexlib_benches);
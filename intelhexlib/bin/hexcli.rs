@@ -1,9 +1,17 @@
-use intelhexlib::IntelHex;
+use intelhexlib::{
+    IntelHex, MemoryMap, NumericValue, SearchOptions, SignatureTable, StringEncoding, Transform,
+};
 use std::cmp::PartialEq;
 use std::env;
 use std::path::{Path, PathBuf};
 use std::process;
 
+/// Input file path with an optional (relocation / base) address
+type Input = (PathBuf, Option<usize>);
+
+/// Scan pattern as written in the pattern file and its bytes
+type Pattern = (String, Vec<u8>);
+
 #[derive(PartialEq, Eq)]
 enum FileType {
     Bin,
@@ -11,6 +19,14 @@ enum FileType {
     Other,
 }
 
+/// How `hexcli split` divides the input into parts
+enum SplitMode {
+    Segment,
+    Every(usize),
+    Region(MemoryMap),
+}
+
+#[allow(clippy::literal_string_with_formatting_args)]
 fn print_usage() {
     let version = env!("CARGO_PKG_VERSION");
 
@@ -18,20 +34,56 @@ fn print_usage() {
     println!("|  Intel HEX Utility  | v{version} - Copyright (c) 2026 Ihar Hlukhau |");
     println!(" ----------------------------------------------------------------");
     println!("\nUsage:");
-    println!("  hexcli info <input>");
+    println!("  hexcli info <input1>[:addr] ... <inputN>[:addr]");
     println!("  hexcli relocate <input> <output> [options]");
     println!("  hexcli convert <input> <output> [options]");
     println!("  hexcli merge <output> <input1>[:addr] ... <inputN>[:addr]");
+    println!("  hexcli transform <input> <output> --op <transform> [options]");
+    println!("  hexcli split <input> <name template> [options]");
+    println!("  hexcli split-lanes <input> <lane1> ... <laneN> [options]");
+    println!("  hexcli join-lanes <output> <lane1>[:addr] ... <laneN>[:addr] [options]");
+    println!("  hexcli scan <input> [options]");
+    println!("  hexcli search <input> --value <num> [options]");
+    println!("  hexcli strings <input> [options]");
+    println!("  hexcli entropy <input> [options]");
     println!("\nOptions:");
     println!("  --address <val>    Base address for relocate / convert from BIN to HEX");
     println!(
         "  --gap-fill <val>   Byte to fill gaps when converting / merging to BIN (default: 0xFF)"
     );
+    println!(
+        "  --op <transform>   swap16 | swap32 | swap64 | reverse-bits | invert | xor:<key bytes>"
+    );
+    println!("  --range <s>:<e>    Inclusive address range to transform / export to BIN");
+    println!("  --strict           Fail if data lies outside of the BIN export '--range'");
+    println!("  --every <size>     Split into parts of <size> bytes (default: split by segment)");
+    println!("  --region <n>:<s>:<e>  Split by named inclusive region (repeatable)");
+    println!(
+        "  --patterns <file>  Scan for one hex or \"ASCII\" pattern per line (default: file signatures)"
+    );
+    println!(
+        "  --value <num>      Integer or float to search for in all u16..u64 / f32 / f64 encodings"
+    );
+    println!("  --min-len <n>      Minimum length of extracted strings in characters (default: 4)");
+    println!("  --encoding <list>  Comma-separated ascii,utf8,utf16le,utf16be (default: all)");
+    println!("  --block <size>     Size of the blocks to compute the entropy of (default: 0x1000)");
     println!("\nExamples:");
     println!("  hexcli info firmware.hex");
+    println!("  hexcli info boot.hex app.hex config.bin:0x8000");
     println!("  hexcli relocate firmware.hex firmware_shifted.hex --address 0x1000");
     println!("  hexcli convert firmware.hex firmware.bin --gap-fill 0x00");
+    println!("  hexcli convert firmware.hex flash.bin --range 0x08000000:0x0803FFFF");
     println!("  hexcli merge final.hex firmware1.hex firmware2.bin:0xFF00");
+    println!("  hexcli transform dump.bin fixed.bin --op swap16 --range 0x0:0xFFFF");
+    println!("  hexcli split firmware.hex out/{{name}}_{{start:08X}}.bin --every 0x10000");
+    println!("  hexcli split firmware.hex {{name}}.hex --region boot:0x0:0x7FFF");
+    println!("  hexcli split-lanes firmware.hex even.bin odd.bin");
+    println!("  hexcli join-lanes firmware.hex even.bin odd.bin");
+    println!("  hexcli scan firmware.bin --address 0x08000000");
+    println!("  hexcli scan firmware.hex --patterns known_constants.txt");
+    println!("  hexcli search firmware.hex --value 115200");
+    println!("  hexcli strings firmware.hex --min-len 6 --encoding ascii,utf16le");
+    println!("  hexcli entropy firmware.hex --block 0x400");
 }
 
 fn main() {
@@ -62,13 +114,14 @@ fn run_dispatch(cmd: &str, args: &[String]) -> Result<(), Box<dyn std::error::Er
         }
         "info" => {
             // Guard: Check args count
-            let path_str = args.get(2).ok_or("Missing input file path")?;
+            if args.len() < 3 {
+                return Err("Missing input file path".into());
+            }
 
-            // Guard: File must exist
-            let abs_path =
-                validate_exists(path_str).map_err(|_| format!("File not found: {path_str}"))?;
+            // Collect input file paths and optional base addresses
+            let inputs = parse_inputs(&args[2..])?;
 
-            run_info(&abs_path)
+            run_info(inputs)
         }
         "relocate" => {
             // Guard: Check file path arguments given
@@ -90,12 +143,8 @@ fn run_dispatch(cmd: &str, args: &[String]) -> Result<(), Box<dyn std::error::Er
             }
 
             // Get relocation address
-            let addr_str = get_flag_value(args, "--address");
-            let addr = if let Some(addr) = addr_str {
-                parse_hex_str(&addr).map_err(|_e| format!("Invalid address: {addr}"))?
-            } else {
-                return Err("Missing '--address' flag or the value after it".into());
-            };
+            let addr =
+                get_address_flag(args)?.ok_or("Missing '--address' flag or the value after it")?;
 
             run_relocate(&in_abs_path, &out_path, addr)
         }
@@ -121,42 +170,46 @@ fn run_dispatch(cmd: &str, args: &[String]) -> Result<(), Box<dyn std::error::Er
                 return Err("Cannot convert between the same file type".into());
             }
 
-            let addr_str = get_flag_value(args, "--address");
-            let gap_fill_str = get_flag_value(args, "--gap-fill");
+            let base_addr = get_address_flag(args)?;
 
             // Guard: Check address is provided ONLY if converting FROM bin
-            if addr_str.is_some() && in_file_type != FileType::Bin {
+            if base_addr.is_some() && in_file_type != FileType::Bin {
                 return Err(
                     "Base address '--address' is only supported for BIN to HEX conversion".into(),
                 );
-            } else if addr_str.is_none() && in_file_type == FileType::Bin {
+            } else if base_addr.is_none() && in_file_type == FileType::Bin {
                 return Err(
                     "Base address '--address' is required for BIN to HEX conversion".into(),
                 );
             }
 
-            let base_addr = if let Some(addr) = addr_str {
-                Some(parse_hex_str(&addr).map_err(|_e| format!("Invalid address: {addr}"))?)
-            } else {
-                None
-            };
-
             // Guard: Handle optional gap fill ONLY if converting TO bin
-            if gap_fill_str.is_some() && in_file_type != FileType::Hex {
+            if get_flag_value(args, "--gap-fill").is_some() && in_file_type != FileType::Hex {
                 return Err(
                     "Gap fill '--gap-fill' is only supported for HEX to BIN conversion".into(),
                 );
             }
-            let gap_fill = if let Some(gap_fill) = gap_fill_str {
-                u8::try_from(
-                    parse_hex_str(&gap_fill)
-                        .map_err(|_e| format!("Invalid gap fill: {gap_fill}"))?,
-                )?
+            let gap_fill = get_gap_fill_flag(args)?;
+
+            // Guard: Handle optional address window ONLY if converting TO bin
+            let range_str = get_flag_value(args, "--range");
+            let strict = args.iter().any(|arg| arg == "--strict");
+            if (range_str.is_some() || strict) && out_file_type != FileType::Bin {
+                return Err(
+                    "Flags '--range' and '--strict' are only supported for conversion to BIN"
+                        .into(),
+                );
+            }
+            if strict && range_str.is_none() {
+                return Err("Flag '--strict' requires '--range'".into());
+            }
+            let window = if let Some(range_str) = range_str {
+                Some((parse_range_str(&range_str)?, strict))
             } else {
-                0xFF
+                None
             };
 
-            run_convert(&in_abs_path, &out_path, base_addr, gap_fill)
+            run_convert(&in_abs_path, &out_path, base_addr, gap_fill, window)
         }
         "merge" => {
             if args.len() < 5 {
@@ -170,36 +223,269 @@ fn run_dispatch(cmd: &str, args: &[String]) -> Result<(), Box<dyn std::error::Er
             let out_path = PathBuf::from(out_path_str);
 
             // Collect input file paths and optional base addresses
-            let mut inputs: Vec<(PathBuf, Option<usize>)> = Vec::new();
-            for arg in &args[3..] {
-                if arg.starts_with("--") {
-                    break; // stop at flags
+            let inputs = parse_inputs(&args[3..])?;
+
+            let gap_fill = get_gap_fill_flag(args)?;
+
+            run_merge(inputs, &out_path, gap_fill)
+        }
+        "transform" => {
+            // Guard: Check file paths arguments given
+            let in_path_str = args.get(2).ok_or("Missing input path")?;
+            let out_path_str = args.get(3).ok_or("Missing output path")?;
+
+            // Guard: Check input exists
+            let in_abs_path = validate_exists(in_path_str)?;
+
+            let out_path = PathBuf::from(out_path_str);
+
+            // Guard: Check files are of a supported type
+            if get_file_type(&in_abs_path) == FileType::Other
+                || get_file_type(&out_path) == FileType::Other
+            {
+                return Err("Input or output files are of unsupported type".into());
+            }
+
+            // Get the transform
+            let op_str =
+                get_flag_value(args, "--op").ok_or("Missing '--op' flag or the value after it")?;
+            let transform: Transform = op_str.parse()?;
+
+            // Get optional address range
+            let range = if let Some(range_str) = get_flag_value(args, "--range") {
+                Some(parse_range_str(&range_str)?)
+            } else {
+                None
+            };
+
+            // Get optional base address of BIN input (default: 0x0)
+            let base_addr = get_address_flag(args)?.unwrap_or(0x0);
+
+            let gap_fill = get_gap_fill_flag(args)?;
+
+            run_transform(
+                &in_abs_path,
+                &out_path,
+                &transform,
+                range,
+                base_addr,
+                gap_fill,
+            )
+        }
+        "split" => {
+            // Guard: Check file path arguments given
+            let in_path_str = args.get(2).ok_or("Missing input path")?;
+            let template = args.get(3).ok_or("Missing output name template")?;
+
+            // Guard: Check input exists
+            let in_abs_path = validate_exists(in_path_str)?;
+
+            // Guard: Check files are of a supported type
+            if get_file_type(&in_abs_path) == FileType::Other
+                || get_file_type(Path::new(template)) == FileType::Other
+            {
+                return Err("Input or output files are of unsupported type".into());
+            }
+
+            // Get the split mode
+            let every_str = get_flag_value(args, "--every");
+            let region_strs = get_flag_values(args, "--region");
+
+            // Guard: Check only one split mode given
+            if every_str.is_some() && !region_strs.is_empty() {
+                return Err("Flags '--every' and '--region' cannot be combined".into());
+            }
+
+            let mode = if let Some(every) = every_str {
+                SplitMode::Every(
+                    parse_hex_str(&every).map_err(|_e| format!("Invalid size: {every}"))?,
+                )
+            } else if region_strs.is_empty() {
+                SplitMode::Segment
+            } else {
+                let mut map = MemoryMap::new();
+                for region_str in &region_strs {
+                    let (name, range_str) = region_str.split_once(':').ok_or_else(|| {
+                        format!("Invalid region (expected <name>:<start>:<end>): {region_str}")
+                    })?;
+                    let (start, end) = parse_range_str(range_str)?;
+                    map.add_region(name, start, end)?;
                 }
+                SplitMode::Region(map)
+            };
+
+            // Get optional base address of BIN input (default: 0x0)
+            let base_addr = get_address_flag(args)?.unwrap_or(0x0);
+
+            let gap_fill = get_gap_fill_flag(args)?;
+
+            run_split(&in_abs_path, template, &mode, base_addr, gap_fill)
+        }
+        "split-lanes" => {
+            if args.len() < 5 {
+                return Err(
+                    "Usage: hexcli split-lanes <input> <lane1> ... <laneN> [options]".into(),
+                );
+            }
+
+            // Guard: Check input exists
+            let in_abs_path = validate_exists(&args[2])?;
+
+            // Collect lane output paths (one per lane)
+            let out_paths: Vec<PathBuf> = args[3..]
+                .iter()
+                .take_while(|arg| !arg.starts_with("--"))
+                .map(PathBuf::from)
+                .collect();
+
+            // Guard: Check files are of a supported type
+            if get_file_type(&in_abs_path) == FileType::Other
+                || out_paths
+                    .iter()
+                    .any(|p| get_file_type(p) == FileType::Other)
+            {
+                return Err("Input or output files are of unsupported type".into());
+            }
 
-                let parts: Vec<&str> = arg.split(':').collect();
-                let in_abs_path = validate_exists(parts[0])?;
-                let addr = if parts.len() > 1 {
-                    Some(
-                        parse_hex_str(parts[1])
-                            .map_err(|_e| format!("Invalid address: {}", parts[1]))?,
-                    )
-                } else {
-                    None
-                };
-                inputs.push((in_abs_path, addr));
+            // Guard: Check at least two lanes requested
+            if out_paths.len() < 2 {
+                return Err("At least two lane output paths are required".into());
             }
 
-            let gap_fill_str = get_flag_value(args, "--gap-fill");
-            let gap_fill = if let Some(gap_fill) = gap_fill_str {
-                u8::try_from(
-                    parse_hex_str(&gap_fill)
-                        .map_err(|_e| format!("Invalid gap fill: {gap_fill}"))?,
-                )?
+            // Get optional base address of BIN input (default: 0x0)
+            let base_addr = get_address_flag(args)?.unwrap_or(0x0);
+
+            let gap_fill = get_gap_fill_flag(args)?;
+
+            run_split_lanes(&in_abs_path, &out_paths, base_addr, gap_fill)
+        }
+        "join-lanes" => {
+            if args.len() < 5 {
+                return Err(
+                    "Usage: hexcli join-lanes <output> <lane1>[:addr] ... <laneN>[:addr]".into(),
+                );
+            }
+
+            // Guard: Check output file path argument given
+            let out_path = PathBuf::from(&args[2]);
+
+            // Collect lane input paths and optional base addresses
+            let inputs = parse_inputs(&args[3..])?;
+
+            // Guard: Check at least two lanes given
+            if inputs.len() < 2 {
+                return Err("At least two lane input paths are required".into());
+            }
+
+            let gap_fill = get_gap_fill_flag(args)?;
+
+            run_join_lanes(inputs, &out_path, gap_fill)
+        }
+        "scan" => {
+            // Guard: Check input file path argument given
+            let in_path_str = args.get(2).ok_or("Missing input path")?;
+
+            // Guard: Check input exists
+            let in_abs_path = validate_exists(in_path_str)?;
+
+            // Guard: Check input is of a supported type
+            if get_file_type(&in_abs_path) == FileType::Other {
+                return Err("Input file is of unsupported type".into());
+            }
+
+            // Guard: Check pattern file exists (if given)
+            let patterns_path = get_flag_value(args, "--patterns")
+                .map(|path| validate_exists(&path))
+                .transpose()?;
+
+            // Get optional base address of BIN input (default: 0x0)
+            let base_addr = get_address_flag(args)?.unwrap_or(0x0);
+
+            // Scan for the patterns of the file or for the built-in file signatures
+            patterns_path.map_or_else(
+                || run_scan_signatures(&in_abs_path, base_addr),
+                |patterns_path| run_scan(&in_abs_path, &patterns_path, base_addr),
+            )
+        }
+        "search" => {
+            // Guard: Check input file path argument given
+            let in_path_str = args.get(2).ok_or("Missing input path")?;
+
+            // Guard: Check input exists
+            let in_abs_path = validate_exists(in_path_str)?;
+
+            // Guard: Check input is of a supported type
+            if get_file_type(&in_abs_path) == FileType::Other {
+                return Err("Input file is of unsupported type".into());
+            }
+
+            // Get the value to search for
+            let value_str = get_flag_value(args, "--value")
+                .ok_or("Missing '--value' flag or the value after it")?;
+            let value: NumericValue = value_str.parse()?;
+
+            // Get optional base address of BIN input (default: 0x0)
+            let base_addr = get_address_flag(args)?.unwrap_or(0x0);
+
+            run_search(&in_abs_path, &value, base_addr)
+        }
+        "strings" => {
+            // Guard: Check input file path argument given
+            let in_path_str = args.get(2).ok_or("Missing input path")?;
+
+            // Guard: Check input exists
+            let in_abs_path = validate_exists(in_path_str)?;
+
+            // Guard: Check input is of a supported type
+            if get_file_type(&in_abs_path) == FileType::Other {
+                return Err("Input file is of unsupported type".into());
+            }
+
+            // Get optional minimum length (default: 4)
+            let min_len = if let Some(len) = get_flag_value(args, "--min-len") {
+                len.parse::<usize>()
+                    .map_err(|_e| format!("Invalid minimum length: {len}"))?
             } else {
-                0xFF
+                4
             };
 
-            run_merge(inputs, &out_path, gap_fill)
+            // Get optional encodings (default: all)
+            let encodings = if let Some(list) = get_flag_value(args, "--encoding") {
+                list.split(',')
+                    .map(str::parse)
+                    .collect::<Result<Vec<StringEncoding>, String>>()?
+            } else {
+                StringEncoding::ALL.to_vec()
+            };
+
+            // Get optional base address of BIN input (default: 0x0)
+            let base_addr = get_address_flag(args)?.unwrap_or(0x0);
+
+            run_strings(&in_abs_path, min_len, &encodings, base_addr)
+        }
+        "entropy" => {
+            // Guard: Check input file path argument given
+            let in_path_str = args.get(2).ok_or("Missing input path")?;
+
+            // Guard: Check input exists
+            let in_abs_path = validate_exists(in_path_str)?;
+
+            // Guard: Check input is of a supported type
+            if get_file_type(&in_abs_path) == FileType::Other {
+                return Err("Input file is of unsupported type".into());
+            }
+
+            // Get optional block size (default: 0x1000)
+            let block_size = if let Some(size) = get_flag_value(args, "--block") {
+                parse_hex_str(&size).map_err(|_e| format!("Invalid block size: {size}"))?
+            } else {
+                0x1000
+            };
+
+            // Get optional base address of BIN input (default: 0x0)
+            let base_addr = get_address_flag(args)?.unwrap_or(0x0);
+
+            run_entropy(&in_abs_path, block_size, base_addr)
         }
         _ => {
             print_usage();
@@ -208,7 +494,7 @@ fn run_dispatch(cmd: &str, args: &[String]) -> Result<(), Box<dyn std::error::Er
     }
 }
 
-fn run_info(path: &PathBuf) -> Result<(), Box<dyn std::error::Error>> {
+fn run_info(inputs: Vec<Input>) -> Result<(), Box<dyn std::error::Error>> {
     fn format_addr(addr: usize) -> String {
         let s = format!("{addr:08X}");
         format!("0x{}_{}", &s[0..4], &s[4..8])
@@ -224,21 +510,83 @@ fn run_info(path: &PathBuf) -> Result<(), Box<dyn std::error::Error>> {
             .join(",")
     }
 
-    let ih = if get_file_type(path) == FileType::Hex {
-        IntelHex::from_hex(path)?
-    } else if get_file_type(path) == FileType::Bin {
-        IntelHex::from_bin(path, 0x0)?
-    } else {
-        return Err(format!("File type not supported: {}", path.display()).into());
-    };
+    let paths: Vec<String> = inputs
+        .iter()
+        .map(|(path, _)| path.display().to_string())
+        .collect();
+
+    // Merge all inputs into one image while keeping track of the source of every byte
+    let mut ih = IntelHex::new();
+    for (path, addr) in inputs {
+        if get_file_type(&path) == FileType::Other {
+            return Err(format!("File type not supported: {}", path.display()).into());
+        }
+
+        // Binary files without explicit address are placed at 0x0
+        let addr = if get_file_type(&path) == FileType::Bin {
+            addr.or(Some(0x0))
+        } else {
+            addr
+        };
+
+        let mut input_ih = load_input(&path, addr)?;
+        let name = path.file_name().map_or_else(
+            || path.display().to_string(),
+            |n| n.to_string_lossy().into_owned(),
+        );
+        input_ih.tag_source(&name);
+
+        ih.merge(&input_ih);
+    }
 
-    println!("File Path:   {}", path.display());
+    println!("File Path:   {}", paths.join("\n             "));
     println!("Data Size:   {} bytes", format_with_commas(ih.size));
     println!(
         "Range:       {} - {}",
         format_addr(ih.get_min_addr().unwrap_or(0)),
         format_addr(ih.get_max_addr().unwrap_or(0)),
     );
+
+    // Per-source layout table
+    if let Some(provenance) = ih.provenance() {
+        println!("\nLayout:");
+        println!(
+            "  {:<11}   {:<11}   {:>13}   Source",
+            "Start", "End", "Size"
+        );
+        for range in provenance.ranges() {
+            let size = format!("{} bytes", format_with_commas(range.end - range.start + 1));
+            println!(
+                "  {}   {}   {size:>13}   {}",
+                format_addr(range.start),
+                format_addr(range.end),
+                provenance.names()[range.source],
+            );
+        }
+    }
+
+    // Cortex-M vector tables with their system handlers and number of interrupt handlers
+    for table in ih.vector_tables() {
+        println!(
+            "\nVector Table: {} ({} entries)",
+            format_addr(table.address),
+            table.entry_count()
+        );
+        println!(
+            "  {:<13} {}",
+            "Initial SP",
+            format_addr(table.initial_sp as usize)
+        );
+        let mut irqs = 0;
+        for (index, name, handler) in table.handlers() {
+            if index < 16 {
+                println!("  {name:<13} {}", format_addr(handler));
+            } else {
+                irqs += 1;
+            }
+        }
+        println!("  {:<13} {irqs} handlers", "IRQs");
+    }
     Ok(())
 }
 
@@ -247,13 +595,20 @@ fn run_convert(
     out_path: &PathBuf,
     addr: Option<usize>,
     gap_fill: u8,
+    window: Option<((usize, usize), bool)>,
 ) -> Result<(), Box<dyn std::error::Error>> {
     let mut ih = match addr {
         Some(base) => IntelHex::from_bin(in_path, base)?,
         None => IntelHex::from_hex(in_path)?,
     };
 
-    if get_file_type(out_path) == FileType::Bin {
+    if let Some(((start, end), strict)) = window {
+        if strict {
+            ih.write_bin_range_safe(out_path, start, end, gap_fill)?;
+        } else {
+            ih.write_bin_range(out_path, start, end, gap_fill)?;
+        }
+    } else if get_file_type(out_path) == FileType::Bin {
         ih.write_bin(out_path, gap_fill)?;
     } else {
         ih.write_hex(out_path)?;
@@ -291,32 +646,14 @@ fn run_relocate(
 }
 
 fn run_merge(
-    inputs: Vec<(PathBuf, Option<usize>)>,
+    inputs: Vec<Input>,
     out_path: &PathBuf,
     gap_fill: u8,
 ) -> Result<(), Box<dyn std::error::Error>> {
     let mut master_ih = IntelHex::new();
 
     for (path, addr) in inputs {
-        let ih = match get_file_type(&path) {
-            FileType::Bin => {
-                let base_addr = addr.ok_or_else(|| {
-                    format!("Base address required for binary file: {}", path.display())
-                })?;
-                IntelHex::from_bin(&path, base_addr)?
-            }
-            FileType::Hex => {
-                let mut ih = IntelHex::from_hex(&path)?;
-                if let Some(new_addr) = addr {
-                    ih.relocate(new_addr)?;
-                }
-                ih
-            }
-            FileType::Other => {
-                return Err(format!("Unsupported file type: {}", path.display()).into());
-            }
-        };
-
+        let ih = load_input(&path, addr)?;
         master_ih.merge(&ih); // todo: use safe or not safe merge?
     }
 
@@ -333,8 +670,383 @@ fn run_merge(
     Ok(())
 }
 
+fn run_transform(
+    in_path: &PathBuf,
+    out_path: &PathBuf,
+    transform: &Transform,
+    range: Option<(usize, usize)>,
+    base_addr: usize,
+    gap_fill: u8,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut ih = match get_file_type(in_path) {
+        FileType::Bin => IntelHex::from_bin(in_path, base_addr)?,
+        _ => IntelHex::from_hex(in_path)?,
+    };
+
+    if let Some((start, end)) = range {
+        ih.transform_range(start, end, transform)?;
+    } else {
+        // Transform every contiguous chunk of data. Chunks that do not consist of whole
+        // words are reported and left unchanged.
+        let chunks: Vec<(usize, usize)> = ih
+            .iter()
+            .map(|(&start, data)| (start, start + data.len() - 1))
+            .collect();
+        let word_size = transform.word_size();
+        for (start, end) in chunks {
+            let len = end - start + 1;
+            if !len.is_multiple_of(word_size) {
+                eprintln!(
+                    "Skipped 0x{start:08X} - 0x{end:08X} ({len} bytes): \
+                     not a multiple of the {word_size}-byte word"
+                );
+                continue;
+            }
+            ih.transform_range(start, end, transform)?;
+        }
+    }
+
+    if get_file_type(out_path) == FileType::Bin {
+        ih.write_bin(out_path, gap_fill)?;
+    } else {
+        ih.write_hex(out_path)?;
+    }
+
+    // Validate output file was written
+    let out_abs_path = validate_exists(&out_path.to_string_lossy())?;
+
+    println!(
+        "Applied '{transform}' to {} -> {}",
+        in_path.display(),
+        out_abs_path.display()
+    );
+    Ok(())
+}
+
+fn run_split(
+    in_path: &PathBuf,
+    template: &str,
+    mode: &SplitMode,
+    base_addr: usize,
+    gap_fill: u8,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let ih = match get_file_type(in_path) {
+        FileType::Bin => IntelHex::from_bin(in_path, base_addr)?,
+        _ => IntelHex::from_hex(in_path)?,
+    };
+
+    let mut parts = match mode {
+        SplitMode::Segment => ih.split_by_segment(),
+        SplitMode::Every(size) => ih.split_every(*size)?,
+        SplitMode::Region(map) => ih.split_by_region(map),
+    };
+
+    // Fill the template for every part first, so that nothing is written if two parts
+    // would end up in the same file
+    let mut out_paths = Vec::with_capacity(parts.len());
+    for (index, (name, part)) in parts.iter().enumerate() {
+        let start = part.get_min_addr().unwrap_or(0);
+        let end = part.get_max_addr().unwrap_or(0);
+        let out_path = PathBuf::from(format_template(template, name, index, start, end)?);
+        if out_paths.contains(&out_path) {
+            return Err(format!(
+                "Template '{template}' gives the same file name {} for several parts, \
+                 use {{name}}, {{index}} or {{start}} to tell them apart",
+                out_path.display()
+            )
+            .into());
+        }
+        out_paths.push(out_path);
+    }
+
+    for ((name, part), out_path) in parts.iter_mut().zip(out_paths) {
+        let start = part.get_min_addr().unwrap_or(0);
+        let end = part.get_max_addr().unwrap_or(0);
+
+        // Create the output directory if the template contains one
+        if let Some(dir) = out_path.parent()
+            && !dir.as_os_str().is_empty()
+        {
+            std::fs::create_dir_all(dir)?;
+        }
+
+        if get_file_type(&out_path) == FileType::Bin {
+            part.write_bin(&out_path, gap_fill)?;
+        } else {
+            part.write_hex(&out_path)?;
+        }
+
+        // Validate output file was written
+        let out_abs_path = validate_exists(&out_path.to_string_lossy())?;
+        println!(
+            "{name:<12} 0x{start:08X} - 0x{end:08X} -> {}",
+            out_abs_path.display()
+        );
+    }
+
+    println!("Split {} into {} files", in_path.display(), parts.len());
+    Ok(())
+}
+
+fn run_split_lanes(
+    in_path: &PathBuf,
+    out_paths: &[PathBuf],
+    base_addr: usize,
+    gap_fill: u8,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let ih = match get_file_type(in_path) {
+        FileType::Bin => IntelHex::from_bin(in_path, base_addr)?,
+        _ => IntelHex::from_hex(in_path)?,
+    };
+
+    let lanes = ih.split_lanes(out_paths.len())?;
+
+    for (mut lane, out_path) in lanes.into_iter().zip(out_paths) {
+        if get_file_type(out_path) == FileType::Bin {
+            lane.write_bin(out_path, gap_fill)?;
+        } else {
+            lane.write_hex(out_path)?;
+        }
+
+        // Validate output file was written
+        let out_abs_path = validate_exists(&out_path.to_string_lossy())?;
+        println!("Lane -> {}", out_abs_path.display());
+    }
+
+    println!(
+        "Split {} into {} byte lanes",
+        in_path.display(),
+        out_paths.len()
+    );
+    Ok(())
+}
+
+fn run_join_lanes(
+    inputs: Vec<Input>,
+    out_path: &PathBuf,
+    gap_fill: u8,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut lanes = Vec::with_capacity(inputs.len());
+    for (path, addr) in inputs {
+        // Binary lanes without explicit address are placed at 0x0
+        let addr = if get_file_type(&path) == FileType::Bin {
+            addr.or(Some(0x0))
+        } else {
+            addr
+        };
+        lanes.push(load_input(&path, addr)?);
+    }
+
+    let mut ih = IntelHex::join_lanes(&lanes)?;
+
+    if get_file_type(out_path) == FileType::Bin {
+        ih.write_bin(out_path, gap_fill)?;
+    } else {
+        ih.write_hex(out_path)?;
+    }
+
+    // Validate output file was written
+    let out_abs_path = validate_exists(&out_path.to_string_lossy())?;
+
+    println!(
+        "Joined {} byte lanes into {}",
+        lanes.len(),
+        out_abs_path.display()
+    );
+    Ok(())
+}
+
+fn run_scan(
+    in_path: &PathBuf,
+    patterns_path: &Path,
+    base_addr: usize,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let ih = match get_file_type(in_path) {
+        FileType::Bin => IntelHex::from_bin(in_path, base_addr)?,
+        _ => IntelHex::from_hex(in_path)?,
+    };
+
+    let patterns = parse_pattern_file(patterns_path)?;
+    let bytes: Vec<&[u8]> = patterns.iter().map(|(_, bytes)| bytes.as_slice()).collect();
+
+    let matches = ih.search_many(&bytes);
+    for &(idx, addr) in &matches {
+        println!("0x{addr:08X}  {}", patterns[idx].0);
+    }
+
+    println!(
+        "Found {} matches of {} patterns in {}",
+        matches.len(),
+        patterns.len(),
+        in_path.display()
+    );
+    Ok(())
+}
+
+fn run_scan_signatures(
+    in_path: &PathBuf,
+    base_addr: usize,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let ih = match get_file_type(in_path) {
+        FileType::Bin => IntelHex::from_bin(in_path, base_addr)?,
+        _ => IntelHex::from_hex(in_path)?,
+    };
+
+    let hits = ih.scan_signatures(&SignatureTable::new());
+    for hit in &hits {
+        let length = hit
+            .info
+            .length
+            .map_or_else(String::new, |len| format!(" ({len} bytes)"));
+        println!(
+            "0x{:08X}  {:<16}  {}{length}",
+            hit.address, hit.name, hit.info.description
+        );
+    }
+
+    println!(
+        "Found {} embedded files in {}",
+        hits.len(),
+        in_path.display()
+    );
+    Ok(())
+}
+
+fn run_search(
+    in_path: &PathBuf,
+    value: &NumericValue,
+    base_addr: usize,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let ih = match get_file_type(in_path) {
+        FileType::Bin => IntelHex::from_bin(in_path, base_addr)?,
+        _ => IntelHex::from_hex(in_path)?,
+    };
+
+    let matches = ih.search_value(value, &SearchOptions::default());
+    for (addr, encoding) in &matches {
+        println!("0x{addr:08X}  {encoding}");
+    }
+
+    println!(
+        "Found {} matches of {value} in {}",
+        matches.len(),
+        in_path.display()
+    );
+    Ok(())
+}
+
+fn run_strings(
+    in_path: &PathBuf,
+    min_len: usize,
+    encodings: &[StringEncoding],
+    base_addr: usize,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let ih = match get_file_type(in_path) {
+        FileType::Bin => IntelHex::from_bin(in_path, base_addr)?,
+        _ => IntelHex::from_hex(in_path)?,
+    };
+
+    let mut count = 0;
+    for (addr, encoding, text) in ih.strings(min_len, encodings) {
+        println!(
+            "0x{addr:08X}  {:<8}  {}",
+            encoding.to_string(),
+            text.escape_debug()
+        );
+        count += 1;
+    }
+
+    println!("Found {count} strings in {}", in_path.display());
+    Ok(())
+}
+
+fn run_entropy(
+    in_path: &PathBuf,
+    block_size: usize,
+    base_addr: usize,
+) -> Result<(), Box<dyn std::error::Error>> {
+    /// Width of the bar of the maximum entropy (8 bits per byte)
+    const BAR_WIDTH: usize = 48;
+
+    let ih = match get_file_type(in_path) {
+        FileType::Bin => IntelHex::from_bin(in_path, base_addr)?,
+        _ => IntelHex::from_hex(in_path)?,
+    };
+
+    let blocks = ih.entropy(block_size)?;
+
+    println!(
+        "{:<10}  {:>7}  0{:>w$}8",
+        "Block",
+        "Entropy",
+        "",
+        w = BAR_WIDTH - 1
+    );
+    for (addr, entropy) in &blocks {
+        #[allow(
+            clippy::cast_possible_truncation,
+            clippy::cast_sign_loss,
+            clippy::cast_precision_loss
+        )]
+        let len = (entropy / 8.0 * BAR_WIDTH as f64).round() as usize;
+        println!(
+            "0x{addr:08X}  {entropy:>7.3}  |{:<BAR_WIDTH$}|",
+            "#".repeat(len)
+        );
+    }
+
+    println!(
+        "Analyzed {} blocks of 0x{block_size:X} bytes in {}",
+        blocks.len(),
+        in_path.display()
+    );
+    Ok(())
+}
+
 // =============================== HELPER FUNCTIONS ===============================
 
+/// Parse `<input>[:addr]` arguments into absolute paths and optional base addresses.
+/// Stops at the first flag argument.
+fn parse_inputs(args: &[String]) -> Result<Vec<Input>, Box<dyn std::error::Error>> {
+    let mut inputs: Vec<Input> = Vec::new();
+    for arg in args {
+        if arg.starts_with("--") {
+            break; // stop at flags
+        }
+
+        let parts: Vec<&str> = arg.split(':').collect();
+        let in_abs_path = validate_exists(parts[0])?;
+        let addr = if parts.len() > 1 {
+            Some(parse_hex_str(parts[1]).map_err(|_e| format!("Invalid address: {}", parts[1]))?)
+        } else {
+            None
+        };
+        inputs.push((in_abs_path, addr));
+    }
+    Ok(inputs)
+}
+
+/// Load a HEX or BIN input file. HEX files are relocated to `addr` if given,
+/// BIN files require `addr` as their base address.
+fn load_input(path: &PathBuf, addr: Option<usize>) -> Result<IntelHex, Box<dyn std::error::Error>> {
+    match get_file_type(path) {
+        FileType::Bin => {
+            let base_addr = addr.ok_or_else(|| {
+                format!("Base address required for binary file: {}", path.display())
+            })?;
+            Ok(IntelHex::from_bin(path, base_addr)?)
+        }
+        FileType::Hex => {
+            let mut ih = IntelHex::from_hex(path)?;
+            if let Some(new_addr) = addr {
+                ih.relocate(new_addr)?;
+            }
+            Ok(ih)
+        }
+        FileType::Other => Err(format!("Unsupported file type: {}", path.display()).into()),
+    }
+}
+
 /// Parse a string as a hex number (with optional 0x prefix)
 fn parse_hex_str(s: &str) -> Result<usize, std::num::ParseIntError> {
     let s = s.trim();
@@ -348,6 +1060,124 @@ fn parse_hex_str(s: &str) -> Result<usize, std::num::ParseIntError> {
     usize::from_str_radix(s, 16)
 }
 
+/// Read a pattern file for `hexcli scan`. Every non-empty line that is not a `#` comment is
+/// either a sequence of hex bytes (`DE AD BE EF`, `DEADBEEF`) or a quoted ASCII string
+/// (`"DEBUG"`). Returns the patterns as written in the file together with their bytes.
+fn parse_pattern_file(path: &Path) -> Result<Vec<Pattern>, Box<dyn std::error::Error>> {
+    let content = std::fs::read_to_string(path)?;
+    let mut patterns = Vec::new();
+
+    for (num, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let bytes = if let Some(ascii) = line
+            .strip_prefix('"')
+            .and_then(|rest| rest.strip_suffix('"'))
+        {
+            ascii.as_bytes().to_vec()
+        } else {
+            let digits: String = line.split_whitespace().collect();
+            if !digits.len().is_multiple_of(2) || !digits.is_ascii() {
+                return Err(format!("Invalid pattern at line {}: {line}", num + 1).into());
+            }
+            (0..digits.len())
+                .step_by(2)
+                .map(|i| u8::from_str_radix(&digits[i..i + 2], 16))
+                .collect::<Result<Vec<u8>, _>>()
+                .map_err(|_e| format!("Invalid pattern at line {}: {line}", num + 1))?
+        };
+
+        if bytes.is_empty() {
+            return Err(format!("Empty pattern at line {}", num + 1).into());
+        }
+        patterns.push((line.to_string(), bytes));
+    }
+
+    Ok(patterns)
+}
+
+/// Parse an inclusive address range given as `<start>:<end>` (hex numbers)
+fn parse_range_str(s: &str) -> Result<(usize, usize), Box<dyn std::error::Error>> {
+    let (start_str, end_str) = s
+        .split_once(':')
+        .ok_or_else(|| format!("Invalid range (expected <start>:<end>): {s}"))?;
+    let start =
+        parse_hex_str(start_str).map_err(|_e| format!("Invalid range start: {start_str}"))?;
+    let end = parse_hex_str(end_str).map_err(|_e| format!("Invalid range end: {end_str}"))?;
+    if start > end {
+        return Err(format!("Invalid range (start is after end): {s}").into());
+    }
+    Ok((start, end))
+}
+
+/// Fill the output name template. Supported placeholders are `{name}`, `{index}`,
+/// `{start}` and `{end}`. Numbers accept a format spec of the form `[0][width][X|x|d]`,
+/// e.g. `{start:08X}`.
+fn format_template(
+    template: &str,
+    name: &str,
+    index: usize,
+    start: usize,
+    end: usize,
+) -> Result<String, Box<dyn std::error::Error>> {
+    let mut out = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(open) = rest.find('{') {
+        out.push_str(&rest[..open]);
+        let close = rest[open..]
+            .find('}')
+            .ok_or_else(|| format!("Unclosed placeholder in template: {template}"))?;
+        let placeholder = &rest[open + 1..open + close];
+        rest = &rest[open + close + 1..];
+
+        let (key, spec) = placeholder.split_once(':').unwrap_or((placeholder, ""));
+        let value = match key {
+            "name" => {
+                out.push_str(name);
+                continue;
+            }
+            "index" => index,
+            "start" => start,
+            "end" => end,
+            _ => return Err(format!("Unknown placeholder in template: {{{placeholder}}}").into()),
+        };
+
+        // Parse the format spec: [0][width][X|x|d]
+        let (spec, radix) = match spec.chars().last() {
+            Some('X') => (&spec[..spec.len() - 1], 'X'),
+            Some('x') => (&spec[..spec.len() - 1], 'x'),
+            Some('d') => (&spec[..spec.len() - 1], 'd'),
+            _ => (spec, 'd'),
+        };
+        let zero_pad = spec.starts_with('0');
+        let width: usize = if spec.is_empty() {
+            0
+        } else {
+            spec.parse()
+                .map_err(|_e| format!("Invalid format spec in template: {{{placeholder}}}"))?
+        };
+
+        let formatted = match radix {
+            'X' => format!("{value:X}"),
+            'x' => format!("{value:x}"),
+            _ => value.to_string(),
+        };
+        let pad = if zero_pad { '0' } else { ' ' };
+        out.extend(std::iter::repeat_n(
+            pad,
+            width.saturating_sub(formatted.len()),
+        ));
+        out.push_str(&formatted);
+    }
+
+    out.push_str(rest);
+    Ok(out)
+}
+
 /// Determine `FileType` based on the file's extension (case-insensitive)
 fn get_file_type(path: &Path) -> FileType {
     if path
@@ -377,6 +1207,31 @@ fn validate_exists(path_str: &str) -> Result<PathBuf, Box<dyn std::error::Error>
     Ok(std::fs::canonicalize(path)?)
 }
 
+/// Find the values after every occurrence of a repeatable flag (e.g., "--region a:0:F")
+fn get_flag_values(args: &[String], flag: &str) -> Vec<String> {
+    args.windows(2)
+        .filter(|pair| pair[0] == flag)
+        .map(|pair| pair[1].clone())
+        .collect()
+}
+
+/// Parse the base address after the `--address` flag, if given
+fn get_address_flag(args: &[String]) -> Result<Option<usize>, String> {
+    get_flag_value(args, "--address")
+        .map(|addr| parse_hex_str(&addr).map_err(|_e| format!("Invalid address: {addr}")))
+        .transpose()
+}
+
+/// Parse the gap fill byte after the `--gap-fill` flag (default: 0xFF)
+fn get_gap_fill_flag(args: &[String]) -> Result<u8, String> {
+    get_flag_value(args, "--gap-fill").map_or(Ok(0xFF), |gap_fill| {
+        parse_hex_str(&gap_fill)
+            .ok()
+            .and_then(|value| u8::try_from(value).ok())
+            .ok_or_else(|| format!("Invalid gap fill: {gap_fill}"))
+    })
+}
+
 /// Find the value after a specific flag (e.g., "--gap-fill 0xFF")
 fn get_flag_value(args: &[String], flag: &str) -> Option<String> {
     args.iter()
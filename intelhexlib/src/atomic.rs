@@ -0,0 +1,190 @@
+//! The `atomic` module implements crash-safe file writes. Data is written to a temporary
+//! file in the destination directory, synced to disk and then renamed over the destination.
+//! A failure midway leaves the original file untouched.
+
+use crate::error::IntelHexError;
+use std::fs::{self, File};
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Counter that keeps names of temporary files unique within the process, so that
+/// concurrent writes of the same file do not share a temporary file
+static TEMP_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// Atomically replace the file at `filepath` with the contents produced by `write_fn`.
+/// Permissions of an existing destination file are kept.
+///
+/// # Errors
+/// Returns an error if `write_fn` fails or the file cannot be written, synced or renamed.
+/// The temporary file is removed in that case.
+pub fn write_atomic<P, F>(filepath: P, write_fn: F) -> Result<(), IntelHexError>
+where
+    P: AsRef<Path>,
+    F: FnOnce(&mut BufWriter<File>) -> Result<(), IntelHexError>,
+{
+    let filepath = filepath.as_ref();
+
+    // Ensure the parent directory exists
+    let parent = filepath.parent().unwrap_or_else(|| Path::new(""));
+    if !parent.as_os_str().is_empty() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let tmp_path = temp_path_for(filepath);
+
+    let res = write_and_sync(filepath, &tmp_path, write_fn)
+        .and_then(|()| fs::rename(&tmp_path, filepath).map_err(IntelHexError::from));
+
+    if res.is_err() {
+        let _ = fs::remove_file(&tmp_path);
+        return res;
+    }
+
+    sync_dir(parent);
+    Ok(())
+}
+
+/// Atomically replace the file at `filepath` with `contents`. See [`write_atomic`].
+///
+/// # Errors
+/// Returns an error if the file cannot be written, synced or renamed.
+///
+/// # Example
+/// ```
+/// use intelhexlib::write_file_atomic;
+///
+/// write_file_atomic("build/ex5/notes.txt", b"calibration at 0x8000\n").unwrap();
+///
+/// assert_eq!(std::fs::read("build/ex5/notes.txt").unwrap(), b"calibration at 0x8000\n");
+/// ```
+pub fn write_file_atomic<P: AsRef<Path>>(
+    filepath: P,
+    contents: &[u8],
+) -> Result<(), IntelHexError> {
+    write_atomic(filepath, |writer| Ok(writer.write_all(contents)?))
+}
+
+/// Write the temporary file and flush it to disk.
+fn write_and_sync<F>(filepath: &Path, tmp_path: &Path, write_fn: F) -> Result<(), IntelHexError>
+where
+    F: FnOnce(&mut BufWriter<File>) -> Result<(), IntelHexError>,
+{
+    let file = fs::OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .open(tmp_path)?;
+
+    // Keep permissions of the file being replaced
+    if let Ok(metadata) = fs::metadata(filepath) {
+        file.set_permissions(metadata.permissions())?;
+    }
+
+    // Wrap in BufWriter for efficient line-by-line writing
+    let mut writer = BufWriter::new(file);
+    write_fn(&mut writer)?;
+    writer.flush()?;
+
+    let file = writer
+        .into_inner()
+        .map_err(|e| IntelHexError::from(e.into_error()))?;
+    file.sync_all()?;
+    Ok(())
+}
+
+/// Temporary (hidden) file next to the destination, e.g. `dir/.firmware.hex.1234.0.tmp`.
+/// The name holds the process id and a counter, so every call gives a new name.
+fn temp_path_for(filepath: &Path) -> PathBuf {
+    let name = filepath
+        .file_name()
+        .map_or_else(|| "out".into(), |n| n.to_string_lossy());
+    let count = TEMP_COUNTER.fetch_add(1, Ordering::Relaxed);
+    filepath.with_file_name(format!(".{name}.{}.{count}.tmp", std::process::id()))
+}
+
+/// Sync the directory so that the rename itself survives a crash. Only possible on Unix,
+/// failures are ignored as the data itself is already on disk.
+fn sync_dir(dir: &Path) {
+    #[cfg(unix)]
+    {
+        let dir = if dir.as_os_str().is_empty() {
+            Path::new(".")
+        } else {
+            dir
+        };
+        if let Ok(d) = File::open(dir) {
+            let _ = d.sync_all();
+        }
+    }
+    #[cfg(not(unix))]
+    let _ = dir;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::IntelHexErrorKind;
+
+    #[test]
+    fn test_write_atomic_replaces_file() {
+        // Arrange
+        let path = std::env::temp_dir().join("intelhexlib_atomic_ok.bin");
+        let _ = fs::write(&path, b"old contents");
+
+        // Act
+        let res = write_atomic(&path, |w| Ok(w.write_all(b"new")?));
+
+        // Assert
+        assert!(res.is_ok());
+        assert_eq!(fs::read(&path).ok(), Some(b"new".to_vec()));
+        assert!(!has_temp_files(&path));
+    }
+
+    #[test]
+    fn test_write_atomic_keeps_original_on_error() {
+        // Arrange
+        let path = std::env::temp_dir().join("intelhexlib_atomic_err.bin");
+        let _ = fs::write(&path, b"old contents");
+
+        // Act - fail after writing part of the data
+        let res = write_atomic(&path, |w| {
+            w.write_all(b"partial")?;
+            Err(IntelHexError::CreateRecordError(
+                IntelHexErrorKind::RecordNotSupported,
+            ))
+        });
+
+        // Assert
+        assert!(res.is_err());
+        assert_eq!(fs::read(&path).ok(), Some(b"old contents".to_vec()));
+        assert!(!has_temp_files(&path));
+    }
+
+    #[test]
+    fn test_temp_path_is_unique() {
+        // Arrange
+        let path = Path::new("build/firmware.hex");
+
+        // Act
+        let first = temp_path_for(path);
+        let second = temp_path_for(path);
+
+        // Assert
+        assert_ne!(first, second);
+        assert_eq!(first.parent(), path.parent());
+    }
+
+    /// Check if any temporary file of `path` is left in its directory
+    fn has_temp_files(path: &Path) -> bool {
+        let prefix = format!(
+            ".{}.",
+            path.file_name().unwrap_or_default().to_string_lossy()
+        );
+        fs::read_dir(path.parent().unwrap_or_else(|| Path::new(".")))
+            .into_iter()
+            .flatten()
+            .flatten()
+            .any(|entry| entry.file_name().to_string_lossy().starts_with(&prefix))
+    }
+}
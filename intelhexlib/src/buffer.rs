@@ -0,0 +1,489 @@
+//! The `buffer` module implements [`ChunkMap`] - the sparse data buffer of an
+//! [`IntelHex`](crate::IntelHex) instance.
+//!
+//! Contiguous data chunks are reference counted and copied on write: cloning a buffer
+//! (e.g. by cloning `IntelHex` or taking a [`Snapshot`](crate::Snapshot)) only bumps the
+//! reference counts, and a chunk is copied the first time it is modified while shared
+//! (memory-mapped chunks only copy their patched pages, see [`MappedChunk`]).
+//! Unchanged chunks stay shared between all clones.
+//!
+//! The bytes of a chunk are stored by one of the backends of [`ChunkData`]: in memory,
+//! or memory-mapped from a file for images too large to be read at once.
+//!
+//! New storage backends are added as variants of [`ChunkData`], which is the intended
+//! extension point, and implement its methods (`as_slice`, `as_mut_range`, `as_mut_vec`,
+//! `into_vec` and `slice`). A backend trait behind [`ChunkMap`] was rejected: copying and
+//! splitting shared chunks depends on how each backend stores its bytes (a mapped chunk is
+//! mapped again and only its patches are copied), and a closed enum keeps `IntelHex` free of
+//! a type parameter and the hot read paths free of dynamic dispatch.
+
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::collections::btree_map;
+use std::fs::File;
+use std::ops::{Range, RangeBounds};
+use std::sync::Arc;
+
+/// Size of the pages whose edits a mapped chunk keeps track of
+const PATCH_PAGE_SIZE: usize = 4096;
+
+/// Storage backend of a single contiguous chunk.
+#[derive(Debug)]
+pub enum ChunkData {
+    /// Bytes held in memory
+    Owned(Vec<u8>),
+    /// Bytes of a file mapped into memory, see [`MappedChunk`]
+    Mapped(MappedChunk),
+}
+
+/// Region of a file mapped into memory. The mapping is private (copy-on-write): edited pages
+/// are copied into memory by the OS and form an overlay of patches, the file itself is never
+/// modified. The patched pages are tracked, so that copies and sub-ranges of the chunk map
+/// the file again and only copy the patches instead of the whole region.
+#[derive(Debug)]
+pub struct MappedChunk {
+    /// File the region is mapped from
+    file: Arc<File>,
+    /// Offset of the region in the file
+    offset: u64,
+    /// Private mapping of the region
+    map: memmap2::MmapMut,
+    /// Indices of the pages (of `PATCH_PAGE_SIZE` bytes from the start of the region)
+    /// that were written to
+    patched: BTreeSet<usize>,
+}
+
+impl MappedChunk {
+    /// Map `len` bytes of the file starting at `offset`.
+    fn new(file: Arc<File>, offset: u64, len: usize) -> std::io::Result<Self> {
+        // SAFETY: The mapping is private, so writes never reach the file. The file must not
+        // be truncated by another process while mapped - a limitation inherent to memory
+        // mapping, documented on `IntelHex::load_bin_mapped`.
+        let map = unsafe {
+            memmap2::MmapOptions::new()
+                .offset(offset)
+                .len(len)
+                .map_copy(&file)?
+        };
+        Ok(Self {
+            file,
+            offset,
+            map,
+            patched: BTreeSet::new(),
+        })
+    }
+
+    /// Mutable bytes in `range`. The pages of the range are marked as patched.
+    fn patch(&mut self, range: Range<usize>) -> &mut [u8] {
+        if !range.is_empty() {
+            let pages = range.start / PATCH_PAGE_SIZE..=(range.end - 1) / PATCH_PAGE_SIZE;
+            self.patched.extend(pages);
+        }
+        &mut self.map[range]
+    }
+
+    /// The bytes in `range` as a new mapping of the file with the patches of the range
+    /// copied over.
+    fn remap(&self, range: Range<usize>) -> std::io::Result<Self> {
+        let mut sub = Self::new(
+            Arc::clone(&self.file),
+            self.offset + range.start as u64,
+            range.len(),
+        )?;
+
+        let first = range.start / PATCH_PAGE_SIZE;
+        let last = range.end.div_ceil(PATCH_PAGE_SIZE);
+        for &page in self.patched.range(first..last) {
+            let start = (page * PATCH_PAGE_SIZE).max(range.start);
+            let end = ((page + 1) * PATCH_PAGE_SIZE).min(range.end);
+            sub.patch(start - range.start..end - range.start)
+                .copy_from_slice(&self.map[start..end]);
+        }
+
+        Ok(sub)
+    }
+}
+
+impl ChunkData {
+    /// Map `len` bytes of the file starting at `offset`.
+    pub fn map_file(file: Arc<File>, offset: u64, len: usize) -> std::io::Result<Self> {
+        MappedChunk::new(file, offset, len).map(Self::Mapped)
+    }
+
+    pub fn as_slice(&self) -> &[u8] {
+        match self {
+            Self::Owned(data) => data,
+            Self::Mapped(chunk) => &chunk.map,
+        }
+    }
+
+    /// Mutable bytes in `range` for in-place edits. Mapped chunks stay mapped,
+    /// only the pages of the range are patched.
+    pub fn as_mut_range(&mut self, range: Range<usize>) -> &mut [u8] {
+        match self {
+            Self::Owned(data) => &mut data[range],
+            Self::Mapped(chunk) => chunk.patch(range),
+        }
+    }
+
+    /// Mutable vector of the chunk for edits that change its length.
+    /// Mapped chunks are copied into memory first.
+    pub fn as_mut_vec(&mut self) -> &mut Vec<u8> {
+        match self {
+            Self::Owned(data) => data,
+            Self::Mapped(chunk) => {
+                // Now owned, so the recursion ends in the first arm
+                *self = Self::Owned(chunk.map.to_vec());
+                self.as_mut_vec()
+            }
+        }
+    }
+
+    /// Bytes of the chunk as a vector. Mapped chunks are copied into memory.
+    pub fn into_vec(self) -> Vec<u8> {
+        match self {
+            Self::Owned(data) => data,
+            Self::Mapped(chunk) => chunk.map.to_vec(),
+        }
+    }
+
+    /// The bytes in `range` as a new chunk. Large ranges of mapped chunks are mapped again
+    /// instead of being copied into memory.
+    pub fn slice(&self, range: Range<usize>) -> Self {
+        if let Self::Mapped(chunk) = self
+            && range.len() >= PATCH_PAGE_SIZE
+            && let Ok(sub) = chunk.remap(range.clone())
+        {
+            return Self::Mapped(sub);
+        }
+        Self::Owned(self.as_slice()[range].to_vec())
+    }
+
+    pub fn len(&self) -> usize {
+        self.as_slice().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.as_slice().is_empty()
+    }
+
+    pub const fn is_mapped(&self) -> bool {
+        matches!(self, Self::Mapped(_))
+    }
+}
+
+/// Cloning a mapped chunk maps the file again and copies only the patched pages.
+impl Clone for ChunkData {
+    fn clone(&self) -> Self {
+        self.slice(0..self.len())
+    }
+}
+
+impl PartialEq for ChunkData {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl Eq for ChunkData {}
+
+/// Maps start address of each contiguous data chunk to its shared storage.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ChunkMap {
+    chunks: BTreeMap<usize, Arc<ChunkData>>,
+}
+
+impl ChunkMap {
+    pub const fn new() -> Self {
+        Self {
+            chunks: BTreeMap::new(),
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.chunks.clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.chunks.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.chunks.is_empty()
+    }
+
+    pub fn insert(&mut self, addr: usize, data: Vec<u8>) {
+        self.chunks.insert(addr, Arc::new(ChunkData::Owned(data)));
+    }
+
+    /// Insert a chunk without copying it, sharing it with its other owners.
+    pub fn insert_shared(&mut self, addr: usize, data: Arc<ChunkData>) {
+        self.chunks.insert(addr, data);
+    }
+
+    #[allow(clippy::trivially_copy_pass_by_ref)] // mirrors `BTreeMap`
+    pub fn get(&self, addr: &usize) -> Option<&[u8]> {
+        self.chunks.get(addr).map(|d| d.as_slice())
+    }
+
+    #[allow(clippy::trivially_copy_pass_by_ref)] // mirrors `BTreeMap`
+    /// Mutable vector of the chunk, e.g. to append to it. The chunk is copied first if it
+    /// is shared or mapped.
+    pub fn get_mut(&mut self, addr: &usize) -> Option<&mut Vec<u8>> {
+        self.chunks
+            .get_mut(addr)
+            .map(|d| Arc::make_mut(d).as_mut_vec())
+    }
+
+    #[allow(clippy::trivially_copy_pass_by_ref)] // mirrors `BTreeMap`
+    /// Remove the chunk and return its data. The data is copied if the chunk is shared or mapped.
+    pub fn remove(&mut self, addr: &usize) -> Option<Vec<u8>> {
+        self.chunks
+            .remove(addr)
+            .map(|d| Arc::unwrap_or_clone(d).into_vec())
+    }
+
+    #[allow(clippy::trivially_copy_pass_by_ref)] // mirrors `BTreeMap`
+    /// Remove the chunk and return it without copying.
+    pub fn remove_shared(&mut self, addr: &usize) -> Option<Arc<ChunkData>> {
+        self.chunks.remove(addr)
+    }
+
+    pub fn first_key_value(&self) -> Option<(&usize, &[u8])> {
+        self.chunks
+            .first_key_value()
+            .map(|(k, v)| (k, v.as_slice()))
+    }
+
+    pub fn last_key_value(&self) -> Option<(&usize, &[u8])> {
+        self.chunks.last_key_value().map(|(k, v)| (k, v.as_slice()))
+    }
+
+    /// Mutable vector of the last chunk. The chunk is copied first if it is shared or mapped.
+    pub fn last_mut(&mut self) -> Option<(usize, &mut Vec<u8>)> {
+        self.chunks
+            .iter_mut()
+            .next_back()
+            .map(|(&k, v)| (k, Arc::make_mut(v).as_mut_vec()))
+    }
+
+    pub fn range<R: RangeBounds<usize>>(
+        &self,
+        range: R,
+    ) -> impl DoubleEndedIterator<Item = (&usize, &[u8])> {
+        self.chunks.range(range).map(|(k, v)| (k, v.as_slice()))
+    }
+
+    /// Iterator over the shared chunks in the range.
+    pub fn range_shared<R: RangeBounds<usize>>(
+        &self,
+        range: R,
+    ) -> impl DoubleEndedIterator<Item = (usize, &Arc<ChunkData>)> {
+        self.chunks.range(range).map(|(&k, v)| (k, v))
+    }
+
+    /// Mutable bytes `[addr, addr + len)` for in-place edits, if they lie within a single
+    /// chunk. The chunk is copied first if it is shared (mapped chunks only copy their
+    /// patches, see [`MappedChunk`]).
+    pub fn slice_mut(&mut self, addr: usize, len: usize) -> Option<&mut [u8]> {
+        let (&chunk_start, chunk) = self.chunks.range_mut(..=addr).next_back()?;
+        let offset = addr - chunk_start;
+        let end = offset.checked_add(len).filter(|&end| end <= chunk.len())?;
+        Some(Arc::make_mut(chunk).as_mut_range(offset..end))
+    }
+
+    pub fn iter(&self) -> Chunks<'_> {
+        Chunks {
+            inner: self.chunks.iter(),
+        }
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &[u8]> {
+        self.chunks.values().map(|d| d.as_slice())
+    }
+
+    /// Iterator over the shared chunks, e.g. to insert them into another buffer without copying.
+    pub fn iter_shared(&self) -> impl Iterator<Item = (usize, &Arc<ChunkData>)> {
+        self.chunks.iter().map(|(&k, v)| (k, v))
+    }
+
+    /// Consume the buffer and return the shared chunks without copying.
+    pub fn into_shared(self) -> impl Iterator<Item = (usize, Arc<ChunkData>)> {
+        self.chunks.into_iter()
+    }
+
+    /// Move every chunk to a new start address given by `f`, without copying data.
+    /// `f` must keep the order of the chunks and must not make them overlap.
+    pub fn map_keys(&mut self, f: impl Fn(usize) -> usize) {
+        self.chunks = std::mem::take(&mut self.chunks)
+            .into_iter()
+            .map(|(addr, data)| (f(addr), data))
+            .collect();
+    }
+
+    /// Returns `true` if any chunk is memory-mapped from a file.
+    pub fn is_mapped(&self) -> bool {
+        self.chunks.values().any(|d| d.is_mapped())
+    }
+
+    /// Returns `true` if the chunk at `addr` is the very same allocation in both buffers.
+    #[cfg(test)]
+    pub fn shares_chunk(&self, other: &Self, addr: usize) -> bool {
+        self.chunks
+            .get(&addr)
+            .zip(other.chunks.get(&addr))
+            .is_some_and(|(a, b)| Arc::ptr_eq(a, b))
+    }
+}
+
+impl<'a> IntoIterator for &'a ChunkMap {
+    type Item = (&'a usize, &'a [u8]);
+    type IntoIter = Chunks<'a>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl IntoIterator for ChunkMap {
+    type Item = (usize, Vec<u8>);
+    type IntoIter = IntoChunks;
+    fn into_iter(self) -> Self::IntoIter {
+        IntoChunks {
+            inner: self.chunks.into_iter(),
+        }
+    }
+}
+
+/// Borrowing iterator over (address, data chunk) pairs of an [`IntelHex`](crate::IntelHex),
+/// see [`IntelHex::iter`](crate::IntelHex::iter).
+#[derive(Debug, Clone)]
+pub struct Chunks<'a> {
+    inner: btree_map::Iter<'a, usize, Arc<ChunkData>>,
+}
+
+impl<'a> Iterator for Chunks<'a> {
+    type Item = (&'a usize, &'a [u8]);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|(k, v)| (k, v.as_slice()))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for Chunks<'_> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back().map(|(k, v)| (k, v.as_slice()))
+    }
+}
+
+impl ExactSizeIterator for Chunks<'_> {}
+
+/// Consuming iterator over (address, data chunk) pairs of an [`IntelHex`](crate::IntelHex).
+/// Chunks still shared with other instances or memory-mapped are copied when yielded.
+#[derive(Debug)]
+pub struct IntoChunks {
+    inner: btree_map::IntoIter<usize, Arc<ChunkData>>,
+}
+
+impl Iterator for IntoChunks {
+    type Item = (usize, Vec<u8>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner
+            .next()
+            .map(|(k, v)| (k, Arc::unwrap_or_clone(v).into_vec()))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl DoubleEndedIterator for IntoChunks {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner
+            .next_back()
+            .map(|(k, v)| (k, Arc::unwrap_or_clone(v).into_vec()))
+    }
+}
+
+impl ExactSizeIterator for IntoChunks {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clone_shares_chunks_until_written() {
+        // Arrange
+        let mut buffer = ChunkMap::new();
+        buffer.insert(0x00, vec![0x1; 0x10]);
+        buffer.insert(0x20, vec![0x2; 0x10]);
+
+        // Act
+        let mut copy = buffer.clone();
+        let shared_before = copy.shares_chunk(&buffer, 0x00) && copy.shares_chunk(&buffer, 0x20);
+        if let Some(data) = copy.slice_mut(0x20, 1) {
+            data[0] = 0xFF;
+        }
+
+        // Assert
+        assert!(shared_before);
+        assert!(copy.shares_chunk(&buffer, 0x00));
+        assert!(!copy.shares_chunk(&buffer, 0x20));
+        assert_eq!(buffer.get(&0x20).map(|d| d[0]), Some(0x2));
+        assert_eq!(copy.get(&0x20).map(|d| d[0]), Some(0xFF));
+    }
+
+    #[test]
+    fn test_mapped_chunk_copies_only_patches() {
+        // Arrange
+        let file = File::open("tests/fixtures/ih_valid_1.bin").map(Arc::new);
+        let mut chunk = file
+            .and_then(|f| ChunkData::map_file(f, 0, 51596))
+            .unwrap_or_else(|_| ChunkData::Owned(Vec::new()));
+        chunk
+            .as_mut_range(0x1000..0x1002)
+            .copy_from_slice(&[0xAA, 0xBB]);
+
+        // Act
+        let copy = chunk.clone();
+        let sub = chunk.slice(0x800..0x2000);
+        let small = chunk.slice(0x1000..0x1010);
+
+        // Assert
+        assert!(copy.is_mapped());
+        assert!(sub.is_mapped());
+        assert!(!small.is_mapped());
+        assert_eq!(copy, chunk);
+        assert_eq!(sub.as_slice(), &chunk.as_slice()[0x800..0x2000]);
+        assert_eq!(small.as_slice()[..2], [0xAA, 0xBB]);
+        // The patched page 0x1000..0x2000 lands on pages 0 and 1 of the sub-range
+        if let ChunkData::Mapped(sub) = &sub {
+            assert_eq!(sub.patched.iter().copied().collect::<Vec<_>>(), vec![0, 1]);
+        }
+    }
+
+    #[test]
+    fn test_map_keys_keeps_chunks_shared() {
+        // Arrange
+        let mut buffer = ChunkMap::new();
+        buffer.insert(0x00, vec![0x1; 0x10]);
+        let original = buffer.clone();
+
+        // Act
+        buffer.map_keys(|addr| addr + 0x100);
+
+        // Assert
+        assert_eq!(buffer.get(&0x100), original.get(&0x00));
+        assert!(
+            buffer
+                .iter_shared()
+                .zip(original.iter_shared())
+                .all(|((_, a), (_, b))| Arc::ptr_eq(a, b))
+        );
+    }
+}
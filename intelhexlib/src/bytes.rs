@@ -0,0 +1,44 @@
+//! The `bytes` module reads fixed-size unsigned integers from byte slices in either byte
+//! order. Used by the header decoders of the `signatures` and `symbols` modules.
+
+/// Little-endian `u16` at `offset`, `None` if the data ends before it.
+pub fn le_u16(data: &[u8], offset: usize) -> Option<u16> {
+    Some(u16::from_le_bytes(
+        data.get(offset..offset.checked_add(2)?)?.try_into().ok()?,
+    ))
+}
+
+/// Big-endian `u16` at `offset`, `None` if the data ends before it.
+pub fn be_u16(data: &[u8], offset: usize) -> Option<u16> {
+    Some(u16::from_be_bytes(
+        data.get(offset..offset.checked_add(2)?)?.try_into().ok()?,
+    ))
+}
+
+/// Little-endian `u32` at `offset`, `None` if the data ends before it.
+pub fn le_u32(data: &[u8], offset: usize) -> Option<u32> {
+    Some(u32::from_le_bytes(
+        data.get(offset..offset.checked_add(4)?)?.try_into().ok()?,
+    ))
+}
+
+/// Big-endian `u32` at `offset`, `None` if the data ends before it.
+pub fn be_u32(data: &[u8], offset: usize) -> Option<u32> {
+    Some(u32::from_be_bytes(
+        data.get(offset..offset.checked_add(4)?)?.try_into().ok()?,
+    ))
+}
+
+/// Little-endian `u64` at `offset`, `None` if the data ends before it.
+pub fn le_u64(data: &[u8], offset: usize) -> Option<u64> {
+    Some(u64::from_le_bytes(
+        data.get(offset..offset.checked_add(8)?)?.try_into().ok()?,
+    ))
+}
+
+/// Big-endian `u64` at `offset`, `None` if the data ends before it.
+pub fn be_u64(data: &[u8], offset: usize) -> Option<u64> {
+    Some(u64::from_be_bytes(
+        data.get(offset..offset.checked_add(8)?)?.try_into().ok()?,
+    ))
+}
@@ -15,15 +15,23 @@ pub enum IntelHexError {
     ParseRecordError(IntelHexErrorKind, usize),
     CreateRecordError(IntelHexErrorKind),
     UpdateError(IntelHexErrorKind),
+    AnalysisError(IntelHexErrorKind),
+    ParseSymbolsError(IntelHexErrorKind),
+    ParseTemplateError(IntelHexErrorKind, usize),
     Io(io::Error),
 }
 
 impl PartialEq for IntelHexError {
     fn eq(&self, other: &Self) -> bool {
         match (self, other) {
-            (Self::ParseRecordError(a, la), Self::ParseRecordError(b, lb)) => a == b && la == lb,
+            (Self::ParseRecordError(a, la), Self::ParseRecordError(b, lb))
+            | (Self::ParseTemplateError(a, la), Self::ParseTemplateError(b, lb)) => {
+                a == b && la == lb
+            }
             (Self::CreateRecordError(a), Self::CreateRecordError(b))
-            | (Self::UpdateError(a), Self::UpdateError(b)) => a == b,
+            | (Self::UpdateError(a), Self::UpdateError(b))
+            | (Self::AnalysisError(a), Self::AnalysisError(b))
+            | (Self::ParseSymbolsError(a), Self::ParseSymbolsError(b)) => a == b,
             (Self::Io(a), Self::Io(b)) => a.kind() == b.kind(),
             _ => false,
         }
@@ -53,6 +61,24 @@ impl fmt::Display for IntelHexError {
                     "Error encountered during update of IntelHex instance:\n{base_err}",
                 )
             }
+            Self::AnalysisError(base_err) => {
+                write!(
+                    f,
+                    "Error encountered during analysis of IntelHex instance:\n{base_err}",
+                )
+            }
+            Self::ParseSymbolsError(base_err) => {
+                write!(
+                    f,
+                    "Error encountered during parsing of symbol file:\n{base_err}",
+                )
+            }
+            Self::ParseTemplateError(base_err, line) => {
+                write!(
+                    f,
+                    "Error encountered during parsing of structure template at line #{line}:\n{base_err}",
+                )
+            }
             Self::Io(err) => {
                 write!(f, "I/O error: {err}")
             }
@@ -111,9 +137,36 @@ pub enum IntelHexErrorKind {
     RelocateAddressOverflow(usize),
     /// Parsed address range exceeds the maximum supported (32-bit)
     AddressRangeOverflow,
+    /// Range length is not a multiple of the transform's word size (word size, range length)
+    TransformLengthInvalid(usize, usize),
+    /// XOR transform key is empty
+    TransformKeyEmpty,
+    /// Number of byte lanes is invalid
+    InvalidLaneCount(usize),
+    /// Size of the parts to split the data into is invalid
+    InvalidSplitSize(usize),
+    /// Size of the blocks to analyze the data in is invalid
+    InvalidBlockSize(usize),
+    /// Memory region overlaps with an existing one
+    MemoryRegionOverlap(usize),
+    /// Data found outside of the requested address window
+    DataOutsideRange(usize),
+    /// Symbol file is neither an ELF file nor a supported linker map file
+    SymbolFormatUnknown,
+    /// ELF file is truncated or its headers point outside of the file
+    ElfMalformed,
+    /// ELF file has no symbol table (e.g. stripped)
+    ElfNoSymbolTable,
+    /// Structure template does not follow the C declaration syntax (what was expected)
+    TemplateSyntax(String),
+    /// Structure template uses a type that is neither built-in nor defined before
+    TemplateUnknownType(String),
+    /// Structure template uses a C feature that templates do not support (e.g. unions)
+    TemplateUnsupported(String),
 }
 
 impl fmt::Display for IntelHexErrorKind {
+    #[allow(clippy::too_many_lines)]
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Self::MissingStartCode => {
@@ -179,6 +232,57 @@ impl fmt::Display for IntelHexErrorKind {
             Self::AddressRangeOverflow => {
                 write!(f, "Maximum address exceeds 32-bit range")
             }
+            Self::TransformLengthInvalid(word_size, length) => {
+                write!(
+                    f,
+                    "Range length of {length} bytes is not a multiple of the {word_size}-byte word"
+                )
+            }
+            Self::TransformKeyEmpty => {
+                write!(f, "Transform key is empty")
+            }
+            Self::InvalidLaneCount(lanes) => {
+                write!(f, "Invalid number of byte lanes: {lanes}")
+            }
+            Self::InvalidSplitSize(size) => {
+                write!(f, "Invalid split size: {size} bytes")
+            }
+            Self::InvalidBlockSize(size) => {
+                write!(f, "Invalid block size: {size} bytes")
+            }
+            Self::DataOutsideRange(address) => {
+                write!(
+                    f,
+                    "Data found outside of the address window at: 0x{address:X}"
+                )
+            }
+            Self::SymbolFormatUnknown => {
+                write!(
+                    f,
+                    "Unknown symbol file format, expected ELF or GNU ld / IAR / Keil map file"
+                )
+            }
+            Self::ElfMalformed => {
+                write!(f, "Malformed ELF file")
+            }
+            Self::ElfNoSymbolTable => {
+                write!(f, "ELF file has no symbol table")
+            }
+            Self::TemplateSyntax(expected) => {
+                write!(f, "Syntax error, expected {expected}")
+            }
+            Self::TemplateUnknownType(name) => {
+                write!(f, "Unknown type: {name}")
+            }
+            Self::TemplateUnsupported(feature) => {
+                write!(f, "Not supported in templates: {feature}")
+            }
+            Self::MemoryRegionOverlap(address) => {
+                write!(
+                    f,
+                    "Memory region overlaps with existing one at address: 0x{address:X}"
+                )
+            }
         }
     }
 }
@@ -6,12 +6,32 @@
 //! The module also provides utilities for binary file interop, memory relocation,
 //! and generating valid Intel HEX output with configurable record sizes.
 
+use crate::atomic::write_atomic;
+use crate::buffer::{ChunkData, ChunkMap, Chunks, IntoChunks};
 use crate::error::{IntelHexError, IntelHexErrorKind};
-use crate::record::{Record, RecordType};
-use crate::search::{SearchType, search};
+use crate::journal::{Bytes, Change, Journal, Recording, SharedChunks};
+use crate::memory_map::MemoryMap;
+use crate::parse;
+use crate::provenance::{Provenance, TagsDiff};
+use crate::record::{LARGEST_RECORD_LINE, Record, RecordType};
+use crate::search::{
+    MaskedPattern, NumericValue, SearchOptions, SearchType, ValueEncoding, search, search_hamming,
+    search_many, search_value,
+};
+use crate::signatures::{SignatureHit, SignatureTable, scan_signatures};
+use crate::snapshot::Snapshot;
+use crate::stats::{add_to_histogram, block_entropy};
+use crate::strings::{StringEncoding, find_strings};
+use crate::templates::{Template, TemplateField, decode_template};
+use crate::transform::Transform;
+use crate::vectors::{VectorTable, find_vector_tables};
+use crate::xrefs::{Reference, ReferenceIndex, find_references};
 use std::collections::BTreeMap;
+use std::fs::File;
 use std::io::Write;
+use std::ops::{Bound, RangeBounds};
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 
 #[derive(Debug, Clone)]
 pub struct IntelHex {
@@ -28,7 +48,12 @@ pub struct IntelHex {
     offset: usize,
     /// Data buffer of the Intel HEX file.
     /// Maps start address of each contiguous data chunk to a vector of bytes.
-    buffer: BTreeMap<usize, Vec<u8>>,
+    /// Chunks are shared between clones and copied on write.
+    buffer: ChunkMap,
+    /// Optional table of source tags. `None` when provenance tracking is disabled.
+    provenance: Option<Provenance>,
+    /// Optional undo/redo journal. `None` when journaling is disabled.
+    journal: Option<Journal>,
 }
 
 impl Default for IntelHex {
@@ -43,7 +68,7 @@ impl Default for IntelHex {
 /// the behavior of [`IntelHex::read_range_safe`] but without allocating a `Vec`.
 pub struct IterRange<'a> {
     /// Reference to the sparse buffer that stores contiguous data chunks.
-    buffer: &'a BTreeMap<usize, Vec<u8>>,
+    buffer: &'a ChunkMap,
     /// Next address to yield. Advances by 1 on each `next()` call.
     cur_addr: usize,
     /// One-past-the-end address. The iterator stops when `cur_addr >= end_addr`.
@@ -51,7 +76,7 @@ pub struct IterRange<'a> {
     /// Cached reference to the chunk we're currently iterating through.
     /// Avoids repeated `BTreeMap::range` lookups for contiguous data.
     /// Set to `None` when we advance past the chunk's end or enter a gap.
-    current_chunk: Option<(usize, &'a Vec<u8>)>,
+    current_chunk: Option<(usize, &'a [u8])>,
 }
 
 impl Iterator for IterRange<'_> {
@@ -103,11 +128,129 @@ impl Iterator for IterRange<'_> {
 
 impl ExactSizeIterator for IterRange<'_> {}
 
+/// Lazy iterator over the start addresses of the matches of a byte pattern in the `IntelHex`
+/// buffer, in ascending order. See [`IntelHex::search_bytes_iter`].
+pub struct SearchIter<'a> {
+    /// Reference to the sparse buffer that stores contiguous data chunks.
+    buffer: &'a ChunkMap,
+    /// Byte pattern to search for. Never empty unless the iterator is exhausted.
+    pattern: Vec<u8>,
+    /// Byte the gaps are treated as filled with, if matches may span gaps.
+    gap_fill: Option<u8>,
+    /// Whether the pattern consists of the gap fill byte only, i.e. matches inside gaps.
+    matches_gaps: bool,
+    /// Next candidate start address.
+    pos: usize,
+    /// Last address a match may end at. `None` once the iterator is exhausted.
+    end: Option<usize>,
+    /// Number of matches that may still be yielded.
+    remaining: usize,
+}
+
+impl SearchIter<'_> {
+    /// Byte at the address, with gaps filled with the gap fill byte (if any).
+    fn byte_at(&self, addr: usize) -> Option<u8> {
+        self.buffer
+            .range(..=addr)
+            .next_back()
+            .and_then(|(&start, data)| data.get(addr - start).copied())
+            .or(self.gap_fill)
+    }
+
+    /// Start address of the first chunk at or after the address.
+    fn chunk_after(&self, addr: usize) -> Option<usize> {
+        self.buffer.range(addr..).next().map(|(&start, _)| start)
+    }
+}
+
+impl Iterator for SearchIter<'_> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let len = self.pattern.len();
+
+        while self.remaining > 0
+            && let Some(end) = self.end
+        {
+            let pos = self.pos;
+
+            // Exhausted once the window at `pos` would end past the search end
+            let Some(last) = pos.checked_add(len - 1).filter(|&last| last <= end) else {
+                self.end = None;
+                break;
+            };
+
+            // Chunk that contains `pos`, if any
+            let chunk = self
+                .buffer
+                .range(..=pos)
+                .next_back()
+                .filter(|&(&start, data)| pos < start + data.len());
+
+            match chunk {
+                Some((&start, data)) if last < start + data.len() => {
+                    // Fast path: the window lies inside the chunk, search the rest of it at once
+                    let limit = data.len().min(end - start + 1);
+                    let found = data[pos - start..limit]
+                        .windows(len)
+                        .position(|window| window == self.pattern);
+                    if let Some(i) = found {
+                        self.pos = pos + i + 1;
+                        self.remaining -= 1;
+                        return Some(pos + i);
+                    }
+                    // Continue with the first window that ends past the searched part
+                    self.pos = start + limit - len + 1;
+                    continue;
+                }
+                Some(_) if self.gap_fill.is_none() => {
+                    // The window runs past the end of the chunk, continue with the next one
+                    let next_start = self.chunk_after(pos + 1);
+                    self.end = next_start.and(self.end);
+                    self.pos = next_start.unwrap_or(pos);
+                    continue;
+                }
+                Some(_) => {}
+                None => {
+                    // `pos` lies in a gap
+                    let next_start = self.chunk_after(pos);
+                    if self.gap_fill.is_none() {
+                        self.end = next_start.and(self.end);
+                        self.pos = next_start.unwrap_or(pos);
+                        continue;
+                    }
+                    if next_start.is_none_or(|next| last < next) {
+                        // The window lies entirely in the gap
+                        if self.matches_gaps {
+                            self.pos = pos + 1;
+                            self.remaining -= 1;
+                            return Some(pos);
+                        }
+                        // Skip to the first window that reaches into the next chunk
+                        self.end = next_start.and(self.end);
+                        self.pos = next_start.map_or(pos, |next| next + 1 - len);
+                        continue;
+                    }
+                }
+            }
+
+            // The window spans a chunk border and a gap, compare it byte by byte
+            self.pos = pos + 1;
+            if (0..len).all(|i| self.byte_at(pos + i) == Some(self.pattern[i])) {
+                self.remaining -= 1;
+                return Some(pos);
+            }
+        }
+
+        None
+    }
+}
+
 /// Borrowing iterator over (address, data chunk) pairs in the `BTreeMap` buffer of the `IntelHex`.
 /// Replicates the structure of the internal buffer and has the highest performance.
 impl<'a> IntoIterator for &'a IntelHex {
-    type Item = (&'a usize, &'a Vec<u8>);
-    type IntoIter = std::collections::btree_map::Iter<'a, usize, Vec<u8>>;
+    type Item = (&'a usize, &'a [u8]);
+    type IntoIter = Chunks<'a>;
     fn into_iter(self) -> Self::IntoIter {
         self.buffer.iter()
     }
@@ -115,9 +258,11 @@ impl<'a> IntoIterator for &'a IntelHex {
 
 /// Consuming iterator over (address, data chunk) pairs in the `BTreeMap` buffer of the `IntelHex`.
 /// Replicates the structure of the internal buffer and has the highest performance.
+/// Yields owned `Vec<u8>` chunks as before; chunks still shared with other instances or
+/// memory-mapped are copied when yielded.
 impl IntoIterator for IntelHex {
     type Item = (usize, Vec<u8>);
-    type IntoIter = std::collections::btree_map::IntoIter<usize, Vec<u8>>;
+    type IntoIter = IntoChunks;
     fn into_iter(self) -> Self::IntoIter {
         self.buffer.into_iter()
     }
@@ -141,7 +286,9 @@ impl IntelHex {
             offset: 0,
             max_payload_size: 16,
             start_addr: None,
-            buffer: BTreeMap::new(),
+            buffer: ChunkMap::new(),
+            provenance: None,
+            journal: None,
         }
     }
 
@@ -163,6 +310,10 @@ impl IntelHex {
         self.start_addr = None;
         self.offset = 0;
         self.buffer.clear();
+        self.provenance = None;
+        if let Some(journal) = self.journal.as_mut() {
+            journal.clear();
+        }
     }
 
     /// Check if a new range `[start, end)` overlaps with existing chunks.
@@ -238,14 +389,135 @@ impl IntelHex {
         }
     }
 
-    #[allow(clippy::too_many_lines)]
     /// Parse the raw contents of the hex file and fill internal buffer.
+    /// Large inputs are parsed on several threads.
     ///
     /// # Errors
     /// - Returns an error if the record is corrupted
     /// - Returns an error if there is an issue during filling the internal buffer
     ///
     fn parse(&mut self, raw_bytes: &[u8]) -> Result<(), IntelHexError> {
+        self.parse_with_threads(raw_bytes, parse::thread_count(raw_bytes.len()))
+    }
+
+    /// Parse the raw contents of the hex file on `threads` threads (see [`IntelHex::parse`]).
+    fn parse_with_threads(
+        &mut self,
+        raw_bytes: &[u8],
+        threads: usize,
+    ) -> Result<(), IntelHexError> {
+        if threads > 1 {
+            self.parse_parallel(raw_bytes, threads)
+        } else {
+            self.parse_sequential(raw_bytes)
+        }
+    }
+
+    /// Decode parts of the input concurrently and resolve the decoded records in order.
+    fn parse_parallel(&mut self, raw_bytes: &[u8], threads: usize) -> Result<(), IntelHexError> {
+        let parts = parse::split_lines(raw_bytes, threads);
+
+        let decoded: Vec<parse::DecodedPart> = std::thread::scope(|scope| {
+            // All threads must be spawned before the first one is joined
+            #[allow(clippy::needless_collect)]
+            let handles: Vec<_> = parts
+                .iter()
+                .map(|part| scope.spawn(|| parse::decode_part(part)))
+                .collect();
+            handles
+                .into_iter()
+                .map(|handle| {
+                    handle
+                        .join()
+                        .unwrap_or_else(|e| std::panic::resume_unwind(e))
+                })
+                .collect()
+        });
+
+        // Number of records in the parts resolved so far
+        let mut count: usize = 0;
+
+        for part in decoded {
+            for entry in &part.entries {
+                match entry {
+                    parse::Entry::Data {
+                        address,
+                        first,
+                        data,
+                        lens,
+                    } => {
+                        let addr = *address as usize + self.offset;
+                        let data = &part.data[data.clone()];
+
+                        if self.check_no_overlap(addr, addr + data.len()).is_ok() {
+                            self.insert_chunk_with_merge(addr, data.to_vec());
+                        } else {
+                            // Insert record by record to report the exact overlapping one
+                            self.insert_records(
+                                addr,
+                                data,
+                                &part.lens[lens.clone()],
+                                count + first,
+                            )?;
+                        }
+                    }
+                    parse::Entry::Offset(offset) => self.offset = *offset,
+                    parse::Entry::StartAddr { index, raw } => {
+                        if self.start_addr.is_some() {
+                            return Err(IntelHexError::ParseRecordError(
+                                IntelHexErrorKind::DuplicateStartAddress,
+                                count + index + 1,
+                            ));
+                        }
+                        if raw.is_some() {
+                            self.start_addr = *raw;
+                        }
+                    }
+                }
+            }
+
+            if let Some((err, index)) = part.error {
+                return Err(IntelHexError::ParseRecordError(err, count + index));
+            }
+
+            count += part.records;
+        }
+
+        self.check_parsed_addr_range(count)
+    }
+
+    /// Insert data of consecutive data records at `addr`, checking each record for overlaps.
+    /// `first` is the line number of the first record.
+    fn insert_records(
+        &mut self,
+        mut addr: usize,
+        data: &[u8],
+        lens: &[u8],
+        first: usize,
+    ) -> Result<(), IntelHexError> {
+        let mut pos = 0;
+
+        for (i, &len) in lens.iter().enumerate() {
+            let end = addr + len as usize;
+
+            self.check_no_overlap(addr, end).map_err(|overlap_addr| {
+                IntelHexError::ParseRecordError(
+                    IntelHexErrorKind::RecordAddressOverlap(overlap_addr),
+                    first + i,
+                )
+            })?;
+            self.insert_chunk_with_merge(addr, data[pos..pos + len as usize].to_vec());
+
+            addr = end;
+            pos += len as usize;
+        }
+
+        Ok(())
+    }
+
+    #[allow(clippy::too_many_lines)]
+    /// Parse the raw contents of the hex file line by line.
+    fn parse_sequential(&mut self, raw_bytes: &[u8]) -> Result<(), IntelHexError> {
         let mut count: usize = 0;
 
         // Iterate over lines of records
@@ -310,8 +582,12 @@ impl IntelHex {
             }
         }
 
-        // For now, limit the address range to u32 limit.
-        // Check at the end to not hinder the parsing performance.
+        self.check_parsed_addr_range(count)
+    }
+
+    /// For now, limit the address range to u32 limit.
+    /// Checked at the end of parsing (of `count` records) to not hinder the parsing performance.
+    fn check_parsed_addr_range(&self, count: usize) -> Result<(), IntelHexError> {
         if let Some(max_addr) = self.get_max_addr()
             && max_addr > u32::MAX as usize
         {
@@ -370,7 +646,7 @@ impl IntelHex {
         self.parse(&raw_bytes)?;
 
         // Compute the payload size from the parsed buffer
-        self.size = self.buffer.values().map(Vec::len).sum();
+        self.size = self.buffer.values().map(<[u8]>::len).sum();
 
         Ok(())
     }
@@ -436,8 +712,89 @@ impl IntelHex {
         Ok(())
     }
 
-    #[allow(clippy::cast_possible_truncation)]
+    /// Creates an `IntelHex` instance backed by a memory-mapped binary file,
+    /// see [`IntelHex::load_bin_mapped`].
+    ///
+    /// # Errors
+    /// Returns an error if the file cannot be opened or mapped.
+    ///
+    /// # Example
+    /// ```
+    /// use intelhexlib::IntelHex;
+    ///
+    /// let ih = IntelHex::from_bin_mapped("tests/fixtures/ih_valid_1.bin", 0x1000).unwrap();
+    ///
+    /// assert!(ih.is_mapped());
+    /// assert_eq!(ih.size, 51596);
+    /// ```
+    pub fn from_bin_mapped<P: AsRef<Path>>(
+        filepath: P,
+        base_address: usize,
+    ) -> Result<Self, IntelHexError> {
+        let mut ih = Self::new();
+        ih.load_bin_mapped(filepath, base_address)?;
+        Ok(ih)
+    }
+
+    /// Fills an `IntelHex` instance with data from the provided binary without reading it
+    /// into memory: the file is memory-mapped and pages are loaded by the OS on access.
+    /// Use it for dumps too large to be read at once (e.g. a whole eMMC or NAND image).
+    ///
+    /// The mapping is read-mostly. Byte edits (`update_*`, `transform_range`, and merging data
+    /// that lies within the mapped range) are kept in memory as an overlay of patched pages and
+    /// the file itself is never modified. Snapshots, clones, undo history and removing ranges
+    /// map the file again and copy only the patched pages. Operations that extend a mapped
+    /// chunk (inserting a range right next to it, merging data that overlaps its end) copy the
+    /// whole chunk into memory first.
+    ///
+    /// > **NOTE**: The file must not be truncated by another process while it is mapped.
+    ///
+    /// # Errors
+    /// Returns an error if the file cannot be opened or mapped.
+    ///
+    /// # Example
+    /// ```
+    /// use intelhexlib::IntelHex;
+    ///
+    /// let mut ih = IntelHex::new();
+    /// ih.load_bin_mapped("tests/fixtures/ih_valid_1.bin", 0x1000).unwrap();
+    ///
+    /// ih.update_byte(0x1000, 0xAB).unwrap();
+    /// assert_eq!(ih.read_byte(0x1000), Some(0xAB));
+    /// ```
+    pub fn load_bin_mapped<P: AsRef<Path>>(
+        &mut self,
+        filepath: P,
+        base_address: usize,
+    ) -> Result<(), IntelHexError> {
+        let file = File::open(&filepath)?;
+        let len = usize::try_from(file.metadata()?.len())
+            .map_err(|_| std::io::Error::from(std::io::ErrorKind::FileTooLarge))?;
+
+        // Empty files cannot be mapped
+        if len == 0 {
+            return self.load_bin(filepath, base_address);
+        }
+
+        let chunk = ChunkData::map_file(Arc::new(file), 0, len)?;
+
+        self.clear();
+        self.size = len;
+        self.filepath = filepath.as_ref().to_path_buf();
+        self.buffer.insert_shared(base_address, Arc::new(chunk));
+
+        Ok(())
+    }
+
+    /// Returns `true` if any data is memory-mapped from a file (see [`IntelHex::load_bin_mapped`]).
+    #[must_use]
+    pub fn is_mapped(&self) -> bool {
+        self.buffer.is_mapped()
+    }
+
     /// Generates an Intel HEX file at the specified path.
+    /// The file is written to a temporary file first and then renamed, so an existing file
+    /// is never left half-written.
     ///
     /// > **NOTE**: Extended Segment Address (ESA) records are not supported,
     /// > Extended Linear Address (ELA) records are used instead.
@@ -455,25 +812,24 @@ impl IntelHex {
     /// assert_eq!(ih.size, 68);
     /// ```
     pub fn write_hex<P: AsRef<Path>>(&mut self, filepath: P) -> Result<(), IntelHexError> {
-        // Ensure the parent directory exists
-        if let Some(parent) = filepath.as_ref().parent() {
-            std::fs::create_dir_all(parent)?;
-        }
+        write_atomic(filepath, |writer| self.write_hex_records(writer))
+    }
 
-        let file = std::fs::OpenOptions::new()
-            .write(true)
-            .create(true)
-            .truncate(true)
-            .open(filepath)?;
+    #[allow(clippy::cast_possible_truncation)]
+    /// Write all records of the Intel HEX file into the writer.
+    /// Records are formatted into a reusable buffer that is handed to the writer in large
+    /// blocks, so no memory is allocated per record.
+    fn write_hex_records<W: Write>(&self, writer: &mut W) -> Result<(), IntelHexError> {
+        // Flush the buffer to the writer once it holds this many bytes
+        const FLUSH_SIZE: usize = 64 * 1024;
 
-        // Wrap in BufWriter for efficient line-by-line writing
-        let mut writer = std::io::BufWriter::new(file);
+        let mut out = Vec::with_capacity(FLUSH_SIZE + 2 * (LARGEST_RECORD_LINE + 1));
 
         // Write start address record (raw bytes + newline)
         if let Some(s) = self.start_addr {
-            writer.write_all(b":")?;
-            writer.write_all(&s)?;
-            writeln!(writer)?;
+            out.push(b':');
+            out.extend_from_slice(&s);
+            out.push(b'\n');
         }
 
         let mut cur_high_addr = 0u16;
@@ -491,12 +847,13 @@ impl IntelHex {
 
                 // If ELA segment changed -> emit ELA record
                 if cur_high_addr != high_addr {
-                    let msb = (high_addr >> 8) as u8;
-                    let lsb = (high_addr & 0xFF) as u8;
-
-                    let record = Record::create(0, RecordType::ExtendedLinearAddress, &[msb, lsb])?;
-
-                    writeln!(writer, "{record}")?;
+                    Record::encode(
+                        &mut out,
+                        0,
+                        RecordType::ExtendedLinearAddress,
+                        &high_addr.to_be_bytes(),
+                    )?;
+                    out.push(b'\n');
 
                     cur_high_addr = high_addr;
                 }
@@ -510,26 +867,34 @@ impl IntelHex {
                     std::cmp::min(data.len() - chunk_offset, remaining_in_segment),
                 );
 
-                let record = Record::create(
+                Record::encode(
+                    &mut out,
                     low_addr,
                     RecordType::Data,
                     &data[chunk_offset..chunk_offset + chunk_size],
                 )?;
-                writeln!(writer, "{record}")?;
+                out.push(b'\n');
 
                 chunk_offset += chunk_size;
+
+                if out.len() >= FLUSH_SIZE {
+                    writer.write_all(&out)?;
+                    out.clear();
+                }
             }
         }
 
-        // Write EOF record
-        let record = Record::create(0, RecordType::EndOfFile, &[])?;
-        write!(writer, "{record}")?; // write last line (no newline)
+        // Write EOF record (last line has no newline)
+        Record::encode(&mut out, 0, RecordType::EndOfFile, &[])?;
+        writer.write_all(&out)?;
 
         Ok(())
     }
 
     /// Generates a binary file at the specified path.
     /// Address gaps are filled with the provided `gap_fill` byte (usually 0x00 or 0xFF).
+    /// The file is written to a temporary file first and then renamed, so an existing file
+    /// is never left half-written.
     ///
     /// # Errors
     /// Returns an error if the file cannot be written.
@@ -550,59 +915,170 @@ impl IntelHex {
         filepath: P,
         gap_fill: u8,
     ) -> Result<(), IntelHexError> {
-        // Ensure the parent directory exists
-        if let Some(parent) = filepath.as_ref().parent() {
-            std::fs::create_dir_all(parent)?;
+        let start = self.get_min_addr().unwrap_or(0);
+        let end = self
+            .buffer
+            .last_key_value()
+            .map_or(0, |(&s, d)| s + d.len());
+
+        self.write_bin_span(filepath, start, end, gap_fill)
+    }
+
+    /// Generates a binary file at the specified path that spans exactly the address window
+    /// `[start_addr, end_addr]` (inclusive). Parts of the window without data are filled with
+    /// the provided `gap_fill` byte, data outside the window is not written (truncated).
+    ///
+    /// # Errors
+    /// Returns an error if `start_addr > end_addr` or the file cannot be written.
+    ///
+    /// # Example
+    /// ```
+    /// use intelhexlib::IntelHex;
+    ///
+    /// let mut ih = IntelHex::from_hex("tests/fixtures/ih_valid_1.hex").unwrap();
+    /// ih.write_bin_range("build/ex4/ih.bin", 0x2, 0x9, 0xFF).unwrap();
+    ///
+    /// let bin = std::fs::read("build/ex4/ih.bin").unwrap();
+    /// assert_eq!(bin, vec![0x00, 0x02, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF]);
+    /// ```
+    pub fn write_bin_range<P: AsRef<Path>>(
+        &mut self,
+        filepath: P,
+        start_addr: usize,
+        end_addr: usize,
+        gap_fill: u8,
+    ) -> Result<(), IntelHexError> {
+        if start_addr > end_addr {
+            return Err(IntelHexError::UpdateError(
+                IntelHexErrorKind::InvalidAddress(start_addr),
+            ));
+        }
+
+        self.write_bin_span(filepath, start_addr, end_addr.saturating_add(1), gap_fill)
+    }
+
+    /// Generates a binary file at the specified path that spans exactly the address window
+    /// `[start_addr, end_addr]` (inclusive). Same as [`write_bin_range`](Self::write_bin_range),
+    /// but returns an error instead of truncating data outside the window.
+    ///
+    /// # Errors
+    /// Returns an error if `start_addr > end_addr` or the file cannot be written.
+    /// Returns an error if any data lies outside the window. The file is not written then.
+    ///
+    /// # Example
+    /// ```
+    /// use intelhexlib::IntelHex;
+    ///
+    /// let mut ih = IntelHex::from_hex("tests/fixtures/ih_valid_1.hex").unwrap();
+    ///
+    /// // Data at 0x1C200..0x1C23F does not fit into the window
+    /// let res = ih.write_bin_range_safe("build/ex4/ih_safe.bin", 0x0, 0xFFFF, 0xFF);
+    ///
+    /// assert!(res.is_err());
+    /// ```
+    pub fn write_bin_range_safe<P: AsRef<Path>>(
+        &mut self,
+        filepath: P,
+        start_addr: usize,
+        end_addr: usize,
+        gap_fill: u8,
+    ) -> Result<(), IntelHexError> {
+        if let Some(min_addr) = self.get_min_addr()
+            && min_addr < start_addr
+        {
+            return Err(IntelHexError::UpdateError(
+                IntelHexErrorKind::DataOutsideRange(min_addr),
+            ));
+        }
+
+        if let Some(max_addr) = self.get_max_addr()
+            && max_addr > end_addr
+        {
+            return Err(IntelHexError::UpdateError(
+                IntelHexErrorKind::DataOutsideRange(max_addr),
+            ));
         }
 
-        let file = std::fs::OpenOptions::new()
-            .write(true)
-            .create(true)
-            .truncate(true)
-            .open(filepath)?;
+        self.write_bin_range(filepath, start_addr, end_addr, gap_fill)
+    }
+
+    /// Write the address window `[start, end)` to a binary file, filling gaps with `gap_fill`.
+    fn write_bin_span<P: AsRef<Path>>(
+        &self,
+        filepath: P,
+        start: usize,
+        end: usize,
+        gap_fill: u8,
+    ) -> Result<(), IntelHexError> {
+        write_atomic(filepath, |writer| {
+            self.write_bin_data(writer, start, end, gap_fill)
+        })
+    }
 
-        // Wrap in BufWriter for efficient line-by-line writing
-        let mut writer = std::io::BufWriter::new(file);
+    /// Write the address window `[start, end)` into the writer, filling gaps with `gap_fill`.
+    fn write_bin_data<W: Write>(
+        &self,
+        writer: &mut W,
+        start: usize,
+        end: usize,
+        gap_fill: u8,
+    ) -> Result<(), IntelHexError> {
+        // Use a small buffer to write gaps. Limit the buffer to 4096 KB as it is the
+        // default / typical page size of most OS - more efficient + avoids large heap allocations.
+        let gap_buf = vec![gap_fill; std::cmp::min(end.saturating_sub(start), 4096)];
 
         // Get the starting point
-        let mut cur_addr = self.get_min_addr().unwrap_or(0);
+        let mut cur_addr = start;
+
+        // Only the chunk right before `start` can overlap the window from the left
+        let first = self
+            .buffer
+            .range(..=start)
+            .next_back()
+            .filter(|&(&s, d)| s + d.len() > start)
+            .map_or(start, |(&s, _)| s);
+
+        // Iterate over contiguous chunks of data within the window
+        for (&chunk_start, chunk_data) in self.buffer.range(first..end) {
+            let data_start = chunk_start.max(start);
+            let data_end = (chunk_start + chunk_data.len()).min(end);
 
-        // Iterate over contiguous chunks of data
-        for (&chunk_start, chunk_data) in &self.buffer {
             // Fill the gap between the last written byte and the start of this chunk
-            if chunk_start > cur_addr {
-                let gap_size = chunk_start - cur_addr;
-
-                // Use a small buffer to write gaps. Limit the buffer to 4096 KB as it is the
-                // default / typical page size of most OS - more efficient + avoids large heap allocations.
-                let gap_buf = vec![gap_fill; std::cmp::min(gap_size, 4096)];
-
-                let mut remaining = gap_size;
-                while remaining > 0 {
-                    let to_write = std::cmp::min(remaining, gap_buf.len());
-                    writer.write_all(&gap_buf[..to_write])?;
-                    remaining -= to_write;
-                }
+            if data_start > cur_addr {
+                Self::write_gap(writer, &gap_buf, data_start - cur_addr)?;
             }
 
-            // Write the entire contiguous chunk at once
-            writer.write_all(chunk_data)?;
+            // Write the contiguous chunk (or its part within the window) at once
+            writer.write_all(&chunk_data[data_start - chunk_start..data_end - chunk_start])?;
 
             // Advance the tracking address
-            cur_addr = chunk_start + chunk_data.len();
+            cur_addr = data_end;
         }
 
-        writer.flush()?;
+        // Fill the rest of the window
+        Self::write_gap(writer, &gap_buf, end.saturating_sub(cur_addr))?;
+
+        Ok(())
+    }
+
+    /// Write `gap_size` bytes of the gap buffer's fill byte, reusing the buffer in a loop.
+    fn write_gap<W: Write>(writer: &mut W, gap_buf: &[u8], gap_size: usize) -> std::io::Result<()> {
+        let mut remaining = gap_size;
+        while remaining > 0 {
+            let to_write = std::cmp::min(remaining, gap_buf.len());
+            writer.write_all(&gap_buf[..to_write])?;
+            remaining -= to_write;
+        }
         Ok(())
     }
 
     /// Get an iterator over (address, contiguous data chunk) pairs in the
-    /// `BTreeMap<usize, Vec<u8>` buffer of the `IntelHex`.
+    /// buffer of the `IntelHex`. Yields `(&usize, &[u8])` in ascending address order
+    /// through [`Chunks`].
     /// For a more convenient way to iterate over the data, see [`IntelHex::bytes()`].
     ///
     /// # Example
     /// ```
-    /// use std::collections::{BTreeMap, btree_map};
     /// use intelhexlib::IntelHex;
     ///
     /// let ih = IntelHex::from_hex("tests/fixtures/ih_valid_1.hex").unwrap();
@@ -613,7 +1089,8 @@ impl IntelHex {
     /// assert_eq!(*first_key, 0);
     /// assert_eq!(*first_chunk, vec![0xFA, 0x00, 0x00, 0x02]);
     /// ```
-    pub fn iter(&self) -> std::collections::btree_map::Iter<'_, usize, Vec<u8>> {
+    #[must_use]
+    pub fn iter(&self) -> Chunks<'_> {
         self.into_iter()
     }
 
@@ -858,10 +1335,16 @@ impl IntelHex {
     /// assert!(res.is_ok());
     /// ```
     pub fn update_byte(&mut self, address: usize, value: u8) -> Result<(), IntelHexError> {
-        if let Some((&chunk_start, chunk_data)) = self.buffer.range_mut(..=address).next_back()
-            && address < chunk_start + chunk_data.len()
-        {
-            chunk_data[address - chunk_start] = value;
+        if self.journal.is_some() {
+            let window = (address, address.saturating_add(1));
+            return self.journaled("Update byte", Recording::Bytes, &[window], |ih| {
+                ih.update_byte(address, value)
+            });
+        }
+
+        if let Some(byte) = self.buffer.slice_mut(address, 1) {
+            byte[0] = value;
+            self.tag_edit(address, address + 1);
             return Ok(());
         }
 
@@ -889,6 +1372,22 @@ impl IntelHex {
     /// assert!(res.is_ok());
     /// ```
     pub fn update_slice(&mut self, update_map: &[(usize, u8)]) -> Result<(), IntelHexError> {
+        if self.journal.is_some() {
+            // Record runs of consecutive addresses, not the whole span between the updates
+            let mut addrs: Vec<usize> = update_map.iter().map(|&(a, _)| a).collect();
+            addrs.sort_unstable();
+            let mut windows: Vec<(usize, usize)> = Vec::new();
+            for addr in addrs {
+                match windows.last_mut() {
+                    Some((_, end)) if addr <= *end => *end = (*end).max(addr.saturating_add(1)),
+                    _ => windows.push((addr, addr.saturating_add(1))),
+                }
+            }
+            return self.journaled("Update bytes", Recording::Bytes, &windows, |ih| {
+                ih.update_slice(update_map)
+            });
+        }
+
         // First pass: Verify all addresses exist before modifying anything
         for &(addr, _) in update_map {
             let exists = self
@@ -931,18 +1430,21 @@ impl IntelHex {
     /// assert!(res.is_ok());
     /// ```
     pub fn update_range(&mut self, start_addr: usize, data: &[u8]) -> Result<(), IntelHexError> {
-        let len = data.len();
-
-        if let Some((&chunk_start, chunk_data)) = self.buffer.range_mut(..=start_addr).next_back() {
-            let chunk_end = chunk_start + chunk_data.len();
-            let end_addr = start_addr + len;
+        if self.journal.is_some() {
+            let end = start_addr.saturating_add(data.len());
+            return self.journaled(
+                "Update range",
+                Recording::Bytes,
+                &[(start_addr, end)],
+                |ih| ih.update_range(start_addr, data),
+            );
+        }
 
-            // Check the entire range fits within a single chunk
-            if start_addr >= chunk_start && end_addr <= chunk_end {
-                let chunk_offset = start_addr - chunk_start;
-                chunk_data[chunk_offset..chunk_offset + len].copy_from_slice(data);
-                return Ok(());
-            }
+        // The entire range must fit within a single chunk
+        if let Some(target) = self.buffer.slice_mut(start_addr, data.len()) {
+            target.copy_from_slice(data);
+            self.tag_edit(start_addr, start_addr + data.len());
+            return Ok(());
         }
 
         Err(IntelHexError::UpdateError(
@@ -950,51 +1452,134 @@ impl IntelHex {
         ))
     }
 
-    /// Insert a new address range filled with 0x00 bytes into the `IntelHex` buffer.
+    /// Apply an in-place [`Transform`] (byte-swap, bit reversal, XOR, etc.) to the data
+    /// in the address range `[start_addr, end_addr]`.
     ///
-    /// The range is inclusive: `[start_addr, end_addr]`.
-    /// If the range overlaps with existing data, an error is returned and no changes are made.
-    /// Adjacent chunks are merged automatically.
+    /// This operation is atomic: if the range spans across a gap in the sparse buffer,
+    /// or its length is not a multiple of the transform's word size, an error is returned
+    /// and no data is modified.
     ///
     /// # Errors
-    /// Returns an error if `start_addr > end_addr`, the range exceeds 32-bit address space,
-    /// or the range overlaps with existing data.
+    /// Returns `InvalidAddress` if any part of the range is not defined.
+    /// Returns `TransformLengthInvalid` if the range does not consist of whole words.
+    /// Returns `TransformKeyEmpty` if the XOR key is empty.
     ///
     /// # Example
     /// ```
-    /// use intelhexlib::IntelHex;
+    /// use intelhexlib::{IntelHex, Transform};
     ///
-    /// let mut ih = IntelHex::new();
-    /// ih.write_range(0x1000, 0x100F).unwrap();
+    /// let mut ih = IntelHex::from_hex("tests/fixtures/ih_valid_1.hex").unwrap();
+    /// ih.transform_range(0x0, 0x3, &Transform::Swap16).unwrap();
     ///
-    /// assert!(ih.read_range(0x1000, 0xF).is_some());
+    /// assert_eq!(ih.read_range(0x0, 4), Some(vec![0x00, 0xFA, 0x02, 0x00]));
     /// ```
-    pub fn write_range(&mut self, start_addr: usize, end_addr: usize) -> Result<(), IntelHexError> {
+    pub fn transform_range(
+        &mut self,
+        start_addr: usize,
+        end_addr: usize,
+        transform: &Transform,
+    ) -> Result<(), IntelHexError> {
+        if self.journal.is_some() {
+            let end = end_addr.saturating_add(1);
+            return self.journaled(
+                "Transform range",
+                Recording::Bytes,
+                &[(start_addr, end)],
+                |ih| ih.transform_range(start_addr, end_addr, transform),
+            );
+        }
+
         if start_addr > end_addr {
             return Err(IntelHexError::UpdateError(
                 IntelHexErrorKind::InvalidAddress(start_addr),
             ));
         }
 
-        if end_addr > u32::MAX as usize {
+        let len = end_addr - start_addr + 1;
+        let word_size = transform.word_size();
+
+        if !len.is_multiple_of(word_size) {
             return Err(IntelHexError::UpdateError(
-                IntelHexErrorKind::AddressRangeOverflow,
+                IntelHexErrorKind::TransformLengthInvalid(word_size, len),
             ));
         }
 
-        let len = end_addr - start_addr + 1;
+        if let Transform::Xor(key) = transform
+            && key.is_empty()
+        {
+            return Err(IntelHexError::UpdateError(
+                IntelHexErrorKind::TransformKeyEmpty,
+            ));
+        }
 
-        // Check for overlaps
-        self.check_no_overlap(start_addr, end_addr + 1)
-            .map_err(|overlap_addr| {
-                IntelHexError::UpdateError(IntelHexErrorKind::RecordAddressOverlap(overlap_addr))
+        // The entire range must fit within a single chunk
+        if let Some(target) = self.buffer.slice_mut(start_addr, len) {
+            transform.apply(target);
+            self.tag_edit(start_addr, end_addr + 1);
+            return Ok(());
+        }
+
+        Err(IntelHexError::UpdateError(
+            IntelHexErrorKind::InvalidAddress(start_addr),
+        ))
+    }
+
+    /// Insert a new address range filled with 0x00 bytes into the `IntelHex` buffer.
+    ///
+    /// The range is inclusive: `[start_addr, end_addr]`.
+    /// If the range overlaps with existing data, an error is returned and no changes are made.
+    /// Adjacent chunks are merged automatically.
+    ///
+    /// # Errors
+    /// Returns an error if `start_addr > end_addr`, the range exceeds 32-bit address space,
+    /// or the range overlaps with existing data.
+    ///
+    /// # Example
+    /// ```
+    /// use intelhexlib::IntelHex;
+    ///
+    /// let mut ih = IntelHex::new();
+    /// ih.write_range(0x1000, 0x100F).unwrap();
+    ///
+    /// assert!(ih.read_range(0x1000, 0xF).is_some());
+    /// ```
+    pub fn write_range(&mut self, start_addr: usize, end_addr: usize) -> Result<(), IntelHexError> {
+        if self.journal.is_some() {
+            let end = end_addr.saturating_add(1);
+            return self.journaled(
+                "Write range",
+                Recording::Chunks,
+                &[(start_addr, end)],
+                |ih| ih.write_range(start_addr, end_addr),
+            );
+        }
+
+        if start_addr > end_addr {
+            return Err(IntelHexError::UpdateError(
+                IntelHexErrorKind::InvalidAddress(start_addr),
+            ));
+        }
+
+        if end_addr > u32::MAX as usize {
+            return Err(IntelHexError::UpdateError(
+                IntelHexErrorKind::AddressRangeOverflow,
+            ));
+        }
+
+        let len = end_addr - start_addr + 1;
+
+        // Check for overlaps
+        self.check_no_overlap(start_addr, end_addr + 1)
+            .map_err(|overlap_addr| {
+                IntelHexError::UpdateError(IntelHexErrorKind::RecordAddressOverlap(overlap_addr))
             })?;
 
         // Insert data and merge with adjacent chunks
         self.insert_chunk_with_merge(start_addr, vec![0x00u8; len]);
+        self.tag_edit(start_addr, end_addr + 1);
 
         // Update total size
-        self.size = self.buffer.values().map(Vec::len).sum();
+        self.size = self.buffer.values().map(<[u8]>::len).sum();
 
         Ok(())
     }
@@ -1025,6 +1610,16 @@ impl IntelHex {
         start_addr: usize,
         end_addr: usize,
     ) -> Result<(), IntelHexError> {
+        if self.journal.is_some() {
+            let end = end_addr.saturating_add(1);
+            return self.journaled(
+                "Remove range",
+                Recording::Chunks,
+                &[(start_addr, end)],
+                |ih| ih.remove_range(start_addr, end_addr),
+            );
+        }
+
         if start_addr > end_addr {
             return Err(IntelHexError::UpdateError(
                 IntelHexErrorKind::InvalidAddress(start_addr),
@@ -1034,45 +1629,15 @@ impl IntelHex {
         let remove_start = start_addr;
         let remove_end = end_addr + 1; // exclusive
 
-        // Collect keys of chunks that overlap with the removal range
-        let affected_keys: Vec<usize> = self
-            .buffer
-            .range(..=end_addr)
-            .filter(|&(&chunk_start, chunk_data)| {
-                let chunk_end = chunk_start + chunk_data.len();
-                chunk_end > remove_start && chunk_start < remove_end
-            })
-            .map(|(&k, _)| k)
-            .collect();
-
-        let mut new_chunks: Vec<(usize, Vec<u8>)> = Vec::new();
-
-        for key in affected_keys {
-            let Some(chunk_data) = self.buffer.remove(&key) else {
-                continue;
-            };
-            let chunk_end = key + chunk_data.len();
-
-            // Left fragment: portion before the removal range
-            if key < remove_start {
-                let left_end = std::cmp::min(remove_start, chunk_end);
-                new_chunks.push((key, chunk_data[..left_end - key].to_vec()));
-            }
-
-            // Right fragment: portion after the removal range
-            if chunk_end > remove_end {
-                let right_start = std::cmp::max(remove_end, key);
-                new_chunks.push((remove_end, chunk_data[right_start - key..].to_vec()));
-            }
-        }
+        self.clear_window(remove_start, remove_end);
 
-        // Insert trimmed chunks
-        for (addr, data) in new_chunks {
-            self.buffer.insert(addr, data);
+        // Drop source tags of the removed data
+        if let Some(provenance) = self.provenance.as_mut() {
+            provenance.clear_range(remove_start, remove_end);
         }
 
         // Update total size
-        self.size = self.buffer.values().map(Vec::len).sum();
+        self.size = self.buffer.values().map(<[u8]>::len).sum();
 
         Ok(())
     }
@@ -1127,6 +1692,20 @@ impl IntelHex {
     /// assert_eq!(ih.read_byte(0x1234), Some(0xFA));
     /// ```
     pub fn relocate(&mut self, new_start_address: usize) -> Result<(), IntelHexError> {
+        if let Some(mut journal) = self.journal.take() {
+            let old_min_addr = self.get_min_addr();
+            let res = self.relocate(new_start_address);
+            if res.is_ok()
+                && let Some(old_min_addr) = old_min_addr
+                && old_min_addr != new_start_address
+            {
+                let offset = new_start_address as i64 - old_min_addr as i64;
+                journal.record("Relocate", vec![Change::Shift(offset)]);
+            }
+            self.journal = Some(journal);
+            return res;
+        }
+
         let (min_addr, max_addr) =
             self.get_min_addr()
                 .zip(self.get_max_addr())
@@ -1150,14 +1729,26 @@ impl IntelHex {
             ));
         }
 
-        self.buffer = std::mem::take(&mut self.buffer)
-            .into_iter()
-            .map(|(addr, data)| ((addr as i64 + offset) as usize, data))
-            .collect();
+        self.shift_by(offset);
 
         Ok(())
     }
 
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_possible_wrap,
+        clippy::cast_sign_loss
+    )]
+    /// Move all data (and source tags) by `offset`. The caller ensures the result stays
+    /// within the address space.
+    fn shift_by(&mut self, offset: i64) {
+        self.buffer.map_keys(|addr| (addr as i64 + offset) as usize);
+
+        if let Some(provenance) = self.provenance.as_mut() {
+            provenance.shift(offset);
+        }
+    }
+
     /// Merge another `IntelHex` instance into this one.
     ///
     /// # Errors
@@ -1177,7 +1768,17 @@ impl IntelHex {
     /// assert!(res.is_ok());
     /// ```
     pub fn merge_safe(&mut self, other: &Self) -> Result<(), IntelHexError> {
-        for (&chunk_start, chunk_data) in &other.buffer {
+        if self.journal.is_some() {
+            let windows = other.chunk_windows();
+            return self.journaled("Merge", Recording::Chunks, &windows, |ih| {
+                ih.merge_safe(other)
+            });
+        }
+
+        self.track_sources_of(other);
+
+        let mut source_ids = Vec::new();
+        for (chunk_start, chunk_data) in other.buffer.iter_shared() {
             let end_addr = chunk_start + chunk_data.len();
 
             // Check for overlaps
@@ -1188,9 +1789,10 @@ impl IntelHex {
                     ))
                 })?;
 
-            // Insert the chunk if no overlaps
-            self.buffer.insert(chunk_start, chunk_data.clone());
+            // Insert the chunk if no overlaps (shared with `other` until either is modified)
+            self.buffer.insert_shared(chunk_start, chunk_data.clone());
             self.size += chunk_data.len();
+            self.tag_range_from(other, chunk_start, end_addr, &mut source_ids);
         }
 
         // If both files have a start address, return an error
@@ -1211,25 +1813,28 @@ impl IntelHex {
             return Ok(());
         }
 
-        let mut new_buf = BTreeMap::<usize, Vec<u8>>::new();
-        let mut iter = std::mem::take(&mut self.buffer).into_iter();
+        let mut new_buf = ChunkMap::new();
+        let mut iter = std::mem::take(&mut self.buffer).into_shared();
 
         if let Some((mut cur_start, mut cur_data)) = iter.next() {
             let mut cur_end = cur_start + cur_data.len();
 
-            for (start, mut data) in iter {
+            // Only chunks that get joined are copied, the rest stay shared
+            for (start, data) in iter {
                 if start == cur_end {
                     cur_end += data.len();
-                    cur_data.append(&mut data);
+                    Arc::make_mut(&mut cur_data)
+                        .as_mut_vec()
+                        .extend_from_slice(data.as_slice());
                 } else {
-                    new_buf.insert(cur_start, cur_data);
+                    new_buf.insert_shared(cur_start, cur_data);
                     cur_start = start;
                     cur_data = data;
                     cur_end = cur_start + cur_data.len();
                 }
             }
 
-            new_buf.insert(cur_start, cur_data);
+            new_buf.insert_shared(cur_start, cur_data);
         }
 
         self.buffer = new_buf;
@@ -1256,9 +1861,31 @@ impl IntelHex {
     /// assert_eq!(ih1.read_byte(0x100), Some(0x21));
     /// ```
     pub fn merge(&mut self, other: &Self) {
+        if self.journal.is_some() {
+            let windows = other.chunk_windows();
+            let _ = self.journaled("Merge", Recording::Chunks, &windows, |ih| {
+                ih.merge(other);
+                Ok(())
+            });
+            return;
+        }
+
+        self.track_sources_of(other);
+
+        let mut source_ids = Vec::new();
         for (&chunk_start, chunk_data) in &other.buffer {
+            let chunk_end = chunk_start + chunk_data.len();
+
+            // Data that lies within a single existing chunk is overwritten in place, so the
+            // layout is kept and mapped chunks are not copied into memory
+            if let Some(target) = self.buffer.slice_mut(chunk_start, chunk_data.len()) {
+                target.copy_from_slice(chunk_data);
+                self.tag_range_from(other, chunk_start, chunk_end, &mut source_ids);
+                continue;
+            }
+
             let mut new_start = chunk_start;
-            let mut new_data = chunk_data.clone();
+            let mut new_data = chunk_data.to_vec();
             let mut new_end = chunk_start + chunk_data.len();
 
             // Merge overlapping / adjacent keys in a dynamic loop to ensure
@@ -1319,10 +1946,11 @@ impl IntelHex {
 
             // Insert the contiguous chunk
             self.buffer.insert(new_start, new_data);
+            self.tag_range_from(other, chunk_start, chunk_end, &mut source_ids);
         }
 
         // Update total size (recompute to be safe with overwrites)
-        self.size = self.buffer.values().map(Vec::len).sum();
+        self.size = self.buffer.values().map(<[u8]>::len).sum();
 
         // Overwrite start address if the other has it
         if let Some(other_start_addr) = other.start_addr {
@@ -1330,21 +1958,1216 @@ impl IntelHex {
         }
     }
 
-    /// Window slide search for a byte array in the `IntelHex` data.
-    /// Returns start addresses of matches.
+    /// Take a snapshot of the data, start address record and source tags. The snapshot
+    /// shares all data chunks with this instance: chunks are only copied when either side
+    /// modifies them, so a snapshot costs (almost) no memory until the image is edited.
+    /// Note that a modified chunk held in memory is copied as a whole, i.e. the cost of the
+    /// first edit of a shared chunk is proportional to the size of that contiguous chunk.
+    /// Memory-mapped chunks only copy their patched pages.
+    ///
+    /// Cloning an `IntelHex` instance shares the chunks the same way.
+    ///
+    /// # Example
+    /// ```
+    /// use intelhexlib::IntelHex;
+    ///
+    /// let mut ih = IntelHex::from_hex("tests/fixtures/ih_valid_1.hex").unwrap();
+    /// let original = ih.snapshot();
+    ///
+    /// ih.update_byte(0x0, 0xFF).unwrap();
+    /// ih.relocate(0x1000).unwrap();
+    /// assert_eq!(ih.read_byte(0x0), None);
+    ///
+    /// ih.restore(&original);
+    /// assert_eq!(ih.read_byte(0x0), Some(0xFA));
+    /// ```
+    #[must_use]
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            buffer: self.buffer.clone(),
+            size: self.size,
+            start_addr: self.start_addr,
+            provenance: self.provenance.clone(),
+        }
+    }
+
+    /// Restore the data, start address record and source tags saved by
+    /// [`snapshot`](Self::snapshot). The file path and record settings are kept.
+    /// Restoring is recorded by the journal (if enabled) and can be undone.
+    pub fn restore(&mut self, snapshot: &Snapshot) {
+        if let Some(mut journal) = self.journal.take() {
+            let before = self.snapshot();
+            self.apply_snapshot(snapshot);
+            if before != *snapshot {
+                journal.record(
+                    "Restore",
+                    vec![Change::Restore {
+                        before: Box::new(before),
+                        after: Box::new(snapshot.clone()),
+                    }],
+                );
+            }
+            self.journal = Some(journal);
+            return;
+        }
+
+        self.apply_snapshot(snapshot);
+    }
+
+    fn apply_snapshot(&mut self, snapshot: &Snapshot) {
+        self.buffer.clone_from(&snapshot.buffer);
+        self.size = snapshot.size;
+        self.start_addr = snapshot.start_addr;
+        self.provenance.clone_from(&snapshot.provenance);
+    }
+
+    /// Enable the undo/redo journal. From now on every mutation ([`update_byte`](Self::update_byte),
+    /// [`update_range`](Self::update_range), [`write_range`](Self::write_range),
+    /// [`remove_range`](Self::remove_range), [`relocate`](Self::relocate),
+    /// [`merge`](Self::merge), etc.) is recorded and can be reverted with [`undo`](Self::undo).
+    /// Does nothing if the journal is already enabled.
+    ///
+    /// # Example
+    /// ```
+    /// use intelhexlib::IntelHex;
+    ///
+    /// let mut ih = IntelHex::from_hex("tests/fixtures/ih_valid_1.hex").unwrap();
+    /// ih.enable_journal();
+    ///
+    /// ih.begin_transaction("Patch");
+    /// ih.update_byte(0x0, 0xFF).unwrap();
+    /// ih.relocate(0x1000).unwrap();
+    /// ih.commit_transaction();
+    /// assert_eq!(ih.read_byte(0x1000), Some(0xFF));
+    ///
+    /// assert!(ih.undo());
+    /// assert_eq!(ih.read_byte(0x0), Some(0xFA));
+    /// assert_eq!(ih.journal().unwrap().redo_label(), Some("Patch"));
+    ///
+    /// assert!(ih.redo());
+    /// assert_eq!(ih.read_byte(0x1000), Some(0xFF));
+    /// ```
+    pub fn enable_journal(&mut self) {
+        if self.journal.is_none() {
+            self.journal = Some(Journal::default());
+        }
+    }
+
+    /// Disable the undo/redo journal and drop its history.
+    pub fn disable_journal(&mut self) {
+        self.journal = None;
+    }
+
+    /// Get the undo/redo journal. Returns `None` if journaling is disabled
+    /// (see [`enable_journal`](Self::enable_journal)).
+    #[must_use]
+    pub const fn journal(&self) -> Option<&Journal> {
+        self.journal.as_ref()
+    }
+
+    /// Get the mutable undo/redo journal, e.g. to change its limit or clear the history.
+    pub const fn journal_mut(&mut self) -> Option<&mut Journal> {
+        self.journal.as_mut()
+    }
+
+    /// Start grouping all following mutations into a single transaction named `label`,
+    /// which is undone and redone as one step. Transactions can be nested, only the
+    /// outermost one is recorded. Does nothing if journaling is disabled.
+    pub fn begin_transaction(&mut self, label: &str) {
+        if let Some(journal) = self.journal.as_mut() {
+            journal.begin(label);
+        }
+    }
+
+    /// Finish the transaction started by [`begin_transaction`](Self::begin_transaction).
+    pub fn commit_transaction(&mut self) {
+        if let Some(journal) = self.journal.as_mut() {
+            journal.commit();
+        }
+    }
+
+    /// Revert the most recent transaction. Returns `false` if there is nothing to undo
+    /// or journaling is disabled.
+    pub fn undo(&mut self) -> bool {
+        let Some(mut journal) = self.journal.take() else {
+            return false;
+        };
+
+        let transaction = journal.pop_undo();
+        if let Some(transaction) = transaction.as_ref() {
+            for change in transaction.changes.iter().rev() {
+                self.apply_change(change, false);
+            }
+        }
+
+        let undone = transaction.map(|t| journal.push_redo(t)).is_some();
+        self.journal = Some(journal);
+        undone
+    }
+
+    /// Re-apply the most recently undone transaction. Returns `false` if there is nothing
+    /// to redo or journaling is disabled.
+    pub fn redo(&mut self) -> bool {
+        let Some(mut journal) = self.journal.take() else {
+            return false;
+        };
+
+        let transaction = journal.pop_redo();
+        if let Some(transaction) = transaction.as_ref() {
+            for change in &transaction.changes {
+                self.apply_change(change, true);
+            }
+        }
+
+        let redone = transaction.map(|t| journal.push_undo(t)).is_some();
+        self.journal = Some(journal);
+        redone
+    }
+
+    /// Run the mutation `op` with the journal detached and record what it changed within
+    /// the address windows `[start, end)`, along with the start address and source tags.
+    /// `op` must not touch data or source tags outside the windows (apart from joining
+    /// chunks that touch them), and must keep the layout of the chunks if `record` is
+    /// [`Recording::Bytes`]. Changes are recorded even if `op` fails midway.
+    fn journaled<T>(
+        &mut self,
+        label: &str,
+        record: Recording,
+        windows: &[(usize, usize)],
+        op: impl FnOnce(&mut Self) -> Result<T, IntelHexError>,
+    ) -> Result<T, IntelHexError> {
+        let Some(mut journal) = self.journal.take() else {
+            return op(self);
+        };
+
+        let bytes = (record == Recording::Bytes).then(|| self.bytes_in(windows));
+        let chunks = (record == Recording::Chunks).then(|| self.chunks_around(windows));
+        let start_addr = self.start_addr;
+        let tags = self
+            .provenance
+            .as_ref()
+            .map(|provenance| TagsDiff::begin(provenance, windows));
+
+        let res = op(self);
+
+        let mut changes = Vec::new();
+        if let Some(before) = bytes {
+            let after = self.bytes_in(windows);
+            if before != after {
+                changes.push(Change::Bytes { before, after });
+            }
+        }
+        if let Some(before) = chunks {
+            // Unchanged chunks are the very same allocations, their data is not compared
+            let after = self.chunks_around(windows);
+            let unchanged = before.len() == after.len()
+                && before
+                    .iter()
+                    .zip(&after)
+                    .all(|((a1, d1), (a2, d2))| a1 == a2 && Arc::ptr_eq(d1, d2));
+            if !unchanged {
+                changes.push(Change::Chunks { before, after });
+            }
+        }
+        if start_addr != self.start_addr {
+            changes.push(Change::StartAddr {
+                before: start_addr,
+                after: self.start_addr,
+            });
+        }
+        match (tags, &self.provenance) {
+            (Some(tags), Some(provenance)) => {
+                changes.extend(tags.finish(provenance).map(Change::Tags));
+            }
+            // Tracking was enabled by a merge, which tags the existing data as well
+            (None, Some(provenance)) => changes.push(Change::Provenance {
+                before: None,
+                after: Some(provenance.clone()),
+            }),
+            // Mutations never disable tracking
+            (_, None) => {}
+        }
+
+        journal.record(label, changes);
+        self.journal = Some(journal);
+        res
+    }
+
+    /// Apply the change (`forward == true`) or revert it (`forward == false`).
+    fn apply_change(&mut self, change: &Change, forward: bool) {
+        match change {
+            Change::Bytes { before, after } => {
+                for (addr, data) in if forward { after } else { before } {
+                    self.overwrite_in_place(*addr, data);
+                }
+            }
+            Change::Chunks { before, after } => {
+                let (from, to) = if forward {
+                    (before, after)
+                } else {
+                    (after, before)
+                };
+
+                for (addr, _) in from {
+                    self.buffer.remove_shared(addr);
+                }
+                for (addr, data) in to {
+                    self.buffer.insert_shared(*addr, Arc::clone(data));
+                }
+                self.size = self.buffer.values().map(<[u8]>::len).sum();
+            }
+            Change::Shift(offset) => self.shift_by(if forward { *offset } else { -offset }),
+            Change::StartAddr { before, after } => {
+                self.start_addr = if forward { *after } else { *before };
+            }
+            Change::Restore { before, after } => {
+                self.apply_snapshot(if forward { after } else { before });
+            }
+            Change::Provenance { before, after } => {
+                self.provenance
+                    .clone_from(if forward { after } else { before });
+            }
+            Change::Tags(diff) => {
+                if let Some(provenance) = self.provenance.as_mut() {
+                    diff.apply(provenance, forward);
+                }
+            }
+        }
+    }
+
+    /// Split the data into `lanes` byte lanes, e.g. even/odd bytes of a 16-bit bus stored in
+    /// two parallel ROMs. Byte at address `a` goes to lane `a % lanes` at address `a / lanes`,
+    /// so the addresses of every lane image are compacted.
+    ///
+    /// # Errors
+    /// Returns an error if `lanes` is 0.
+    ///
+    /// # Example
+    /// ```
+    /// use intelhexlib::IntelHex;
+    ///
+    /// let ih = IntelHex::from_hex("tests/fixtures/ih_valid_1.hex").unwrap();
+    /// let lanes = ih.split_lanes(2).unwrap();
+    ///
+    /// assert_eq!(ih.read_range(0x0, 4), Some(vec![0xFA, 0x00, 0x00, 0x02]));
+    /// assert_eq!(lanes[0].read_range(0x0, 2), Some(vec![0xFA, 0x00]));
+    /// assert_eq!(lanes[1].read_range(0x0, 2), Some(vec![0x00, 0x02]));
+    /// ```
+    pub fn split_lanes(&self, lanes: usize) -> Result<Vec<Self>, IntelHexError> {
+        if lanes == 0 {
+            return Err(IntelHexError::UpdateError(
+                IntelHexErrorKind::InvalidLaneCount(lanes),
+            ));
+        }
+
+        let mut lane_images: Vec<Self> = (0..lanes)
+            .map(|_| {
+                let mut ih = Self::new();
+                ih.max_payload_size = self.max_payload_size;
+                ih
+            })
+            .collect();
+
+        // Bytes are visited in ascending order, so every lane is filled in ascending order too
+        for (addr, byte) in self.bytes() {
+            lane_images[addr % lanes].push_byte(addr / lanes, byte);
+        }
+
+        Ok(lane_images)
+    }
+
+    /// Interleave byte lanes into a single image. This is the reverse of
+    /// [`split_lanes`](Self::split_lanes): byte at address `a` of lane `i` goes to
+    /// address `a * lanes.len() + i`.
+    ///
+    /// # Errors
+    /// Returns an error if no lanes are given.
+    /// Returns an error if the interleaved addresses exceed the 32-bit address space.
+    ///
+    /// # Example
+    /// ```
+    /// use intelhexlib::IntelHex;
+    ///
+    /// let ih = IntelHex::from_hex("tests/fixtures/ih_valid_1.hex").unwrap();
+    /// let lanes = ih.split_lanes(4).unwrap();
+    ///
+    /// let joined = IntelHex::join_lanes(&lanes).unwrap();
+    ///
+    /// assert_eq!(joined.size, ih.size);
+    /// assert!(joined.bytes().eq(ih.bytes()));
+    /// ```
+    pub fn join_lanes(lanes: &[Self]) -> Result<Self, IntelHexError> {
+        if lanes.is_empty() {
+            return Err(IntelHexError::UpdateError(
+                IntelHexErrorKind::InvalidLaneCount(0),
+            ));
+        }
+
+        let n = lanes.len();
+        let mut bytes: Vec<(usize, u8)> = Vec::with_capacity(lanes.iter().map(|l| l.size).sum());
+
+        for (lane, ih) in lanes.iter().enumerate() {
+            for (addr, byte) in ih.bytes() {
+                let new_addr = addr
+                    .checked_mul(n)
+                    .and_then(|a| a.checked_add(lane))
+                    .filter(|&a| u32::try_from(a).is_ok())
+                    .ok_or(IntelHexError::UpdateError(
+                        IntelHexErrorKind::AddressRangeOverflow,
+                    ))?;
+                bytes.push((new_addr, byte));
+            }
+        }
+
+        // Addresses of different lanes never collide, so plain sorting is enough
+        bytes.sort_unstable_by_key(|&(addr, _)| addr);
+
+        let mut ih = Self::new();
+        ih.max_payload_size = lanes[0].max_payload_size;
+        for (addr, byte) in bytes {
+            ih.push_byte(addr, byte);
+        }
+
+        Ok(ih)
+    }
+
+    /// Append a byte at `addr`, which must be greater than any address already held.
+    /// Extends the last chunk if the byte is contiguous with it.
+    fn push_byte(&mut self, addr: usize, byte: u8) {
+        if let Some((last_start, last_data)) = self.buffer.last_mut()
+            && last_start + last_data.len() == addr
+        {
+            last_data.push(byte);
+        } else {
+            self.buffer.insert(addr, vec![byte]);
+        }
+        self.size += 1;
+    }
+
+    /// Split the data into named sub-images, one per contiguous segment of data.
+    /// Sub-images are named `segment0`, `segment1`, ... in ascending address order.
+    ///
+    /// # Example
+    /// ```
+    /// use intelhexlib::IntelHex;
+    ///
+    /// let ih = IntelHex::from_hex("tests/fixtures/ih_valid_1.hex").unwrap();
+    /// let parts = ih.split_by_segment();
+    ///
+    /// assert_eq!(parts.len(), 2);
+    /// assert_eq!(parts[1].0, "segment1");
+    /// assert_eq!(parts[1].1.get_min_addr(), Some(0x1C200));
+    /// ```
+    #[must_use]
+    pub fn split_by_segment(&self) -> Vec<(String, Self)> {
+        self.buffer
+            .iter()
+            .enumerate()
+            .map(|(i, (&start, data))| {
+                (format!("segment{i}"), self.slice(start, start + data.len()))
+            })
+            .collect()
+    }
+
+    /// Split the data into named sub-images of at most `size` bytes of address space each.
+    /// Parts are aligned to the lowest address, so part `k` covers
+    /// `[min_addr + k * size, min_addr + (k + 1) * size)`. Parts without data are skipped.
+    /// Sub-images are named `part0`, `part1`, ... in ascending address order.
+    ///
+    /// # Errors
+    /// Returns an error if `size` is 0.
     ///
     /// # Example
     /// ```
     /// use intelhexlib::IntelHex;
     ///
-    /// let mut ih = IntelHex::from_hex("tests/fixtures/ih_valid_1.hex").unwrap();
-    /// let matches: Vec<usize> = ih.search_bytes(&[0x00, 0x00, 0x02]);
+    /// let ih = IntelHex::from_hex("tests/fixtures/ih_valid_1.hex").unwrap();
+    /// let parts = ih.split_every(0x20).unwrap();
+    ///
+    /// // One part for the data at 0x0 and two for the data at 0x1C200
+    /// assert_eq!(parts.len(), 3);
+    /// assert_eq!(parts[2].1.get_min_addr(), Some(0x1C220));
+    /// ```
+    pub fn split_every(&self, size: usize) -> Result<Vec<(String, Self)>, IntelHexError> {
+        if size == 0 {
+            return Err(IntelHexError::UpdateError(
+                IntelHexErrorKind::InvalidSplitSize(size),
+            ));
+        }
+
+        let Some(min_addr) = self.get_min_addr() else {
+            return Ok(Vec::new());
+        };
+
+        let mut parts = Vec::new();
+        let mut cursor = min_addr;
+
+        while let Some(addr) = self.next_data_addr(cursor) {
+            let part_start = min_addr + (addr - min_addr) / size * size;
+            let part_end = part_start.saturating_add(size);
+
+            parts.push((
+                format!("part{}", parts.len()),
+                self.slice(part_start, part_end),
+            ));
+
+            if part_end == usize::MAX {
+                break;
+            }
+            cursor = part_end;
+        }
+
+        Ok(parts)
+    }
+
+    /// Split the data into named sub-images, one per region of the memory map.
+    /// Sub-images are named after the regions. Regions without data are skipped
+    /// and data outside of all regions is not included in any sub-image.
+    ///
+    /// # Example
+    /// ```
+    /// use intelhexlib::{IntelHex, MemoryMap};
+    ///
+    /// let mut map = MemoryMap::new();
+    /// map.add_region("boot", 0x0, 0xFFFF).unwrap();
+    /// map.add_region("app", 0x1_0000, 0x1_FFFF).unwrap();
+    /// map.add_region("config", 0x2_0000, 0x2_FFFF).unwrap();
+    ///
+    /// let ih = IntelHex::from_hex("tests/fixtures/ih_valid_1.hex").unwrap();
+    /// let parts = ih.split_by_region(&map);
+    ///
+    /// assert_eq!(parts.len(), 2);
+    /// assert_eq!(parts[0].0, "boot");
+    /// assert_eq!(parts[1].0, "app");
+    /// assert_eq!(parts[1].1.size, 64);
+    /// ```
+    #[must_use]
+    pub fn split_by_region(&self, map: &MemoryMap) -> Vec<(String, Self)> {
+        map.regions()
+            .iter()
+            .map(|region| {
+                (
+                    region.name.clone(),
+                    self.slice(region.start, region.end.saturating_add(1)),
+                )
+            })
+            .filter(|(_, ih)| ih.size != 0)
+            .collect()
+    }
+
+    /// Copy of the data within `[start, end)` as a new instance. Chunks that lie entirely
+    /// within the range are shared, not copied. Source tags are copied as well, the start
+    /// address record is not.
+    fn slice(&self, start: usize, end: usize) -> Self {
+        let mut ih = Self::new();
+        ih.max_payload_size = self.max_payload_size;
+
+        for (addr, data) in self.pieces_in(start, end) {
+            ih.size += data.len();
+            ih.buffer.insert_shared(addr, data);
+        }
+
+        if let Some(provenance) = &self.provenance {
+            let mut sliced = Provenance::default();
+            sliced.copy_from(provenance, start, end, &mut Vec::new());
+            ih.provenance = Some(sliced);
+        }
+
+        ih
+    }
+
+    /// Data pieces within the window `[start, end)`, clipped to the window. Chunks that lie
+    /// entirely within the window are shared, only the clipped parts of others are copied.
+    fn pieces_in(&self, start: usize, end: usize) -> SharedChunks {
+        if start >= end {
+            return Vec::new();
+        }
+
+        // Only the chunk right before `start` can overlap it from the left
+        let first = self
+            .buffer
+            .range(..=start)
+            .next_back()
+            .filter(|&(&s, d)| s + d.len() > start)
+            .map_or(start, |(&s, _)| s);
+
+        self.buffer
+            .range_shared(first..end)
+            .filter_map(|(chunk_start, chunk_data)| {
+                let chunk_end = chunk_start + chunk_data.len();
+                if start <= chunk_start && chunk_end <= end {
+                    return Some((chunk_start, Arc::clone(chunk_data)));
+                }
+
+                let s = chunk_start.max(start);
+                let e = chunk_end.min(end);
+                (s < e).then(|| {
+                    let piece = chunk_data.slice(s - chunk_start..e - chunk_start);
+                    (s, Arc::new(piece))
+                })
+            })
+            .collect()
+    }
+
+    /// Copies of the bytes within the windows `[start, end)`, clipped to the windows.
+    fn bytes_in(&self, windows: &[(usize, usize)]) -> Bytes {
+        let mut bytes = Vec::new();
+        for &(start, end) in windows {
+            // Only the chunk right before `start` can overlap it from the left
+            let first = self
+                .buffer
+                .range(..=start)
+                .next_back()
+                .filter(|&(&s, d)| s + d.len() > start)
+                .map_or(start, |(&s, _)| s);
+
+            for (&chunk_start, chunk_data) in self.buffer.range(first..end) {
+                let s = chunk_start.max(start);
+                let e = (chunk_start + chunk_data.len()).min(end);
+                if s < e {
+                    bytes.push((s, chunk_data[s - chunk_start..e - chunk_start].to_vec()));
+                }
+            }
+        }
+        bytes
+    }
+
+    /// Whole chunks that overlap or touch any of the windows `[start, end)`, shared with
+    /// the buffer. Chunks touching a window are included because they may get joined with it.
+    fn chunks_around(&self, windows: &[(usize, usize)]) -> SharedChunks {
+        let mut chunks = BTreeMap::new();
+        for &(start, end) in windows {
+            let (start, end) = (start.saturating_sub(1), end.saturating_add(1));
+            let first = self
+                .buffer
+                .range(..=start)
+                .next_back()
+                .filter(|&(&s, d)| s + d.len() > start)
+                .map_or(start, |(&s, _)| s);
+
+            for (addr, data) in self.buffer.range_shared(first..end) {
+                chunks.insert(addr, Arc::clone(data));
+            }
+        }
+        chunks.into_iter().collect()
+    }
+
+    /// Overwrite existing data at `addr` with `data`, which must lie within a single chunk.
+    fn overwrite_in_place(&mut self, addr: usize, data: &[u8]) {
+        if let Some(target) = self.buffer.slice_mut(addr, data.len()) {
+            target.copy_from_slice(data);
+        }
+    }
+
+    /// Remove all data within the window `[start, end)`. Chunks overlapping the window
+    /// are trimmed or split. Neither the size nor source tags are updated.
+    fn clear_window(&mut self, start: usize, end: usize) {
+        if start >= end {
+            return;
+        }
+
+        // Collect keys of chunks that overlap with the window
+        let affected_keys: Vec<usize> = self
+            .buffer
+            .range(..end)
+            .rev()
+            .take_while(|&(&chunk_start, chunk_data)| chunk_start + chunk_data.len() > start)
+            .map(|(&k, _)| k)
+            .collect();
+
+        // Fragments of mapped chunks are mapped again instead of being copied
+        for key in affected_keys {
+            let Some(chunk_data) = self.buffer.remove_shared(&key) else {
+                continue;
+            };
+            let chunk_end = key + chunk_data.len();
+
+            // Left fragment: portion before the window
+            if key < start {
+                let left = chunk_data.slice(0..start - key);
+                self.buffer.insert_shared(key, Arc::new(left));
+            }
+
+            // Right fragment: portion after the window
+            if chunk_end > end {
+                let right_start = std::cmp::max(end, key);
+                let right = chunk_data.slice(right_start - key..chunk_data.len());
+                self.buffer.insert_shared(end, Arc::new(right));
+            }
+        }
+    }
+
+    /// Address windows `[start, end)` of all data chunks.
+    fn chunk_windows(&self) -> Vec<(usize, usize)> {
+        self.buffer
+            .iter()
+            .map(|(&start, data)| (start, start + data.len()))
+            .collect()
+    }
+
+    /// First address at or after `addr` that holds data.
+    fn next_data_addr(&self, addr: usize) -> Option<usize> {
+        if let Some((&s, d)) = self.buffer.range(..=addr).next_back()
+            && s + d.len() > addr
+        {
+            return Some(addr);
+        }
+        self.buffer.range(addr..).next().map(|(&s, _)| s)
+    }
+
+    /// Tag all data currently held by this instance as originating from the source `name`
+    /// and enable provenance tracking. Previous source tags are replaced.
+    ///
+    /// Once tracking is enabled, source tags are kept through [`merge`](Self::merge),
+    /// [`merge_safe`](Self::merge_safe), [`relocate`](Self::relocate) and range edits.
+    /// Data merged from an instance that does not track provenance is tagged with the
+    /// file name of that instance. Bytes written by [`update_byte`](Self::update_byte),
+    /// [`update_range`](Self::update_range), [`transform_range`](Self::transform_range)
+    /// and [`write_range`](Self::write_range) are tagged as [`Provenance::EDITED`].
+    ///
+    /// # Example
+    /// ```
+    /// use intelhexlib::IntelHex;
+    ///
+    /// let mut ih1 = IntelHex::from_hex("tests/fixtures/ih_valid_1.hex").unwrap();
+    /// let ih2 = IntelHex::from_hex("tests/fixtures/ih_valid_3.hex").unwrap();
+    ///
+    /// ih1.tag_source("bootloader");
+    /// ih1.merge(&ih2);
+    ///
+    /// let provenance = ih1.provenance().unwrap();
+    /// assert_eq!(provenance.names(), &["bootloader", "ih_valid_3.hex"]);
+    /// assert_eq!(provenance.source_name_at(0x0), Some("bootloader"));
+    /// ```
+    pub fn tag_source(&mut self, name: &str) {
+        // All tags are replaced, so the journal keeps the whole table
+        if let Some(mut journal) = self.journal.take() {
+            let before = self.provenance.clone();
+            self.tag_source(name);
+            journal.record(
+                "Tag source",
+                vec![Change::Provenance {
+                    before,
+                    after: self.provenance.clone(),
+                }],
+            );
+            self.journal = Some(journal);
+            return;
+        }
+
+        let provenance = self.provenance.insert(Provenance::default());
+        let id = provenance.add_source(name);
+
+        for (&chunk_start, chunk_data) in &self.buffer {
+            provenance.tag(chunk_start, chunk_start + chunk_data.len(), id);
+        }
+    }
+
+    /// Get the table of source tags. Returns `None` if provenance tracking is disabled
+    /// (see [`tag_source`](Self::tag_source)).
+    #[must_use]
+    pub const fn provenance(&self) -> Option<&Provenance> {
+        self.provenance.as_ref()
+    }
+
+    /// Name used to tag data of an instance that does not track provenance:
+    /// the file name if available, otherwise "untitled".
+    fn default_source_name(&self) -> String {
+        self.filepath.file_name().map_or_else(
+            || "untitled".to_string(),
+            |name| name.to_string_lossy().into_owned(),
+        )
+    }
+
+    /// Enable provenance tracking before merging `other` into this instance,
+    /// if `other` tracks provenance and this instance does not.
+    fn track_sources_of(&mut self, other: &Self) {
+        if self.provenance.is_some() || other.provenance.is_none() {
+            return;
+        }
+
+        self.provenance = Some(Provenance::default());
+
+        if !self.buffer.is_empty() {
+            let name = self.default_source_name();
+            self.tag_source(&name);
+        }
+    }
+
+    /// Copy source tags of `other` in the range `[start, end)` into this instance.
+    /// If `other` does not track provenance, the range is tagged with its default source name.
+    /// `ids` maps source ids of `other` to ids of this instance across calls for one merge.
+    fn tag_range_from(
+        &mut self,
+        other: &Self,
+        start: usize,
+        end: usize,
+        ids: &mut Vec<Option<usize>>,
+    ) {
+        let Some(provenance) = self.provenance.as_mut() else {
+            return;
+        };
+
+        if let Some(other_provenance) = &other.provenance {
+            provenance.clear_range(start, end);
+            provenance.copy_from(other_provenance, start, end, ids);
+        } else {
+            ids.resize(1, None);
+            let id =
+                *ids[0].get_or_insert_with(|| provenance.add_source(&other.default_source_name()));
+            provenance.tag(start, end, id);
+        }
+    }
+
+    /// Tag the range `[start, end)` as edited if provenance tracking is enabled.
+    fn tag_edit(&mut self, start: usize, end: usize) {
+        if let Some(provenance) = self.provenance.as_mut() {
+            provenance.tag_edit(start, end);
+        }
+    }
+
+    /// Window slide search for a byte array in the `IntelHex` data.
+    /// Returns start addresses of matches.
+    ///
+    /// # Example
+    /// ```
+    /// use intelhexlib::IntelHex;
+    ///
+    /// let mut ih = IntelHex::from_hex("tests/fixtures/ih_valid_1.hex").unwrap();
+    /// let matches: Vec<usize> = ih.search_bytes(&[0x00, 0x00, 0x02]);
+    ///
+    /// assert_eq!(matches, vec![0x1]);
+    /// ```
+    #[must_use]
+    pub fn search_bytes(&self, pattern: &[u8]) -> Vec<usize> {
+        search(self.iter(), &SearchType::Hex(pattern.to_vec()))
+    }
+
+    /// Lazy search for a byte pattern, optionally limited to an address window and a
+    /// maximum number of matches, and optionally across gaps treated as filled with a byte
+    /// (see [`SearchOptions`]). Matches are yielded in ascending address order as they are
+    /// found, so the first ones are available without scanning the whole image.
+    ///
+    /// # Example
+    /// ```
+    /// use intelhexlib::{IntelHex, SearchOptions};
+    ///
+    /// let mut ih = IntelHex::new();
+    /// ih.write_range(0x0, 0x3).unwrap();
+    /// ih.update_range(0x0, &[0xAA, 0xFF, 0xAA, 0xFF]).unwrap();
+    /// ih.write_range(0x6, 0x7).unwrap();
+    /// ih.update_range(0x6, &[0xAA, 0xFF]).unwrap();
+    ///
+    /// // Inside contiguous chunks only
+    /// let matches: Vec<usize> = ih.search_bytes_iter(&[0xFF, 0xAA], &SearchOptions::default()).collect();
+    /// assert_eq!(matches, vec![0x1]);
+    ///
+    /// // Gaps filled with 0xFF, first two matches only
+    /// let options = SearchOptions { gap_fill: Some(0xFF), max_results: Some(2), ..Default::default() };
+    /// let matches: Vec<usize> = ih.search_bytes_iter(&[0xFF, 0xAA], &options).collect();
+    /// assert_eq!(matches, vec![0x1, 0x5]);
+    /// ```
+    #[must_use]
+    pub fn search_bytes_iter(&self, pattern: &[u8], options: &SearchOptions) -> SearchIter<'_> {
+        let window = options
+            .range
+            .or_else(|| self.get_min_addr().zip(self.get_max_addr()));
+
+        SearchIter {
+            buffer: &self.buffer,
+            pattern: pattern.to_vec(),
+            gap_fill: options.gap_fill,
+            matches_gaps: options
+                .gap_fill
+                .is_some_and(|fill| pattern.iter().all(|&b| b == fill)),
+            pos: window.map_or(0, |(start, _)| start),
+            end: window.map(|(_, end)| end).filter(|_| !pattern.is_empty()),
+            remaining: options.max_results.unwrap_or(usize::MAX),
+        }
+    }
+
+    /// Window slide search for byte windows that differ from the pattern in at most
+    /// `max_mismatches` bytes (Hamming distance), e.g. a table with a few changed entries.
+    /// Returns start addresses of matches with their number of mismatching bytes.
+    ///
+    /// Matches have to lie inside `options.range` and stop after `options.max_results`, so
+    /// a large image can be searched in steps. Windows never span gaps, `options.gap_fill`
+    /// is ignored. With `max_mismatches >= pattern.len()` every window matches.
+    ///
+    /// # Example
+    /// ```
+    /// use intelhexlib::{IntelHex, SearchOptions};
+    ///
+    /// let ih = IntelHex::from_hex("tests/fixtures/ih_valid_1.hex").unwrap();
+    /// let options = SearchOptions { max_results: Some(1), ..Default::default() };
+    /// let matches: Vec<(usize, usize)> = ih.search_hamming(&[0xFA, 0x00, 0x00, 0x03], 1, &options);
+    ///
+    /// assert_eq!(matches, vec![(0x0, 1)]);
+    /// ```
+    #[must_use]
+    pub fn search_hamming(
+        &self,
+        pattern: &[u8],
+        max_mismatches: usize,
+        options: &SearchOptions,
+    ) -> Vec<(usize, usize)> {
+        let chunks = self.chunks_in(options.range);
+        search_hamming(
+            chunks.iter().map(|(addr, data)| (addr, *data)),
+            pattern,
+            max_mismatches,
+        )
+        .take(options.max_results.unwrap_or(usize::MAX))
+        .collect()
+    }
+
+    /// Data chunks clipped to the inclusive address `range` (all chunks for `None`)
+    fn chunks_in(&self, range: Option<(usize, usize)>) -> Vec<(usize, &[u8])> {
+        let Some((start, end)) = range else {
+            return self.iter().map(|(&addr, data)| (addr, data)).collect();
+        };
+        if start > end {
+            return Vec::new();
+        }
+
+        // The chunk holding `start` may begin before it
+        let first = self
+            .buffer
+            .range(..=start)
+            .next_back()
+            .map_or(start, |(&addr, _)| addr);
+        self.buffer
+            .range(first..=end)
+            .filter_map(|(&addr, data)| {
+                let from = addr.max(start);
+                let to = (addr + data.len()).min(end.saturating_add(1));
+                (from < to).then(|| (from, &data[from - addr..to - addr]))
+            })
+            .collect()
+    }
+
+    /// Window slide search for a byte pattern with wildcards and masks in the `IntelHex` data.
+    /// Returns start addresses of matches.
+    ///
+    /// Matches have to lie inside `options.range` and stop after `options.max_results`.
+    /// Windows never span gaps, `options.gap_fill` is ignored.
+    ///
+    /// # Example
+    /// ```
+    /// use intelhexlib::{IntelHex, MaskedPattern, SearchOptions};
+    ///
+    /// let mut ih = IntelHex::from_hex("tests/fixtures/ih_valid_1.hex").unwrap();
+    /// let pattern: MaskedPattern = "00 ?? 02".parse().unwrap();
+    /// let matches: Vec<usize> = ih.search_masked(&pattern, &SearchOptions::default());
+    ///
+    /// assert_eq!(matches, vec![0x1]);
+    /// ```
+    #[must_use]
+    pub fn search_masked(&self, pattern: &MaskedPattern, options: &SearchOptions) -> Vec<usize> {
+        let chunks = self.chunks_in(options.range);
+        let mut matches = search(
+            chunks.iter().map(|(addr, data)| (addr, *data)),
+            &SearchType::Masked(pattern.clone()),
+        );
+        matches.truncate(options.max_results.unwrap_or(usize::MAX));
+        matches
+    }
+
+    /// Search for many byte patterns at once in a single pass over the `IntelHex` data.
+    /// Returns `(pattern index, start address)` of all matches, sorted by address.
+    ///
+    /// Unlike calling [`search_bytes`](Self::search_bytes) once per pattern, the cost does not
+    /// grow with the number of patterns, which makes it suitable for scanning an image for
+    /// hundreds of known constants. Empty patterns never match.
+    ///
+    /// # Example
+    /// ```
+    /// use intelhexlib::IntelHex;
+    ///
+    /// let ih = IntelHex::from_hex("tests/fixtures/ih_valid_1.hex").unwrap();
+    /// let patterns: [&[u8]; 2] = [b"H0Y", &[0x00, 0x00, 0x02]];
+    /// let matches: Vec<(usize, usize)> = ih.search_many(&patterns);
+    ///
+    /// assert_eq!(matches, vec![(1, 0x1), (0, 0x1C237)]);
+    /// ```
+    #[must_use]
+    pub fn search_many<P: AsRef<[u8]>>(&self, patterns: &[P]) -> Vec<(usize, usize)> {
+        search_many(self.iter(), patterns)
+    }
+
+    /// Search for a numeric value stored in any integer or float encoding, little- or
+    /// big-endian (see [`NumericValue::encodings`]). Returns start addresses of matches with
+    /// the encoding found there, sorted by address.
+    ///
+    /// Matches have to lie inside `options.range` and stop after `options.max_results`.
+    /// Values never span gaps, `options.gap_fill` is ignored.
+    ///
+    /// # Example
+    /// ```
+    /// use intelhexlib::{IntelHex, NumericValue, SearchOptions};
+    ///
+    /// let ih = IntelHex::from_hex("tests/fixtures/ih_valid_1.hex").unwrap();
+    /// let value: NumericValue = "2".parse().unwrap();
+    /// let matches: Vec<String> = ih
+    ///     .search_value(&value, &SearchOptions::default())
+    ///     .iter()
+    ///     .map(|(addr, encoding)| format!("{addr:X}: {encoding}"))
+    ///     .collect();
+    ///
+    /// assert_eq!(matches[0], "2: u16 BE");
+    /// ```
+    #[must_use]
+    pub fn search_value(
+        &self,
+        value: &NumericValue,
+        options: &SearchOptions,
+    ) -> Vec<(usize, ValueEncoding)> {
+        let chunks = self.chunks_in(options.range);
+        let mut matches = search_value(chunks.iter().map(|(addr, data)| (addr, *data)), value);
+        matches.truncate(options.max_results.unwrap_or(usize::MAX));
+        matches
+    }
+
+    /// Extract printable strings of at least `min_len` characters in the given encodings,
+    /// like the `strings` tool does. Yields `(address, encoding, text)` in ascending address
+    /// order, one data chunk at a time. Strings do not span gaps between chunks.
+    ///
+    /// # Example
+    /// ```
+    /// use intelhexlib::{IntelHex, StringEncoding};
+    ///
+    /// let ih = IntelHex::from_hex("tests/fixtures/ih_valid_1.hex").unwrap();
+    /// let mut strings = ih.strings(3, &[StringEncoding::Ascii]);
+    ///
+    /// assert!(strings.any(|(addr, _, text)| addr == 0x1C236 && text == " H0Y-"));
+    /// ```
+    pub fn strings(
+        &self,
+        min_len: usize,
+        encodings: &[StringEncoding],
+    ) -> impl Iterator<Item = (usize, StringEncoding, String)> + '_ {
+        let encodings = encodings.to_vec();
+        self.iter()
+            .flat_map(move |(&addr, data)| find_strings(addr, data, min_len, &encodings))
+    }
+
+    /// Shannon entropy of every block of `block_size` bytes that holds data, in bits per
+    /// byte (0.0 to 8.0). Blocks are aligned to multiples of `block_size` and only the bytes
+    /// present in a block are taken into account, gaps are ignored.
+    /// Returns `(block start address, entropy)` in ascending address order.
+    ///
+    /// Entropy close to 8 hints at compressed or encrypted data, close to 0 at erased
+    /// flash or padding.
+    ///
+    /// # Errors
+    /// Returns an error if `block_size` is 0.
+    ///
+    /// # Example
+    /// ```
+    /// use intelhexlib::IntelHex;
+    ///
+    /// let ih = IntelHex::from_hex("tests/fixtures/ih_valid_1.hex").unwrap();
+    /// let blocks = ih.entropy(0x100).unwrap();
+    ///
+    /// // Bytes FA 00 00 02 at 0x0
+    /// assert_eq!(blocks[0], (0x0, 1.5));
+    /// assert_eq!(blocks[1].0, 0x1C200);
+    /// ```
+    pub fn entropy(&self, block_size: usize) -> Result<Vec<(usize, f64)>, IntelHexError> {
+        if block_size == 0 {
+            return Err(IntelHexError::AnalysisError(
+                IntelHexErrorKind::InvalidBlockSize(block_size),
+            ));
+        }
+
+        Ok(block_entropy(self.iter(), block_size))
+    }
+
+    /// Number of occurrences of every byte value in the given address range.
+    /// Gaps are not counted.
+    ///
+    /// # Example
+    /// ```
+    /// use intelhexlib::IntelHex;
+    ///
+    /// let ih = IntelHex::from_hex("tests/fixtures/ih_valid_1.hex").unwrap();
+    /// let histogram = ih.histogram(0x0..0x4);
+    ///
+    /// // Bytes FA 00 00 02 at 0x0
+    /// assert_eq!(histogram[0x00], 2);
+    /// assert_eq!(histogram[0xFA], 1);
+    /// assert_eq!(ih.histogram(..).iter().sum::<usize>(), ih.size);
+    /// ```
+    #[must_use]
+    pub fn histogram<R: RangeBounds<usize>>(&self, range: R) -> [usize; 256] {
+        let start = match range.start_bound() {
+            Bound::Included(&addr) => addr,
+            Bound::Excluded(&addr) => addr.saturating_add(1),
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&addr) => addr.saturating_add(1),
+            Bound::Excluded(&addr) => addr,
+            Bound::Unbounded => usize::MAX,
+        };
+
+        let mut histogram = [0; 256];
+        for (&addr, data) in self {
+            if addr >= end {
+                break;
+            }
+            add_to_histogram(&mut histogram, addr, data, start, end);
+        }
+        histogram
+    }
+
+    /// Scan the data for embedded files (compressed streams, images, certificates,
+    /// executables, file systems, ...) with the signatures of the table, like `binwalk` does.
+    /// Returns the hits with their decoded headers in ascending address order.
+    /// Embedded files do not span gaps between chunks.
+    ///
+    /// # Example
+    /// ```
+    /// use intelhexlib::{IntelHex, SignatureTable};
+    ///
+    /// // zlib stream header at 0x100
+    /// let mut ih = IntelHex::new();
+    /// ih.write_range(0x100, 0x10F).unwrap();
+    /// ih.update_range(0x100, &[0x78, 0x9C, 0x4B, 0x4C]).unwrap();
+    ///
+    /// let hits = ih.scan_signatures(&SignatureTable::new());
+    ///
+    /// assert_eq!(hits.len(), 1);
+    /// assert_eq!(hits[0].address, 0x100);
+    /// assert_eq!(hits[0].name, "zlib");
+    /// assert_eq!(hits[0].info.description, "zlib compressed data, compression level: default");
+    /// ```
+    #[must_use]
+    pub fn scan_signatures(&self, table: &SignatureTable) -> Vec<SignatureHit> {
+        scan_signatures(self.iter(), table)
+    }
+
+    /// Find plausible ARM Cortex-M vector tables: an initial stack pointer pointing into RAM
+    /// followed by reset, NMI and hard fault handlers that are Thumb addresses inside the
+    /// image. Only addresses aligned to 0x80 bytes are checked. Returns the decoded tables
+    /// in ascending address order.
+    ///
+    /// # Example
+    /// ```
+    /// use intelhexlib::IntelHex;
+    ///
+    /// // Initial SP 0x20001000 and handlers at 0x41 (Reset, NMI, HardFault)
+    /// let mut ih = IntelHex::new();
+    /// ih.write_range(0x0, 0x7F).unwrap();
+    /// ih.update_range(0x0, &[0x00, 0x10, 0x00, 0x20]).unwrap();
+    /// ih.update_range(0x4, &[0x41, 0x00, 0x00, 0x00].repeat(3)).unwrap();
+    ///
+    /// let tables = ih.vector_tables();
+    ///
+    /// assert_eq!(tables.len(), 1);
+    /// assert_eq!(tables[0].initial_sp, 0x2000_1000);
+    /// assert_eq!(tables[0].reset_handler(), 0x40);
+    /// ```
+    #[must_use]
+    pub fn vector_tables(&self) -> Vec<VectorTable> {
+        find_vector_tables(self.iter())
+    }
+
+    /// Find cross-references: words of the given `encodings` whose value is an address
+    /// inside the populated part of the image. Every word is read as unsigned integer of the
+    /// encoding's size and byte order. If `aligned` is set, words are only read at addresses
+    /// that are a multiple of their size. Returns an index from every referenced address
+    /// (target) to the words referring to it.
+    ///
+    /// # Example
+    /// ```
+    /// use intelhexlib::{IntelHex, POINTER_ENCODINGS};
+    ///
+    /// // u32 LE pointer at 0x100 to the table at 0x108
+    /// let mut ih = IntelHex::new();
+    /// ih.write_range(0x100, 0x10F).unwrap();
+    /// ih.update_range(0x100, &[0x08, 0x01, 0x00, 0x00]).unwrap();
+    ///
+    /// let index = ih.references(&POINTER_ENCODINGS, true);
+    /// let referrers = index.referrers(0x108);
+    ///
+    /// assert_eq!(index.len(), 1);
+    /// assert_eq!(referrers.len(), 3); // read as u16, u32 and u64 LE
+    /// assert_eq!(referrers[1].address, 0x100);
+    /// assert_eq!(referrers[1].encoding.to_string(), "u32 LE");
+    /// ```
+    #[must_use]
+    pub fn references(&self, encodings: &[ValueEncoding], aligned: bool) -> ReferenceIndex {
+        find_references(self.iter(), encodings, aligned, |_| true)
+    }
+
+    /// Find the words referring to `target`, see [`IntelHex::references`]. Returns the
+    /// referrers in ascending address order.
+    ///
+    /// # Example
+    /// ```
+    /// use intelhexlib::{IntelHex, POINTER_ENCODINGS};
+    ///
+    /// // u16 BE pointer at 0x10 to 0x1C
+    /// let mut ih = IntelHex::new();
+    /// ih.write_range(0x10, 0x1F).unwrap();
+    /// ih.update_range(0x10, &[0x00, 0x1C]).unwrap();
+    ///
+    /// let referrers = ih.references_to(0x1C, &POINTER_ENCODINGS, true);
+    ///
+    /// assert_eq!(referrers.len(), 1);
+    /// assert_eq!(referrers[0].address, 0x10);
+    /// assert_eq!(referrers[0].encoding.to_string(), "u16 BE");
+    /// ```
+    #[must_use]
+    pub fn references_to(
+        &self,
+        target: usize,
+        encodings: &[ValueEncoding],
+        aligned: bool,
+    ) -> Vec<Reference> {
+        find_references(self.iter(), encodings, aligned, |addr| addr == target)
+            .referrers(target)
+            .to_vec()
+    }
+
+    /// Decode the struct `name` of the [`Template`] at `address` into a tree of fields.
+    /// Fields without a byte order in the template are decoded as big-endian if
+    /// `big_endian` is set. Fields that are not fully populated have no value.
+    /// Returns `None` if the template has no struct with the name.
+    ///
+    /// # Example
+    /// ```
+    /// use intelhexlib::{FieldValue, IntelHex, Template};
+    ///
+    /// let template = Template::parse("struct hdr { uint16_t version; __be32 length; };").unwrap();
+    ///
+    /// let mut ih = IntelHex::new();
+    /// ih.write_range(0x20, 0x25).unwrap();
+    /// ih.update_range(0x20, &[0x02, 0x01, 0x00, 0x00, 0x10, 0x00]).unwrap();
+    ///
+    /// let hdr = ih.decode_template(&template, "hdr", 0x20, false).unwrap();
     ///
-    /// assert_eq!(matches, vec![0x1]);
+    /// assert_eq!(hdr.children[0].value, Some(FieldValue::Unsigned(0x0102)));
+    /// assert_eq!(hdr.children[1].address, 0x22);
+    /// assert_eq!(hdr.children[1].value, Some(FieldValue::Unsigned(0x1000)));
+    ///
+    /// // Write a new value back
+    /// let bytes = hdr.children[1].encode("0x2000").unwrap();
+    /// ih.update_range(hdr.children[1].address, &bytes).unwrap();
+    /// assert_eq!(ih.read_range(0x22, 4).unwrap(), [0x00, 0x00, 0x20, 0x00]);
     /// ```
     #[must_use]
-    pub fn search_bytes(&self, pattern: &[u8]) -> Vec<usize> {
-        search(self.iter(), &SearchType::Hex(pattern.to_vec()))
+    pub fn decode_template(
+        &self,
+        template: &Template,
+        name: &str,
+        address: usize,
+        big_endian: bool,
+    ) -> Option<TemplateField> {
+        let size = template.size_of(name)?;
+        let bytes = self.read_range_safe(address, size);
+        decode_template(template, name, address, big_endian, &bytes)
     }
 
     /// Window slide search for an ASCII string in the `IntelHex` data.
@@ -1621,7 +3444,7 @@ mod tests {
 
         // Assert
         assert!(res.is_ok());
-        assert_eq!(*ih.buffer.get(&addr).unwrap_or(&vec![]), [value - 1]);
+        assert_eq!(*ih.buffer.get(&addr).unwrap_or_default(), [value - 1]);
     }
 
     #[test]
@@ -1709,7 +3532,7 @@ mod tests {
             update_map.push((addr, (addr - 1) as u8));
             ih.buffer.insert(addr, vec![addr as u8]);
         }
-        ih.buffer.pop_last(); // pop the last addr
+        let _ = ih.buffer.remove(&(addr_start + length)); // pop the last addr
 
         // Act
         let res = ih.update_slice(&update_map);
@@ -1847,7 +3670,7 @@ mod tests {
 
         // Assert
         assert!(res.is_ok());
-        assert_eq!(ih.buffer.get(&0x0), Some(&vec![0xFFu8]));
+        assert_eq!(ih.buffer.get(&0x0), Some(&[0xFFu8][..]));
     }
 
     #[test]
@@ -1889,10 +3712,10 @@ mod tests {
         ih1.merge(&ih2);
 
         // Assert
-        assert_eq!(*ih1.buffer.get(&0x00).unwrap_or(&vec![]), vec![0x0, 0x0]);
-        assert_eq!(*ih1.buffer.get(&0x10).unwrap_or(&vec![]), vec![0x1, 0x1]);
+        assert_eq!(*ih1.buffer.get(&0x00).unwrap_or_default(), vec![0x0, 0x0]);
+        assert_eq!(*ih1.buffer.get(&0x10).unwrap_or_default(), vec![0x1, 0x1]);
         assert_eq!(
-            *ih1.buffer.get(&0x20).unwrap_or(&vec![]),
+            *ih1.buffer.get(&0x20).unwrap_or_default(),
             vec![0x0, 0x0, 0x1, 0x1, 0x0, 0x0]
         );
         assert_eq!(ih1.start_addr, ih2.start_addr);
@@ -1911,20 +3734,100 @@ mod tests {
         ih1.merge(&ih3);
 
         // Assert
-        assert_eq!(*ih1.buffer.get(&0x00).unwrap_or(&vec![]), vec![0x0, 0x0]);
-        assert_eq!(*ih1.buffer.get(&0x04).unwrap_or(&vec![]), vec![0x2]);
+        assert_eq!(*ih1.buffer.get(&0x00).unwrap_or_default(), vec![0x0, 0x0]);
+        assert_eq!(*ih1.buffer.get(&0x04).unwrap_or_default(), vec![0x2]);
         assert_eq!(
-            *ih1.buffer.get(&0x10).unwrap_or(&vec![]),
+            *ih1.buffer.get(&0x10).unwrap_or_default(),
             vec![0x1, 0x1, 0x2]
         );
         assert_eq!(
-            *ih1.buffer.get(&0x20).unwrap_or(&vec![]),
+            *ih1.buffer.get(&0x20).unwrap_or_default(),
             vec![0x0, 0x0, 0x1, 0x1, 0x2, 0x2, 0x2]
         );
         assert_eq!(ih1.start_addr, ih3.start_addr);
         assert_eq!(ih1.size, 13);
     }
 
+    #[test]
+    fn test_merge_tracks_sources() {
+        // Arrange
+        let mut ih1 = IntelHex::new();
+        ih1.buffer.insert(0x00, vec![0x0; 0x10]);
+        ih1.size = 0x10;
+        ih1.tag_source("boot.hex");
+
+        let mut ih2 = IntelHex::new();
+        ih2.filepath = PathBuf::from("fw/app.hex");
+        ih2.buffer.insert(0x08, vec![0x1; 0x10]);
+        ih2.size = 0x10;
+
+        // Act
+        ih1.merge(&ih2);
+
+        // Assert
+        let provenance = ih1.provenance().unwrap_or_else(|| unreachable!());
+        assert_eq!(provenance.names(), &["boot.hex", "app.hex"]);
+        assert_eq!(provenance.source_name_at(0x07), Some("boot.hex"));
+        assert_eq!(provenance.source_name_at(0x08), Some("app.hex"));
+        assert_eq!(provenance.source_name_at(0x17), Some("app.hex"));
+        assert_eq!(provenance.source_name_at(0x18), None);
+        assert_eq!(provenance.sizes(), vec![0x08, 0x10]);
+    }
+
+    #[test]
+    fn test_sources_with_same_name_and_edits() {
+        // Arrange
+        let mut ih1 = IntelHex::new();
+        ih1.buffer.insert(0x00, vec![0x0; 0x10]);
+        ih1.size = 0x10;
+        ih1.tag_source("app.hex");
+
+        let mut ih2 = IntelHex::new();
+        ih2.buffer.insert(0x20, vec![0x1; 0x10]);
+        ih2.size = 0x10;
+        ih2.tag_source("app.hex");
+
+        // Act
+        ih1.merge(&ih2);
+        let res1 = ih1.update_byte(0x02, 0xFF);
+        let res2 = ih1.update_range(0x24, &[0xAA, 0xBB]);
+
+        // Assert
+        assert!(res1.is_ok() && res2.is_ok());
+        let provenance = ih1.provenance().unwrap_or_else(|| unreachable!());
+        assert_eq!(
+            provenance.names(),
+            &["app.hex", "app.hex", Provenance::EDITED]
+        );
+        assert_eq!(provenance.source_at(0x01), Some(0));
+        assert_eq!(provenance.source_at(0x02), Some(2));
+        assert_eq!(provenance.source_at(0x23), Some(1));
+        assert_eq!(provenance.source_at(0x25), Some(2));
+        assert_eq!(provenance.sizes(), vec![0x0F, 0x0E, 0x03]);
+    }
+
+    #[test]
+    fn test_sources_follow_relocate_and_remove_range() {
+        // Arrange
+        let mut ih = IntelHex::new();
+        ih.buffer.insert(0x100, vec![0x0; 0x10]);
+        ih.size = 0x10;
+        ih.tag_source("app.hex");
+
+        // Act
+        let res1 = ih.relocate(0x1000);
+        let res2 = ih.remove_range(0x1004, 0x1007);
+
+        // Assert
+        assert!(res1.is_ok() && res2.is_ok());
+        let provenance = ih.provenance().unwrap_or_else(|| unreachable!());
+        assert_eq!(provenance.source_at(0x100), None);
+        assert_eq!(provenance.source_at(0x1003), Some(0));
+        assert_eq!(provenance.source_at(0x1004), None);
+        assert_eq!(provenance.source_at(0x1008), Some(0));
+        assert_eq!(provenance.sizes(), vec![0x0C]);
+    }
+
     #[test]
     fn test_merge_safe_valid() {
         // Arrange
@@ -1946,10 +3849,10 @@ mod tests {
 
         // Assert
         assert!(res.is_ok());
-        assert_eq!(*ih1.buffer.get(&0x00).unwrap_or(&vec![]), vec![0x0, 0x0]);
-        assert_eq!(*ih1.buffer.get(&0x10).unwrap_or(&vec![]), vec![0x1, 0x1]);
+        assert_eq!(*ih1.buffer.get(&0x00).unwrap_or_default(), vec![0x0, 0x0]);
+        assert_eq!(*ih1.buffer.get(&0x10).unwrap_or_default(), vec![0x1, 0x1]);
         assert_eq!(
-            *ih1.buffer.get(&0x20).unwrap_or(&vec![]),
+            *ih1.buffer.get(&0x20).unwrap_or_default(),
             vec![0x0, 0x0, 0x1, 0x1, 0x0, 0x0]
         );
         assert_eq!(ih1.start_addr, ih2.start_addr);
@@ -2029,6 +3932,206 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_transform_range_valid() {
+        // Arrange
+        let mut ih = IntelHex::new();
+        ih.buffer
+            .insert(0x1000, vec![0x01, 0x02, 0x03, 0x04, 0x05, 0x06]);
+
+        // Act
+        let res1 = ih.transform_range(0x1000, 0x1003, &Transform::Swap32);
+        let res2 = ih.transform_range(0x1004, 0x1005, &Transform::Xor(vec![0xFF]));
+
+        // Assert
+        assert!(res1.is_ok() && res2.is_ok());
+        assert_eq!(
+            *ih.buffer.get(&0x1000).unwrap_or_default(),
+            vec![0x04, 0x03, 0x02, 0x01, 0xFA, 0xF9]
+        );
+    }
+
+    #[test]
+    fn test_transform_range_invalid() {
+        // Arrange
+        let mut ih = IntelHex::new();
+        ih.buffer.insert(0x1000, vec![0x01, 0x02, 0x03, 0x04]);
+        ih.buffer.insert(0x1008, vec![0x05, 0x06]);
+
+        // Act
+        let res_gap = ih.transform_range(0x1002, 0x1009, &Transform::Swap16);
+        let res_len = ih.transform_range(0x1000, 0x1002, &Transform::Swap16);
+        let res_key = ih.transform_range(0x1000, 0x1003, &Transform::Xor(vec![]));
+
+        // Assert
+        assert_eq!(
+            res_gap,
+            Err(IntelHexError::UpdateError(
+                IntelHexErrorKind::InvalidAddress(0x1002)
+            ))
+        );
+        assert_eq!(
+            res_len,
+            Err(IntelHexError::UpdateError(
+                IntelHexErrorKind::TransformLengthInvalid(2, 3)
+            ))
+        );
+        assert_eq!(
+            res_key,
+            Err(IntelHexError::UpdateError(
+                IntelHexErrorKind::TransformKeyEmpty
+            ))
+        );
+        assert_eq!(
+            *ih.buffer.get(&0x1000).unwrap_or_default(),
+            vec![0x01, 0x02, 0x03, 0x04]
+        );
+    }
+
+    #[test]
+    fn test_split_and_join_lanes_valid() {
+        // Arrange - two chunks with a gap, second one not aligned to the lane count
+        let mut ih = IntelHex::new();
+        ih.buffer.insert(0x100, vec![0, 1, 2, 3, 4, 5, 6, 7]);
+        ih.buffer.insert(0x201, vec![0xA1, 0xA2, 0xA3]);
+        ih.size = 11;
+
+        // Act
+        let lanes = ih.split_lanes(2).unwrap_or_default();
+        let joined = IntelHex::join_lanes(&lanes).unwrap_or_default();
+
+        // Assert
+        assert_eq!(lanes.len(), 2);
+        assert_eq!(lanes[0].read_range(0x80, 4), Some(vec![0, 2, 4, 6]));
+        assert_eq!(lanes[1].read_range(0x80, 4), Some(vec![1, 3, 5, 7]));
+        assert_eq!(lanes[0].read_byte(0x100), None);
+        assert_eq!(lanes[0].read_byte(0x101), Some(0xA2));
+        assert_eq!(lanes[1].read_range(0x100, 2), Some(vec![0xA1, 0xA3]));
+        assert_eq!(lanes[0].size + lanes[1].size, ih.size);
+
+        assert_eq!(joined.size, ih.size);
+        assert_eq!(joined.buffer, ih.buffer);
+    }
+
+    #[test]
+    fn test_split_and_join_lanes_invalid() {
+        // Arrange
+        let mut ih = IntelHex::new();
+        ih.buffer.insert(0xFFFF_FF00, vec![0; 0x100]);
+        ih.size = 0x100;
+
+        // Act
+        let res_split = ih.split_lanes(0);
+        let res_join_empty = IntelHex::join_lanes(&[]);
+        let res_join_overflow = IntelHex::join_lanes(&[ih.clone(), ih]);
+
+        // Assert
+        assert_eq!(
+            res_split.err(),
+            Some(IntelHexError::UpdateError(
+                IntelHexErrorKind::InvalidLaneCount(0)
+            ))
+        );
+        assert_eq!(
+            res_join_empty.err(),
+            Some(IntelHexError::UpdateError(
+                IntelHexErrorKind::InvalidLaneCount(0)
+            ))
+        );
+        assert_eq!(
+            res_join_overflow.err(),
+            Some(IntelHexError::UpdateError(
+                IntelHexErrorKind::AddressRangeOverflow
+            ))
+        );
+    }
+
+    #[test]
+    fn test_split_by_segment_and_every_valid() {
+        // Arrange
+        let mut ih = IntelHex::new();
+        ih.buffer.insert(0x100, vec![0x11; 0x30]);
+        ih.buffer.insert(0x1000, vec![0x22; 0x10]);
+        ih.size = 0x40;
+
+        // Act
+        let segments = ih.split_by_segment();
+        let parts = ih.split_every(0x20).unwrap_or_default();
+
+        // Assert
+        assert_eq!(segments.len(), 2);
+        assert_eq!(segments[0].0, "segment0");
+        assert_eq!(segments[0].1.size, 0x30);
+        assert_eq!(segments[1].1.get_min_addr(), Some(0x1000));
+
+        let layout: Vec<(&str, Option<usize>, usize)> = parts
+            .iter()
+            .map(|(name, ih)| (name.as_str(), ih.get_min_addr(), ih.size))
+            .collect();
+        assert_eq!(
+            layout,
+            vec![
+                ("part0", Some(0x100), 0x20),
+                ("part1", Some(0x120), 0x10),
+                ("part2", Some(0x1000), 0x10),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_split_every_invalid() {
+        // Arrange
+        let mut ih = IntelHex::new();
+        ih.buffer.insert(0x100, vec![0x11; 0x30]);
+        ih.size = 0x30;
+
+        // Act
+        let res = ih.split_every(0);
+
+        // Assert
+        assert_eq!(
+            res.err(),
+            Some(IntelHexError::UpdateError(
+                IntelHexErrorKind::InvalidSplitSize(0)
+            ))
+        );
+    }
+
+    #[test]
+    fn test_split_by_region_valid() {
+        // Arrange
+        let mut ih = IntelHex::new();
+        ih.buffer.insert(0x0F0, vec![0x11; 0x20]);
+        ih.buffer.insert(0x400, vec![0x22; 0x10]);
+        ih.size = 0x30;
+        ih.tag_source("fw.hex");
+
+        let mut map = MemoryMap::new();
+        let _ = map.add_region("boot", 0x100, 0x1FF);
+        let _ = map.add_region("empty", 0x200, 0x3FF);
+        let _ = map.add_region("app", 0x400, 0x7FF);
+
+        // Act
+        let parts = ih.split_by_region(&map);
+
+        // Assert - data below 0x100 is outside of all regions
+        let layout: Vec<(&str, Option<usize>, usize)> = parts
+            .iter()
+            .map(|(name, ih)| (name.as_str(), ih.get_min_addr(), ih.size))
+            .collect();
+        assert_eq!(
+            layout,
+            vec![("boot", Some(0x100), 0x10), ("app", Some(0x400), 0x10)]
+        );
+        assert_eq!(
+            parts[0]
+                .1
+                .provenance()
+                .and_then(|p| p.source_name_at(0x100)),
+            Some("fw.hex")
+        );
+    }
+
     #[test]
     fn test_write_range_valid() {
         // Arrange - empty buffer
@@ -2321,6 +4424,258 @@ mod tests {
         assert_eq!(ih.size, 0);
     }
 
+    #[test]
+    fn test_journal_undo_redo_valid() {
+        // Arrange
+        let mut ih = IntelHex::new();
+        ih.buffer.insert(0x00, vec![0x1; 0x10]);
+        ih.size = 0x10;
+        ih.tag_source("boot.hex");
+        ih.enable_journal();
+
+        let mut other = IntelHex::new();
+        other.buffer.insert(0x08, vec![0x2; 0x10]);
+        other.size = 0x10;
+        other.start_addr = Some([0u8; 18]);
+
+        let original = ih.buffer.clone();
+        let original_provenance = ih.provenance.clone();
+
+        // Act
+        let _ = ih.update_byte(0x0, 0xFF);
+        let _ = ih.write_range(0x20, 0x2F);
+        let _ = ih.remove_range(0x04, 0x07);
+        ih.begin_transaction("Merge and relocate");
+        ih.merge(&other);
+        let _ = ih.relocate(0x100);
+        ih.commit_transaction();
+        let edited = ih.buffer.clone();
+        let edited_provenance = ih.provenance.clone();
+
+        let mut undone = 0;
+        while ih.undo() {
+            undone += 1;
+        }
+        let restored = ih.buffer.clone();
+        let restored_size = ih.size;
+        let restored_start_addr = ih.start_addr;
+        let restored_provenance = ih.provenance.clone();
+
+        let mut redone = 0;
+        while ih.redo() {
+            redone += 1;
+        }
+
+        // Assert
+        assert_eq!((undone, redone), (4, 4));
+        assert_eq!(restored, original);
+        assert_eq!(restored_size, 0x10);
+        assert_eq!(restored_start_addr, None);
+        assert_eq!(restored_provenance, original_provenance);
+        assert_eq!(ih.buffer, edited);
+        assert_eq!(ih.provenance, edited_provenance);
+        assert_eq!(ih.start_addr, Some([0u8; 18]));
+        assert_eq!(ih.size, ih.buffer.values().map(<[u8]>::len).sum::<usize>());
+        assert_eq!(
+            ih.journal().and_then(Journal::undo_label),
+            Some("Merge and relocate")
+        );
+    }
+
+    #[test]
+    fn test_journal_undo_redo_invalid() {
+        // Arrange
+        let mut ih = IntelHex::new();
+        ih.buffer.insert(0x00, vec![0x1; 0x10]);
+        ih.size = 0x10;
+
+        // Act
+        let undo_disabled = ih.undo();
+        ih.enable_journal();
+        let res = ih.update_byte(0x20, 0xFF);
+        let undo_failed_op = ih.undo();
+        let _ = ih.update_byte(0x0, 0xFF);
+        let _ = ih.undo();
+        let _ = ih.update_byte(0x1, 0xFF);
+
+        // Assert
+        assert!(!undo_disabled);
+        assert!(res.is_err());
+        assert!(!undo_failed_op);
+        assert_eq!(ih.read_byte(0x0), Some(0x1));
+        assert!(!ih.journal().is_some_and(Journal::can_redo));
+    }
+
+    /// Lines of a hex file with ELA and ESA segments, gaps, an empty data record,
+    /// blank lines and CRLF line endings.
+    #[allow(clippy::cast_possible_truncation)]
+    fn hex_lines_for_parallel_parse() -> Vec<String> {
+        let data_record = |addr: u16, len: u8| {
+            let data: Vec<u8> = (0..len).map(|i| (addr as u8) ^ i).collect();
+            Record::create(addr, RecordType::Data, &data).unwrap_or_default()
+        };
+
+        let mut lines: Vec<String> = (0..400).map(|i| data_record(i * 16, 16)).collect();
+        lines.push(
+            Record::create(0, RecordType::ExtendedLinearAddress, &[0x00, 0x01]).unwrap_or_default(),
+        );
+        lines.extend((0..100).map(|i| data_record(i * 32, 32)));
+        lines.push(Record::create(0x10, RecordType::Data, &[]).unwrap_or_default());
+        lines.push(String::new());
+        lines.extend((0..100).map(|i| data_record(0x8000 + i * 8, 8)));
+        lines.push(":020000022000DC\r".to_string()); // ESA 0x2000
+        lines.extend((0..300).map(|i| data_record(i * 16, 16)));
+        lines.push(
+            Record::create(0, RecordType::StartLinearAddress, &[0x00, 0x00, 0x12, 0x34])
+                .unwrap_or_default(),
+        );
+        lines.push(":00000001FF".to_string());
+        lines
+    }
+
+    /// Parse the lines with 1 to 6 threads and return the results.
+    fn parse_with_all_thread_counts(
+        lines: &[String],
+    ) -> Vec<(Result<(), IntelHexError>, IntelHex)> {
+        let raw = lines.join("\n");
+        (1..=6)
+            .map(|threads| {
+                let mut ih = IntelHex::new();
+                let res = ih.parse_with_threads(raw.as_bytes(), threads);
+                (res, ih)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_parallel_valid() {
+        // Arrange
+        let lines = hex_lines_for_parallel_parse();
+
+        // Act
+        let results = parse_with_all_thread_counts(&lines);
+
+        // Assert
+        let (seq_res, seq_ih) = &results[0];
+        assert_eq!(seq_res, &Ok(()));
+        assert_eq!(seq_ih.buffer.len(), 4);
+        assert!(seq_ih.start_addr.is_some());
+        for (res, ih) in &results[1..] {
+            assert_eq!(res, seq_res);
+            assert_eq!(ih.buffer, seq_ih.buffer);
+            assert_eq!(ih.start_addr, seq_ih.start_addr);
+        }
+    }
+
+    #[test]
+    fn test_parse_parallel_invalid() {
+        use IntelHexErrorKind::{
+            ContainsInvalidCharacters, DuplicateStartAddress, RecordAddressOverlap,
+            RecordChecksumMismatch,
+        };
+
+        // Arrange: each case breaks a valid file in a different way
+        let valid = hex_lines_for_parallel_parse();
+        let mut cases = Vec::new();
+
+        let mut bad_checksum = valid.clone();
+        let last_digit = bad_checksum[700].pop();
+        bad_checksum[700].push(if last_digit == Some('0') { '1' } else { '0' });
+        cases.push((bad_checksum, RecordChecksumMismatch(0x72, 0x70), 700));
+
+        let mut invalid_char = valid.clone();
+        invalid_char[100].replace_range(9..10, "G");
+        cases.push((invalid_char, ContainsInvalidCharacters, 101));
+
+        let mut overlap = valid.clone();
+        overlap.insert(
+            850,
+            Record::create(0x0100, RecordType::Data, &[0x00; 0x40]).unwrap_or_default(),
+        );
+        cases.push((overlap, RecordAddressOverlap(0x20100), 850));
+
+        // Overlap in the middle of a run of contiguous records
+        let mut overlap_in_run = valid.clone();
+        let at = valid.len() - 2;
+        overlap_in_run.splice(
+            at..at,
+            [
+                Record::create(0, RecordType::ExtendedLinearAddress, &[0x00, 0x01])
+                    .unwrap_or_default(),
+                Record::create(0x7FF0, RecordType::Data, &[0x00; 0x10]).unwrap_or_default(),
+                Record::create(0x8000, RecordType::Data, &[0x00; 0x10]).unwrap_or_default(),
+            ],
+        );
+        cases.push((overlap_in_run, RecordAddressOverlap(0x18000), 906));
+
+        let mut duplicate_start = valid.clone();
+        duplicate_start.insert(3, valid[valid.len() - 2].clone());
+        cases.push((duplicate_start, DuplicateStartAddress, 906));
+
+        for (lines, kind, line) in cases {
+            // Act
+            let results = parse_with_all_thread_counts(&lines);
+
+            // Assert
+            let expected = Err(IntelHexError::ParseRecordError(kind, line));
+            for (res, _) in results {
+                assert_eq!(res, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_snapshot_restore_valid() {
+        // Arrange
+        let mut ih = IntelHex::new();
+        ih.buffer.insert(0x00, vec![0x1; 0x10]);
+        ih.buffer.insert(0x100, vec![0x2; 0x10]);
+        ih.size = 0x20;
+        ih.enable_journal();
+
+        // Act
+        let original = ih.snapshot();
+        let _ = ih.update_byte(0x100, 0xFF);
+        let _ = ih.remove_range(0x00, 0x03);
+        let patched = ih.clone();
+        ih.restore(&original);
+        let restored = ih.buffer.clone();
+        let undone = ih.undo();
+
+        // Assert
+        assert!(patched.buffer.shares_chunk(&ih.buffer, 0x100));
+        assert!(!patched.buffer.shares_chunk(&original.buffer, 0x100));
+        assert!(restored.shares_chunk(&original.buffer, 0x00));
+        assert!(restored.shares_chunk(&original.buffer, 0x100));
+        assert_eq!(original.size(), 0x20);
+        assert!(undone);
+        assert_eq!(ih.buffer, patched.buffer);
+        assert_eq!(ih.size, 0x1C);
+    }
+
+    #[test]
+    fn test_journal_shares_whole_chunks() {
+        // Arrange
+        let mut ih = IntelHex::new();
+        ih.buffer.insert(0x00, vec![0x1; 0x10]);
+        ih.buffer.insert(0x100, vec![0x2; 0x10]);
+        ih.size = 0x20;
+        ih.enable_journal();
+        let original = ih.snapshot();
+
+        // Act
+        let _ = ih.remove_range(0x00, 0x1FF);
+        let removed = ih.buffer.is_empty();
+        let undone = ih.undo();
+
+        // Assert
+        assert!(removed);
+        assert!(undone);
+        assert!(ih.buffer.shares_chunk(&original.buffer, 0x00));
+        assert!(ih.buffer.shares_chunk(&original.buffer, 0x100));
+        assert_eq!(ih.size, 0x20);
+    }
+
     #[test]
     fn test_remove_range_invalid() {
         // Arrange
@@ -2337,6 +4692,142 @@ mod tests {
             ))
         );
     }
+
+    #[test]
+    fn test_search_bytes_iter_valid() {
+        // Arrange: gaps of 2 and 0x10 bytes, a pattern of fill bytes fits only in the second
+        let mut ih = IntelHex::new();
+        ih.buffer.insert(0x1000, vec![0x12, 0xFF, 0x12, 0xFF]);
+        ih.buffer.insert(0x1006, vec![0x12, 0xFF, 0xFF]);
+        ih.buffer.insert(0x1019, vec![0xFF, 0x12]);
+        let gaps = SearchOptions {
+            gap_fill: Some(0xFF),
+            ..SearchOptions::default()
+        };
+        let window = SearchOptions {
+            range: Some((0x1001, 0x1008)),
+            ..SearchOptions::default()
+        };
+        let limited = SearchOptions {
+            max_results: Some(1),
+            ..gaps
+        };
+        let fill = [0xFF; 0x10];
+
+        // Act
+        let plain: Vec<usize> = ih
+            .search_bytes_iter(&[0xFF, 0x12], &SearchOptions::default())
+            .collect();
+        let across_gaps: Vec<usize> = ih.search_bytes_iter(&[0xFF, 0xFF, 0x12], &gaps).collect();
+        let in_window: Vec<usize> = ih.search_bytes_iter(&[0xFF, 0x12], &window).collect();
+        let first: Vec<usize> = ih.search_bytes_iter(&[0xFF, 0x12], &limited).collect();
+        let in_gaps: Vec<usize> = ih.search_bytes_iter(&fill, &gaps).collect();
+        let empty = ih.search_bytes_iter(&[], &gaps).next();
+
+        // Assert
+        assert_eq!(plain, ih.search_bytes(&[0xFF, 0x12]));
+        assert_eq!(plain, vec![0x1001, 0x1019]);
+        assert_eq!(across_gaps, vec![0x1004, 0x1018]);
+        assert_eq!(in_window, vec![0x1001]);
+        assert_eq!(first, vec![0x1001]);
+        assert_eq!(in_gaps, vec![0x1007, 0x1008, 0x1009, 0x100A]);
+        assert_eq!(empty, None);
+    }
+
+    #[test]
+    fn test_search_hamming_options_valid() {
+        // Arrange
+        let mut ih = IntelHex::new();
+        ih.buffer
+            .insert(0x1000, vec![0x12, 0x34, 0x12, 0x35, 0x10, 0x34]);
+        ih.buffer.insert(0x1010, vec![0x12, 0x34]);
+        let window = SearchOptions {
+            range: Some((0x1001, 0x1010)),
+            ..SearchOptions::default()
+        };
+        let limited = SearchOptions {
+            max_results: Some(2),
+            ..SearchOptions::default()
+        };
+
+        // Act
+        let all = ih.search_hamming(&[0x12, 0x34], 1, &SearchOptions::default());
+        let in_window = ih.search_hamming(&[0x12, 0x34], 1, &window);
+        let first = ih.search_hamming(&[0x12, 0x34], 1, &limited);
+        let every = ih.search_hamming(&[0x12, 0x34], 2, &window);
+
+        // Assert - a match at the window end would stick out of it
+        assert_eq!(
+            all,
+            vec![(0x1000, 0), (0x1002, 1), (0x1004, 1), (0x1010, 0)]
+        );
+        assert_eq!(in_window, vec![(0x1002, 1), (0x1004, 1)]);
+        assert_eq!(first, vec![(0x1000, 0), (0x1002, 1)]);
+        assert_eq!(every.len(), 4);
+    }
+
+    #[test]
+    fn test_search_masked_and_value_options_valid() {
+        // Arrange
+        let mut ih = IntelHex::new();
+        ih.buffer
+            .insert(0x1000, vec![0x34, 0x12, 0x00, 0x00, 0x34, 0x12, 0x34, 0x12]);
+        let pattern: MaskedPattern = "34 1?".parse().unwrap_or_else(|_| unreachable!());
+        let value: NumericValue = "0x1234".parse().unwrap_or_else(|_| unreachable!());
+        let window = SearchOptions {
+            range: Some((0x1001, 0x1006)),
+            ..SearchOptions::default()
+        };
+        let limited = SearchOptions {
+            max_results: Some(1),
+            ..SearchOptions::default()
+        };
+
+        // Act
+        let masked = ih.search_masked(&pattern, &window);
+        let masked_first = ih.search_masked(&pattern, &limited);
+        let values: Vec<usize> = ih
+            .search_value(&value, &window)
+            .iter()
+            .map(|(addr, _)| *addr)
+            .collect();
+        let values_first = ih.search_value(&value, &limited);
+
+        // Assert - the u32 at 0x1000 and the u16 at 0x1006 stick out of the window, the u16 BE
+        // at 0x1005 does not
+        assert_eq!(masked, vec![0x1004]);
+        assert_eq!(masked_first, vec![0x1000]);
+        assert_eq!(values, vec![0x1004, 0x1005]);
+        assert_eq!(values_first.len(), 1);
+        assert_eq!(values_first[0].0, 0x1000);
+    }
+
+    #[test]
+    fn test_entropy_and_histogram_valid() {
+        // Arrange
+        let mut ih = IntelHex::new();
+        ih.buffer.insert(0x0F8, vec![0xFF; 0x10]);
+        ih.buffer.insert(0x200, (0..=255).collect());
+        ih.size = 0x110;
+
+        // Act
+        let blocks = ih.entropy(0x100).unwrap_or_default();
+        let histogram = ih.histogram(0x100..=0x27F);
+        let invalid = ih.entropy(0);
+
+        // Assert - the erased bytes span the border of the first two blocks
+        assert_eq!(blocks, vec![(0x000, 0.0), (0x100, 0.0), (0x200, 8.0)]);
+        assert_eq!(histogram[0xFF], 0x8);
+        assert_eq!(histogram[0x7F], 1);
+        assert_eq!(histogram[0x80], 0);
+        assert_eq!(histogram.iter().sum::<usize>(), 0x88);
+        assert_eq!(
+            invalid.err(),
+            Some(IntelHexError::AnalysisError(
+                IntelHexErrorKind::InvalidBlockSize(0)
+            ))
+        );
+    }
 }
 
 // =====================  BENCH ACCESS FOR PRIVATE FUNCTIONS  =====================
@@ -2347,4 +4838,14 @@ impl IntelHex {
     pub fn bench_priv_parse(ih: &mut Self, raw_bytes: &[u8]) {
         let _ = ih.parse(raw_bytes);
     }
+
+    #[cfg(feature = "benchmarking")]
+    pub fn bench_priv_write_hex_records<W: Write>(ih: &Self, writer: &mut W) {
+        let _ = ih.write_hex_records(writer);
+    }
+
+    #[cfg(feature = "benchmarking")]
+    pub fn bench_priv_parse_with_threads(ih: &mut Self, raw_bytes: &[u8], threads: usize) {
+        let _ = ih.parse_with_threads(raw_bytes, threads);
+    }
 }
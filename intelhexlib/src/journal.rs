@@ -0,0 +1,285 @@
+//! The `journal` module defines the undo/redo [`Journal`] that an [`IntelHex`](crate::IntelHex)
+//! instance can optionally keep to record its mutations as invertible steps.
+//!
+//! Journaling is opt-in via [`IntelHex::enable_journal`](crate::IntelHex::enable_journal).
+//! Every mutation (byte and range updates, inserting / removing ranges, relocation, merging)
+//! is recorded as a transaction that can be undone and redone. Several mutations can be
+//! grouped into a single transaction via
+//! [`IntelHex::begin_transaction`](crate::IntelHex::begin_transaction).
+
+use crate::buffer::ChunkData;
+use crate::provenance::{Provenance, TagsDiff};
+use crate::snapshot::Snapshot;
+use std::sync::Arc;
+
+/// Bytes (start address, data) overwritten in place
+pub type Bytes = Vec<(usize, Vec<u8>)>;
+
+/// Whole chunks (start address, data) shared with the buffer
+pub type SharedChunks = Vec<(usize, Arc<ChunkData>)>;
+
+/// How the data touched by a mutation is recorded
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Recording {
+    /// Copies of the touched bytes, for edits that keep the layout of the chunks
+    Bytes,
+    /// The whole chunks around the touched windows, for edits that insert, remove or join chunks
+    Chunks,
+}
+
+/// Single invertible change of an `IntelHex` instance.
+#[derive(Debug, Clone)]
+pub enum Change {
+    /// Bytes were overwritten in place, the layout of the chunks did not change
+    Bytes { before: Bytes, after: Bytes },
+    /// Chunks were inserted, removed, trimmed or joined. The chunks around the change are kept
+    /// whole and shared with the buffer, so recording it does not copy their data.
+    Chunks {
+        before: SharedChunks,
+        after: SharedChunks,
+    },
+    /// All data was shifted by the offset (relocation)
+    Shift(i64),
+    /// Start address record was replaced
+    StartAddr {
+        before: Option<[u8; 18]>,
+        after: Option<[u8; 18]>,
+    },
+    /// All data was replaced by a snapshot
+    Restore {
+        before: Box<Snapshot>,
+        after: Box<Snapshot>,
+    },
+    /// Source tags were replaced as a whole (tracking enabled or sources re-tagged)
+    Provenance {
+        before: Option<Provenance>,
+        after: Option<Provenance>,
+    },
+    /// Source tags changed within the address windows of a mutation
+    Tags(TagsDiff),
+}
+
+/// Group of changes that are undone and redone together.
+#[derive(Debug, Clone)]
+pub struct Transaction {
+    pub label: String,
+    pub changes: Vec<Change>,
+}
+
+/// Undo/redo history of an `IntelHex` instance.
+#[derive(Debug, Clone)]
+pub struct Journal {
+    /// Committed transactions, the most recent one last
+    undo: Vec<Transaction>,
+    /// Undone transactions, the most recently undone one last
+    redo: Vec<Transaction>,
+    /// Transaction that is being recorded (see `IntelHex::begin_transaction`)
+    open: Option<Transaction>,
+    /// Nesting depth of `begin_transaction` calls
+    depth: usize,
+    /// Maximum number of transactions kept for undo
+    limit: usize,
+    /// Number of transactions recorded so far
+    recorded: u64,
+}
+
+impl Default for Journal {
+    fn default() -> Self {
+        Self {
+            undo: Vec::new(),
+            redo: Vec::new(),
+            open: None,
+            depth: 0,
+            limit: Self::DEFAULT_LIMIT,
+            recorded: 0,
+        }
+    }
+}
+
+impl Journal {
+    /// Default maximum number of transactions kept for undo
+    pub const DEFAULT_LIMIT: usize = 256;
+
+    /// Returns `true` if there is a transaction to undo.
+    #[must_use]
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty() || self.open.as_ref().is_some_and(|t| !t.changes.is_empty())
+    }
+
+    /// Returns `true` if there is a transaction to redo.
+    #[must_use]
+    pub const fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    /// Label of the transaction that would be undone next, e.g. `Relocate`.
+    #[must_use]
+    pub fn undo_label(&self) -> Option<&str> {
+        self.open
+            .as_ref()
+            .filter(|t| !t.changes.is_empty())
+            .or_else(|| self.undo.last())
+            .map(|t| t.label.as_str())
+    }
+
+    /// Label of the transaction that would be redone next.
+    #[must_use]
+    pub fn redo_label(&self) -> Option<&str> {
+        self.redo.last().map(|t| t.label.as_str())
+    }
+
+    /// Number of transactions recorded so far. Compare it before and after a mutation
+    /// to tell whether the mutation added a new undo step.
+    #[must_use]
+    pub const fn recorded(&self) -> u64 {
+        self.recorded
+    }
+
+    /// Set the maximum number of transactions kept for undo. Oldest ones are dropped first.
+    pub fn set_limit(&mut self, limit: usize) {
+        self.limit = limit;
+        self.trim();
+    }
+
+    /// Drop all recorded transactions.
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+        self.open = None;
+        self.depth = 0;
+    }
+
+    /// Start grouping changes into one transaction. Calls can be nested,
+    /// only the outermost label is used.
+    pub(crate) fn begin(&mut self, label: &str) {
+        if self.depth == 0 {
+            self.open = Some(Transaction {
+                label: label.to_string(),
+                changes: Vec::new(),
+            });
+        }
+        self.depth += 1;
+    }
+
+    /// Finish the transaction started by `begin`.
+    pub(crate) fn commit(&mut self) {
+        self.depth = self.depth.saturating_sub(1);
+        if self.depth == 0 {
+            self.close();
+        }
+    }
+
+    /// Close the open transaction (regardless of nesting) and push it to the undo stack.
+    pub(crate) fn close(&mut self) {
+        self.depth = 0;
+        if let Some(transaction) = self.open.take()
+            && !transaction.changes.is_empty()
+        {
+            self.undo.push(transaction);
+            self.recorded += 1;
+            self.trim();
+        }
+    }
+
+    /// Record changes of a single mutation. Starts a new transaction unless one is open.
+    pub(crate) fn record(&mut self, label: &str, changes: Vec<Change>) {
+        if changes.is_empty() {
+            return;
+        }
+
+        // Any new change invalidates the redo history
+        self.redo.clear();
+
+        if let Some(transaction) = self.open.as_mut() {
+            transaction.changes.extend(changes);
+        } else {
+            self.undo.push(Transaction {
+                label: label.to_string(),
+                changes,
+            });
+            self.recorded += 1;
+            self.trim();
+        }
+    }
+
+    pub(crate) fn pop_undo(&mut self) -> Option<Transaction> {
+        self.close();
+        self.undo.pop()
+    }
+
+    pub(crate) fn pop_redo(&mut self) -> Option<Transaction> {
+        self.close();
+        self.redo.pop()
+    }
+
+    pub(crate) fn push_undo(&mut self, transaction: Transaction) {
+        self.undo.push(transaction);
+    }
+
+    pub(crate) fn push_redo(&mut self, transaction: Transaction) {
+        self.redo.push(transaction);
+    }
+
+    fn trim(&mut self) {
+        if self.undo.len() > self.limit {
+            let excess = self.undo.len() - self.limit;
+            self.undo.drain(..excess);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn shift(offset: i64) -> Vec<Change> {
+        vec![Change::Shift(offset)]
+    }
+
+    #[test]
+    fn test_record_groups_changes_in_open_transaction() {
+        // Arrange
+        let mut journal = Journal::default();
+
+        // Act
+        journal.begin("Merge");
+        journal.record("Relocate", shift(1));
+        journal.begin("Nested");
+        journal.record("Relocate", shift(2));
+        journal.commit();
+        journal.commit();
+        journal.record("Relocate", shift(3));
+
+        // Assert
+        assert_eq!(journal.undo.len(), 2);
+        assert_eq!(journal.undo[0].label, "Merge");
+        assert_eq!(journal.undo[0].changes.len(), 2);
+        assert_eq!(journal.undo_label(), Some("Relocate"));
+        assert_eq!(journal.recorded(), 2);
+    }
+
+    #[test]
+    fn test_record_clears_redo_and_trims_to_limit() {
+        // Arrange
+        let mut journal = Journal::default();
+        journal.set_limit(2);
+        journal.record("A", shift(1));
+        journal.record("B", shift(1));
+        let undone = journal.pop_undo();
+        journal.push_redo(undone.unwrap_or_else(|| Transaction {
+            label: String::new(),
+            changes: Vec::new(),
+        }));
+
+        // Act
+        let could_redo = journal.can_redo();
+        journal.record("C", shift(1));
+        journal.record("D", shift(1));
+
+        // Assert
+        assert!(could_redo);
+        assert!(!journal.can_redo());
+        let labels: Vec<&str> = journal.undo.iter().map(|t| t.label.as_str()).collect();
+        assert_eq!(labels, vec!["C", "D"]);
+    }
+}
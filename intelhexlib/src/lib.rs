@@ -6,6 +6,27 @@
 //! - Parser for Intel HEX files (via [`IntelHex`] struct).
 //! - Error handling with [`IntelHexError`].
 //! - Easy access to hex data for its reading and modification.
+//! - Optional tracking of the source of every byte in merged images (via [`Provenance`]).
+//! - Splitting images into named sub-images, e.g. per region of a [`MemoryMap`].
+//! - Optional undo/redo of all mutations grouped into transactions (via [`Journal`]).
+//! - Cheap copies of images: data chunks are shared between clones and [`Snapshot`]s
+//!   and only copied on write.
+//! - Extraction of ASCII, UTF-8 and UTF-16 strings (via [`IntelHex::strings`]).
+//! - Per-block Shannon entropy and byte histograms (via [`IntelHex::entropy`] and
+//!   [`IntelHex::histogram`]).
+//! - Scanning for embedded files (gzip, PNG, ELF, ...) by their signatures (via
+//!   [`IntelHex::scan_signatures`]).
+//! - Detection and decoding of ARM Cortex-M vector tables (via [`IntelHex::vector_tables`]).
+//! - Symbols with sizes from GNU ld, IAR and Keil map files and ELF symbol tables (via
+//!   [`SymbolTable`]).
+//! - Cross-references: pointers into the image and who points at an address (via
+//!   [`IntelHex::references`]).
+//! - Structure templates: C struct declarations decoded at an address (via [`Template`]
+//!   and [`IntelHex::decode_template`]).
+//! - Crash-safe file writes: files are written to a temporary file and renamed over the
+//!   destination (via [`write_file_atomic`]).
+//! - Memory-mapped loading of large binaries with byte edits kept as an in-memory overlay
+//!   of patched pages (via [`IntelHex::load_bin_mapped`]).
 //!
 //! ## Example
 //!
@@ -16,12 +37,42 @@
 //! ih.write_hex("build/ex1/ih.hex");
 //! ```
 
+mod atomic;
+mod buffer;
+mod bytes;
 mod error;
 mod intelhex;
+mod journal;
+mod memory_map;
+mod parse;
+mod provenance;
 mod record;
 mod search;
+mod signatures;
+mod snapshot;
+mod stats;
+mod strings;
+mod symbols;
+mod templates;
+mod transform;
+mod vectors;
+mod xrefs;
 
 // Public APIs
+pub use atomic::write_file_atomic;
+pub use buffer::{Chunks, IntoChunks};
 pub use error::{IntelHexError, IntelHexErrorKind};
-pub use intelhex::{IntelHex, IterRange};
+pub use intelhex::{IntelHex, IterRange, SearchIter};
+pub use journal::Journal;
+pub use memory_map::{MemoryMap, MemoryRegion};
+pub use provenance::{Provenance, SourceRange};
 pub use record::RecordType;
+pub use search::{MaskedPattern, NumericValue, SearchOptions, ValueEncoding, ValueKind};
+pub use signatures::{Signature, SignatureHit, SignatureInfo, SignatureTable, Validator};
+pub use snapshot::Snapshot;
+pub use strings::StringEncoding;
+pub use symbols::{Symbol, SymbolTable};
+pub use templates::{FieldKind, FieldValue, ScalarType, Template, TemplateField};
+pub use transform::Transform;
+pub use vectors::{VectorTable, exception_name};
+pub use xrefs::{POINTER_ENCODINGS, Reference, ReferenceIndex};
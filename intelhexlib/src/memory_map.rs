@@ -0,0 +1,158 @@
+//! The `memory_map` module defines [`MemoryMap`] - a set of named, non-overlapping address
+//! regions of a target device (e.g. flash banks, bootloader or configuration areas).
+//! An [`IntelHex`](crate::IntelHex) instance can be split into sub-images per region via
+//! [`IntelHex::split_by_region`](crate::IntelHex::split_by_region).
+
+use crate::error::{IntelHexError, IntelHexErrorKind};
+
+/// Named address region of a memory map.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MemoryRegion {
+    /// Name of the region, e.g. `bootloader` or `bank1`
+    pub name: String,
+    /// First address of the region
+    pub start: usize,
+    /// Last address of the region (inclusive)
+    pub end: usize,
+}
+
+impl MemoryRegion {
+    /// Check if the address is within the region
+    #[must_use]
+    pub const fn contains(&self, address: usize) -> bool {
+        self.start <= address && address <= self.end
+    }
+}
+
+/// Set of named, non-overlapping address regions of a target device (see
+/// [`add_region`](Self::add_region)), used to split an image per region.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MemoryMap {
+    /// Regions sorted by their start address
+    regions: Vec<MemoryRegion>,
+}
+
+impl MemoryMap {
+    /// Creates empty `MemoryMap` instance.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            regions: Vec::new(),
+        }
+    }
+
+    /// Add a region with the inclusive address range `[start, end]`.
+    ///
+    /// # Errors
+    /// Returns an error if `start > end` or the region overlaps with an existing one.
+    ///
+    /// # Example
+    /// ```
+    /// use intelhexlib::MemoryMap;
+    ///
+    /// let mut map = MemoryMap::new();
+    /// map.add_region("bootloader", 0x0800_0000, 0x0800_3FFF).unwrap();
+    /// map.add_region("app", 0x0800_4000, 0x0803_FFFF).unwrap();
+    ///
+    /// assert!(map.add_region("config", 0x0803_F000, 0x0803_FFFF).is_err());
+    /// assert_eq!(map.region_at(0x0800_4000).unwrap().name, "app");
+    /// ```
+    pub fn add_region(
+        &mut self,
+        name: &str,
+        start: usize,
+        end: usize,
+    ) -> Result<(), IntelHexError> {
+        if start > end {
+            return Err(IntelHexError::UpdateError(
+                IntelHexErrorKind::InvalidAddress(start),
+            ));
+        }
+
+        if let Some(region) = self
+            .regions
+            .iter()
+            .find(|r| r.start <= end && start <= r.end)
+        {
+            return Err(IntelHexError::UpdateError(
+                IntelHexErrorKind::MemoryRegionOverlap(start.max(region.start)),
+            ));
+        }
+
+        let pos = self.regions.partition_point(|r| r.start < start);
+        self.regions.insert(
+            pos,
+            MemoryRegion {
+                name: name.to_string(),
+                start,
+                end,
+            },
+        );
+        Ok(())
+    }
+
+    /// All regions sorted by their start address.
+    #[must_use]
+    pub fn regions(&self) -> &[MemoryRegion] {
+        &self.regions
+    }
+
+    /// Region that contains the address. Returns `None` if the address is not mapped.
+    #[must_use]
+    pub fn region_at(&self, address: usize) -> Option<&MemoryRegion> {
+        let pos = self.regions.partition_point(|r| r.start <= address);
+        pos.checked_sub(1)
+            .map(|i| &self.regions[i])
+            .filter(|r| r.contains(address))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_add_region_keeps_regions_sorted() {
+        // Arrange
+        let mut map = MemoryMap::new();
+
+        // Act
+        let res1 = map.add_region("bank1", 0x1_0000, 0x1_FFFF);
+        let res2 = map.add_region("bank0", 0x0, 0xFFFF);
+        let res3 = map.add_region("overlap", 0xFF00, 0x1_00FF);
+        let res4 = map.add_region("reversed", 0x3_0000, 0x2_0000);
+
+        // Assert
+        assert!(res1.is_ok() && res2.is_ok());
+        assert_eq!(
+            res3,
+            Err(IntelHexError::UpdateError(
+                IntelHexErrorKind::MemoryRegionOverlap(0xFF00)
+            ))
+        );
+        assert_eq!(
+            res4,
+            Err(IntelHexError::UpdateError(
+                IntelHexErrorKind::InvalidAddress(0x3_0000)
+            ))
+        );
+        let names: Vec<&str> = map.regions().iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(names, vec!["bank0", "bank1"]);
+    }
+
+    #[test]
+    fn test_region_at() {
+        // Arrange
+        let mut map = MemoryMap::new();
+        let _ = map.add_region("boot", 0x100, 0x1FF);
+        let _ = map.add_region("app", 0x400, 0x7FF);
+
+        // Act & Assert
+        assert_eq!(map.region_at(0xFF), None);
+        assert_eq!(map.region_at(0x100).map(|r| r.name.as_str()), Some("boot"));
+        assert_eq!(map.region_at(0x1FF).map(|r| r.name.as_str()), Some("boot"));
+        assert_eq!(map.region_at(0x200), None);
+        assert_eq!(map.region_at(0x7FF).map(|r| r.name.as_str()), Some("app"));
+        assert_eq!(map.region_at(0x800), None);
+    }
+}
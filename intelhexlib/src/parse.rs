@@ -0,0 +1,218 @@
+//! The `parse` module implements the parallel part of Intel HEX parsing.
+//!
+//! Large inputs are split at line boundaries into parts that are decoded concurrently.
+//! Decoding validates every record and joins contiguous data records into runs, but does
+//! not know the ELA/ESA offsets in effect. These, together with the overlap and start address
+//! checks, are resolved by [`IntelHex`](crate::IntelHex) in a cheap sequential pass over the
+//! decoded parts, so the result (and any error with its line number) is identical to parsing
+//! the input line by line.
+
+use crate::error::IntelHexErrorKind;
+use crate::record::{DecodeBuffer, Record, RecordType};
+use std::ops::Range;
+
+/// Inputs are split into parts of at least this size (smaller inputs are parsed on one thread)
+pub const MIN_BYTES_PER_THREAD: usize = 256 * 1024;
+
+/// Number of threads to parse an input of `len` bytes with.
+pub fn thread_count(len: usize) -> usize {
+    let available = std::thread::available_parallelism().map_or(1, std::num::NonZero::get);
+    available.min(len / MIN_BYTES_PER_THREAD).max(1)
+}
+
+/// Decoded record (or run of contiguous data records) of a part of the input.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Entry {
+    /// Data records following each other on consecutive lines with contiguous addresses
+    Data {
+        /// Address of the first record (without the ELA/ESA offset)
+        address: u16,
+        /// Index of the first record within the part (1-based)
+        first: usize,
+        /// Payload of all records, range in `DecodedPart::data`
+        data: Range<usize>,
+        /// Payload length of every record, range in `DecodedPart::lens`
+        lens: Range<usize>,
+    },
+    /// Extended segment / linear address record with the resulting address offset
+    Offset(usize),
+    /// Start segment / linear address record with the raw record (without ':')
+    StartAddr {
+        /// Index of the record within the part (1-based)
+        index: usize,
+        raw: Option<[u8; 18]>,
+    },
+}
+
+/// Records decoded from a part of the input.
+#[derive(Debug, Default)]
+pub struct DecodedPart {
+    pub entries: Vec<Entry>,
+    /// Payload of all data records
+    pub data: Vec<u8>,
+    /// Payload length of every data record
+    pub lens: Vec<u8>,
+    /// Number of records (non-empty lines) in the part, up to and including a failing one
+    pub records: usize,
+    /// First invalid record of the part and its index within the part (1-based)
+    pub error: Option<(IntelHexErrorKind, usize)>,
+}
+
+/// Split the input into (at most) `parts` parts of similar size at line boundaries.
+pub fn split_lines(raw_bytes: &[u8], parts: usize) -> Vec<&[u8]> {
+    let mut result = Vec::with_capacity(parts);
+    let mut rest = raw_bytes;
+
+    for remaining in (2..=parts).rev() {
+        let target = rest.len() / remaining;
+        let cut = rest[target..]
+            .iter()
+            .position(|&b| b == b'\n')
+            .map_or(rest.len(), |pos| target + pos + 1);
+
+        let (head, tail) = rest.split_at(cut);
+        result.push(head);
+        rest = tail;
+    }
+    result.push(rest);
+
+    result
+}
+
+/// Decode all records of a part of the input. Stops at the first invalid record.
+pub fn decode_part(raw_bytes: &[u8]) -> DecodedPart {
+    let mut part = DecodedPart {
+        data: Vec::with_capacity(raw_bytes.len() / 2),
+        ..DecodedPart::default()
+    };
+    let mut buf: DecodeBuffer = [0u8; std::mem::size_of::<DecodeBuffer>()];
+
+    // End address (without offset) of the data run that can still be extended
+    let mut run_end: Option<usize> = None;
+
+    for line in raw_bytes.split(|&b| b == b'\n') {
+        let line = line.strip_suffix(b"\r").unwrap_or(line);
+
+        if line.is_empty() {
+            continue;
+        }
+
+        part.records += 1;
+        let index = part.records;
+
+        let (rtype, address, data) = match Record::decode(line, &mut buf) {
+            Ok(decoded) => decoded,
+            Err(err) => {
+                part.error = Some((err, index));
+                break;
+            }
+        };
+
+        match rtype {
+            RecordType::Data if !data.is_empty() => {
+                let start = part.data.len();
+                part.data.extend_from_slice(data);
+                #[allow(clippy::cast_possible_truncation)] // record length is a u8
+                part.lens.push(data.len() as u8);
+
+                // Extend the current run or start a new one
+                let extends_run = run_end == Some(address as usize);
+                if extends_run && let Some(Entry::Data { data, lens, .. }) = part.entries.last_mut()
+                {
+                    data.end = part.data.len();
+                    lens.end = part.lens.len();
+                } else {
+                    part.entries.push(Entry::Data {
+                        address,
+                        first: index,
+                        data: start..part.data.len(),
+                        lens: part.lens.len() - 1..part.lens.len(),
+                    });
+                }
+                run_end = Some(address as usize + data.len());
+                continue;
+            }
+            RecordType::Data | RecordType::EndOfFile => {}
+            RecordType::ExtendedSegmentAddress => {
+                part.entries.push(Entry::Offset(
+                    (data[0] as usize * 256 + data[1] as usize) * 16,
+                ));
+            }
+            RecordType::ExtendedLinearAddress => {
+                part.entries.push(Entry::Offset(
+                    (data[0] as usize * 256 + data[1] as usize) * 65536,
+                ));
+            }
+            RecordType::StartSegmentAddress | RecordType::StartLinearAddress => {
+                part.entries.push(Entry::StartAddr {
+                    index,
+                    raw: line.get(1..19).and_then(|raw| raw.try_into().ok()),
+                });
+            }
+        }
+
+        // Records of a run must be on consecutive lines
+        run_end = None;
+    }
+
+    part
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_lines() {
+        // Arrange
+        let raw = b":00000001FF\n:00000001FF\r\n\n:00000001FF";
+
+        // Act
+        let parts = split_lines(raw, 3);
+        let too_many = split_lines(raw, 10);
+
+        // Assert
+        assert_eq!(parts.concat(), raw);
+        assert!(
+            parts
+                .iter()
+                .all(|p| p.is_empty() || p.ends_with(b"\n") || raw.ends_with(p))
+        );
+        assert_eq!(too_many.concat(), raw);
+        assert_eq!(too_many.len(), 10);
+    }
+
+    #[test]
+    fn test_decode_part_joins_contiguous_records() {
+        // Arrange: two contiguous records, a gap, then an invalid record
+        let raw = b":020000000102FB\n:010002000DF0\n:0100100003EC\n:00000001FE\n";
+
+        // Act
+        let part = decode_part(raw);
+
+        // Assert
+        assert_eq!(part.records, 4);
+        assert_eq!(part.data, vec![0x01, 0x02, 0x0D, 0x03]);
+        assert_eq!(
+            part.entries,
+            vec![
+                Entry::Data {
+                    address: 0x0000,
+                    first: 1,
+                    data: 0..3,
+                    lens: 0..2,
+                },
+                Entry::Data {
+                    address: 0x0010,
+                    first: 3,
+                    data: 3..4,
+                    lens: 2..3,
+                },
+            ]
+        );
+        assert_eq!(
+            part.error,
+            Some((IntelHexErrorKind::RecordChecksumMismatch(0xFF, 0xFE), 4))
+        );
+    }
+}
@@ -0,0 +1,443 @@
+//! The `provenance` module defines the [`Provenance`] table that an [`IntelHex`](crate::IntelHex)
+//! instance can optionally keep to remember which input (source) every data byte came from.
+//!
+//! Tracking is opt-in via [`IntelHex::tag_source`](crate::IntelHex::tag_source). Once enabled,
+//! source tags are carried through merging, relocation and range edits.
+
+use std::collections::BTreeMap;
+
+/// Contiguous address range that originates from a single source.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SourceRange {
+    /// First address of the range
+    pub start: usize,
+    /// Last address of the range (inclusive)
+    pub end: usize,
+    /// Index of the source in [`Provenance::names`]
+    pub source: usize,
+}
+
+/// Table of source tags: maps address ranges to the source (input file) they came from.
+///
+/// Sources are identified by their id, not by name, so two different inputs with the
+/// same file name stay separate entries. Bytes written by edits are tagged with a source
+/// named [`Provenance::EDITED`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Provenance {
+    /// Names of the sources (usually file names). Index of the name is the source id.
+    names: Vec<String>,
+    /// Id of the source of edited bytes, registered on the first edit
+    edited: Option<usize>,
+    /// Maps start address of each tagged range to its (exclusive) end address and source id.
+    /// Ranges never overlap and adjacent ranges of the same source are merged.
+    ranges: BTreeMap<usize, (usize, usize)>,
+}
+
+impl Provenance {
+    /// Name of the source that edited bytes are tagged with.
+    pub const EDITED: &'static str = "edited";
+
+    /// Names of all known sources. Index of the name is the source id.
+    #[must_use]
+    pub fn names(&self) -> &[String] {
+        &self.names
+    }
+
+    /// Id of the source that the byte at `address` came from.
+    /// Returns `None` if the address is not tagged (gap or data without a source).
+    #[must_use]
+    pub fn source_at(&self, address: usize) -> Option<usize> {
+        self.ranges
+            .range(..=address)
+            .next_back()
+            .filter(|&(_, &(end, _))| address < end)
+            .map(|(_, &(_, id))| id)
+    }
+
+    /// Name of the source that the byte at `address` came from.
+    #[must_use]
+    pub fn source_name_at(&self, address: usize) -> Option<&str> {
+        self.source_at(address)
+            .and_then(|id| self.names.get(id))
+            .map(String::as_str)
+    }
+
+    /// Iterator over all tagged ranges sorted by address.
+    pub fn ranges(&self) -> impl Iterator<Item = SourceRange> + '_ {
+        self.ranges
+            .iter()
+            .map(|(&start, &(end, source))| SourceRange {
+                start,
+                end: end - 1,
+                source,
+            })
+    }
+
+    /// Number of tagged bytes per source. Index of the vector is the source id.
+    #[must_use]
+    pub fn sizes(&self) -> Vec<usize> {
+        let mut sizes = vec![0; self.names.len()];
+        for (&start, &(end, id)) in &self.ranges {
+            sizes[id] += end - start;
+        }
+        sizes
+    }
+
+    /// Register a new source with the given name and get its id.
+    pub(crate) fn add_source(&mut self, name: &str) -> usize {
+        self.names.push(name.to_string());
+        self.names.len() - 1
+    }
+
+    /// Tag the range `[start, end)` as edited.
+    pub(crate) fn tag_edit(&mut self, start: usize, end: usize) {
+        let id = self.edited_id();
+        self.tag(start, end, id);
+    }
+
+    /// Id of the source of edited bytes. Registers the source on first use.
+    fn edited_id(&mut self) -> usize {
+        if let Some(id) = self.edited {
+            return id;
+        }
+        let id = self.add_source(Self::EDITED);
+        self.edited = Some(id);
+        id
+    }
+
+    /// Register the source `id` of `other` in this table and get its new id.
+    /// Edited bytes of both tables share the same source.
+    fn import_source(&mut self, other: &Self, id: usize) -> usize {
+        if other.edited == Some(id) {
+            return self.edited_id();
+        }
+        self.add_source(&other.names[id])
+    }
+
+    /// Tag the range `[start, end)` with the source `id`, overwriting existing tags.
+    pub(crate) fn tag(&mut self, start: usize, end: usize, id: usize) {
+        if start >= end {
+            return;
+        }
+
+        self.clear_range(start, end);
+
+        let mut new_start = start;
+        let mut new_end = end;
+
+        // Merge with the left neighbor if it has the same source and touches the new range
+        if let Some((&prev_start, &(prev_end, prev_id))) = self.ranges.range(..start).next_back()
+            && prev_end == start
+            && prev_id == id
+        {
+            self.ranges.remove(&prev_start);
+            new_start = prev_start;
+        }
+
+        // Merge with the right neighbor if it has the same source and touches the new range
+        if let Some(&(next_end, next_id)) = self.ranges.get(&end)
+            && next_id == id
+        {
+            self.ranges.remove(&end);
+            new_end = next_end;
+        }
+
+        self.ranges.insert(new_start, (new_end, id));
+    }
+
+    /// Remove tags in the range `[start, end)`. Tagged ranges are trimmed or split as needed.
+    pub(crate) fn clear_range(&mut self, start: usize, end: usize) {
+        // Ranges are sorted and never overlap, so walk back from `end` until the first
+        // range that ends before `start`
+        let affected: Vec<(usize, (usize, usize))> = self
+            .ranges
+            .range(..end)
+            .rev()
+            .take_while(|&(_, &(r_end, _))| r_end > start)
+            .map(|(&s, &v)| (s, v))
+            .collect();
+
+        for (r_start, (r_end, id)) in affected {
+            self.ranges.remove(&r_start);
+
+            // Left fragment: portion before the cleared range
+            if r_start < start {
+                self.ranges.insert(r_start, (start, id));
+            }
+
+            // Right fragment: portion after the cleared range
+            if r_end > end {
+                self.ranges.insert(end, (r_end, id));
+            }
+        }
+    }
+
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_possible_wrap,
+        clippy::cast_sign_loss
+    )]
+    /// Shift all tagged ranges by `offset`. Used by relocation.
+    pub(crate) fn shift(&mut self, offset: i64) {
+        self.ranges = std::mem::take(&mut self.ranges)
+            .into_iter()
+            .map(|(start, (end, id))| {
+                (
+                    (start as i64 + offset) as usize,
+                    ((end as i64 + offset) as usize, id),
+                )
+            })
+            .collect();
+    }
+
+    /// Copy tags of `other` within `[start, end)` into this table. Sources of `other` are
+    /// registered as new sources when first used and `ids` maps ids of `other` to ids of
+    /// this table, so that copying several ranges of one instance registers each source once.
+    pub(crate) fn copy_from(
+        &mut self,
+        other: &Self,
+        start: usize,
+        end: usize,
+        ids: &mut Vec<Option<usize>>,
+    ) {
+        ids.resize(other.names.len(), None);
+
+        for (r_start, r_end, other_id) in other.tags_in(start, end) {
+            let id = *ids[other_id].get_or_insert_with(|| self.import_source(other, other_id));
+            self.tag(r_start, r_end, id);
+        }
+    }
+
+    /// Tags within `[start, end)`, clipped to the range.
+    fn tags_in(&self, start: usize, end: usize) -> Vec<Tag> {
+        if start >= end {
+            return Vec::new();
+        }
+
+        // Only the range right before `start` can overlap it from the left
+        let first = self
+            .ranges
+            .range(..=start)
+            .next_back()
+            .filter(|&(_, &(r_end, _))| r_end > start)
+            .map_or(start, |(&r_start, _)| r_start);
+
+        self.ranges
+            .range(first..end)
+            .map(|(&r_start, &(r_end, id))| (r_start.max(start), r_end.min(end), id))
+            .collect()
+    }
+}
+
+/// Tagged range (start, exclusive end, source id)
+type Tag = (usize, usize, usize);
+
+/// Change of source tags within address windows, recorded by the journal instead of a copy
+/// of the whole table. Only sources registered by the change are stored.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TagsDiff {
+    /// Address windows `[start, end)` the change is limited to
+    windows: Vec<(usize, usize)>,
+    /// Tags within each window before the change
+    before: Vec<Vec<Tag>>,
+    /// Tags within each window after the change
+    after: Vec<Vec<Tag>>,
+    /// Number of sources before the change
+    sources: usize,
+    /// Sources registered by the change
+    added: Vec<String>,
+    /// Id of the source of edited bytes before and after the change
+    edited: (Option<usize>, Option<usize>),
+}
+
+impl TagsDiff {
+    /// Start recording a change of `provenance` limited to `windows`.
+    pub fn begin(provenance: &Provenance, windows: &[(usize, usize)]) -> Self {
+        Self {
+            windows: windows.to_vec(),
+            before: windows
+                .iter()
+                .map(|&(start, end)| provenance.tags_in(start, end))
+                .collect(),
+            after: Vec::new(),
+            sources: provenance.names.len(),
+            added: Vec::new(),
+            edited: (provenance.edited, None),
+        }
+    }
+
+    /// Finish recording with the state of `provenance` after the change.
+    /// Returns `None` if the tags did not change.
+    pub fn finish(mut self, provenance: &Provenance) -> Option<Self> {
+        self.after = self
+            .windows
+            .iter()
+            .map(|&(start, end)| provenance.tags_in(start, end))
+            .collect();
+        self.added = provenance.names[self.sources.min(provenance.names.len())..].to_vec();
+        self.edited.1 = provenance.edited;
+
+        let changed = self.before != self.after || !self.added.is_empty();
+        changed.then_some(self)
+    }
+
+    /// Apply the change (`forward == true`) or revert it (`forward == false`).
+    pub fn apply(&self, provenance: &mut Provenance, forward: bool) {
+        provenance.names.truncate(self.sources);
+        let (tags, edited) = if forward {
+            provenance.names.extend(self.added.iter().cloned());
+            (&self.after, self.edited.1)
+        } else {
+            (&self.before, self.edited.0)
+        };
+        provenance.edited = edited;
+
+        for (&(start, end), tags) in self.windows.iter().zip(tags) {
+            provenance.clear_range(start, end);
+            for &(s, e, id) in tags {
+                provenance.tag(s, e, id);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tag_merges_adjacent_ranges() {
+        // Arrange
+        let mut p = Provenance::default();
+        let id = p.add_source("boot.hex");
+
+        // Act
+        p.tag(0x100, 0x200, id);
+        p.tag(0x200, 0x300, id);
+
+        // Assert
+        assert_eq!(
+            p.ranges().collect::<Vec<_>>(),
+            vec![SourceRange {
+                start: 0x100,
+                end: 0x2FF,
+                source: id
+            }]
+        );
+    }
+
+    #[test]
+    fn test_tag_overwrites_existing_range() {
+        // Arrange
+        let mut p = Provenance::default();
+        let boot = p.add_source("boot.hex");
+        let app = p.add_source("app.hex");
+        p.tag(0x100, 0x200, boot);
+
+        // Act
+        p.tag(0x140, 0x180, app);
+
+        // Assert
+        assert_eq!(p.source_at(0x13F), Some(boot));
+        assert_eq!(p.source_at(0x140), Some(app));
+        assert_eq!(p.source_at(0x17F), Some(app));
+        assert_eq!(p.source_at(0x180), Some(boot));
+        assert_eq!(p.source_at(0x200), None);
+        assert_eq!(p.sizes(), vec![0xC0, 0x40]);
+    }
+
+    #[test]
+    fn test_clear_range_and_shift() {
+        // Arrange
+        let mut p = Provenance::default();
+        let id = p.add_source("app.hex");
+        p.tag(0x100, 0x200, id);
+
+        // Act
+        p.clear_range(0x120, 0x1F0);
+        p.shift(0x1000);
+
+        // Assert
+        assert_eq!(p.source_at(0x111F), Some(id));
+        assert_eq!(p.source_at(0x1120), None);
+        assert_eq!(p.source_at(0x11EF), None);
+        assert_eq!(p.source_at(0x11F0), Some(id));
+        assert_eq!(p.source_name_at(0x11FF), Some("app.hex"));
+    }
+
+    #[test]
+    fn test_copy_from_keeps_sources_with_same_name_apart() {
+        // Arrange
+        let mut p1 = Provenance::default();
+        let app = p1.add_source("app.hex");
+        p1.tag(0x0, 0x100, app);
+        p1.tag_edit(0x10, 0x20);
+
+        let mut p2 = Provenance::default();
+        let other_app = p2.add_source("app.hex");
+        p2.tag(0x100, 0x200, other_app);
+        p2.tag_edit(0x110, 0x120);
+
+        // Act
+        let mut ids = Vec::new();
+        p1.copy_from(&p2, 0x100, 0x180, &mut ids);
+        p1.copy_from(&p2, 0x180, 0x200, &mut ids);
+
+        // Assert
+        assert_eq!(p1.names(), &["app.hex", Provenance::EDITED, "app.hex"]);
+        assert_eq!(p1.source_at(0x0), Some(0));
+        assert_eq!(p1.source_at(0x100), Some(2));
+        assert_eq!(p1.source_at(0x110), Some(1));
+        assert_eq!(p1.source_at(0x1FF), Some(2));
+        assert_eq!(p1.sizes(), vec![0xF0, 0x20, 0xF0]);
+    }
+
+    #[test]
+    fn test_tags_diff_keeps_only_windows() {
+        // Arrange
+        let mut p = Provenance::default();
+        let app = p.add_source("app.hex");
+        p.tag(0x0, 0x1000, app);
+        let original = p.clone();
+
+        // Act
+        let diff = TagsDiff::begin(&p, &[(0x10, 0x12)]);
+        p.tag_edit(0x10, 0x12);
+        let edited = p.clone();
+        let diff = diff.finish(&p).unwrap_or_else(|| unreachable!());
+        diff.apply(&mut p, false);
+        let undone = p.clone();
+        diff.apply(&mut p, true);
+
+        // Assert
+        assert_eq!(diff.before, vec![vec![(0x10, 0x12, app)]]);
+        assert_eq!(diff.after, vec![vec![(0x10, 0x12, 1)]]);
+        assert_eq!(diff.added, vec![Provenance::EDITED.to_string()]);
+        assert_eq!(undone, original);
+        assert_eq!(p, edited);
+    }
+
+    #[test]
+    fn test_copy_from_remaps_ids() {
+        // Arrange
+        let mut p1 = Provenance::default();
+        let boot = p1.add_source("boot.hex");
+        p1.tag(0x0, 0x100, boot);
+
+        let mut p2 = Provenance::default();
+        let cfg = p2.add_source("config.hex");
+        let app = p2.add_source("app.hex");
+        p2.tag(0x100, 0x200, app);
+        p2.tag(0x200, 0x210, cfg);
+
+        // Act
+        p1.copy_from(&p2, 0x180, 0x208, &mut Vec::new());
+
+        // Assert
+        assert_eq!(p1.names(), &["boot.hex", "app.hex", "config.hex"]);
+        assert_eq!(p1.source_name_at(0x17F), None);
+        assert_eq!(p1.source_name_at(0x180), Some("app.hex"));
+        assert_eq!(p1.source_name_at(0x207), Some("config.hex"));
+        assert_eq!(p1.source_name_at(0x208), None);
+    }
+}
@@ -3,7 +3,6 @@
 
 use crate::IntelHexError;
 use crate::error::IntelHexErrorKind;
-use std::fmt::Write;
 
 mod sizes {
     pub const SMALLEST_RECORD: usize = (1 + 2 + 1 + 1) * 2; // len + addr + rtype + checksum
@@ -35,6 +34,92 @@ const fn fast_decode(high: u8, low: u8) -> u8 {
     (HEX_TABLE[high as usize] << 4) | HEX_TABLE[low as usize]
 }
 
+/// Byte with value `b` repeated in every lane of a `u64`
+const fn lanes(b: u8) -> u64 {
+    u64::from_ne_bytes([b; 8])
+}
+
+/// High bit of every lane of `x` set if the byte in it lies within `[lo, hi]`.
+/// All bytes of `x` must be below 0x80.
+const fn in_range(x: u64, lo: u8, hi: u8) -> u64 {
+    let ge_lo = x.wrapping_add(lanes(0x80 - lo));
+    let gt_hi = x.wrapping_add(lanes(0x7F - hi));
+    ge_lo & !gt_hi & lanes(0x80)
+}
+
+#[allow(clippy::cast_possible_truncation)]
+/// Validate and decode 8 ASCII hex digits into 4 bytes at once (SIMD within a register).
+/// Returns `None` if any of the digits is not hexadecimal.
+const fn decode_8_digits(digits: [u8; 8]) -> Option<[u8; 4]> {
+    let x = u64::from_le_bytes(digits);
+
+    // Every byte must be one of '0'..='9', 'A'..='F' or 'a'..='f'
+    if x & lanes(0x80) != 0 {
+        return None;
+    }
+    let valid = in_range(x, b'0', b'9') | in_range(x, b'A', b'F') | in_range(x, b'a', b'f');
+    if valid != lanes(0x80) {
+        return None;
+    }
+
+    // Nibble value: low 4 bits of the digit, plus 9 for letters (which have bit 0x40 set)
+    let nibbles = (x & lanes(0x0F)) + ((x >> 6) & lanes(0x01)) * 9;
+
+    // Join nibble pairs (high nibble comes first) into bytes and pack the bytes together
+    let mut v = ((nibbles << 4) | (nibbles >> 8)) & 0x00FF_00FF_00FF_00FF;
+    v = (v | (v >> 8)) & 0x0000_FFFF_0000_FFFF;
+    v = (v | (v >> 16)) & 0x0000_0000_FFFF_FFFF;
+
+    Some((v as u32).to_le_bytes())
+}
+
+/// Decode ASCII hex digits of `src` into `dst` (half of the length of `src`).
+/// Returns `false` if `src` contains a character that is not a hex digit.
+fn decode_hex(src: &[u8], dst: &mut [u8]) -> bool {
+    let mut src_chunks = src.chunks_exact(8);
+    let mut dst_chunks = dst.chunks_exact_mut(4);
+
+    for (digits, out) in src_chunks.by_ref().zip(dst_chunks.by_ref()) {
+        let Ok(digits) = digits.try_into() else {
+            return false;
+        };
+        let Some(bytes) = decode_8_digits(digits) else {
+            return false;
+        };
+        out.copy_from_slice(&bytes);
+    }
+
+    // Remaining (less than 8) digits are decoded one pair at a time
+    let tail = src_chunks.remainder();
+    if !tail.iter().all(u8::is_ascii_hexdigit) {
+        return false;
+    }
+    for (pair, out) in tail.chunks_exact(2).zip(dst_chunks.into_remainder()) {
+        *out = fast_decode(pair[0], pair[1]);
+    }
+
+    true
+}
+
+/// Buffer for the decoded bytes of the largest possible record
+pub type DecodeBuffer = [u8; sizes::LARGEST_RECORD_HEX];
+
+#[allow(clippy::cast_possible_truncation)]
+// A 256-entry lookup table of the two (uppercase) ASCII hex digits of every byte value
+const HEX_DIGITS: [[u8; 2]; 256] = {
+    let digits = b"0123456789ABCDEF";
+    let mut table = [[0u8; 2]; 256];
+    let mut i = 0;
+    while i < 256 {
+        table[i] = [digits[i >> 4], digits[i & 0x0F]];
+        i += 1;
+    }
+    table
+};
+
+/// Longest record line (without line ending)
+pub const LARGEST_RECORD_LINE: usize = 1 + sizes::LARGEST_RECORD;
+
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
 #[repr(u8)]
 pub enum RecordType {
@@ -80,14 +165,32 @@ impl Record {
         (!sum).wrapping_add(1) // two's complement
     }
 
-    #[allow(clippy::cast_possible_truncation)]
     /// Create the record string from address, type and data vector.
     ///
+    #[cfg(test)]
     pub(crate) fn create(
         address: u16,
         rtype: RecordType,
         data: &[u8],
     ) -> Result<String, IntelHexError> {
+        let mut line = Vec::with_capacity(LARGEST_RECORD_LINE);
+        Self::encode(&mut line, address, rtype, data)?;
+
+        // Encoded records consist of ASCII characters only
+        Ok(String::from_utf8_lossy(&line).into_owned())
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    /// Append the record (without line ending) to `out`. Hex digits are taken from a lookup
+    /// table and the checksum is computed on the fly, so nothing is allocated as long as `out`
+    /// has enough spare capacity.
+    ///
+    pub(crate) fn encode(
+        out: &mut Vec<u8>,
+        address: u16,
+        rtype: RecordType,
+        data: &[u8],
+    ) -> Result<(), IntelHexError> {
         // Get length of payload data
         let length = data.len();
 
@@ -98,110 +201,87 @@ impl Record {
             ));
         }
 
-        // Create a vector of data for checksum calculation
-        let mut v = vec![
+        // Sanity checks for length and address of the special record types
+        let (expected_len, expected_address) = match rtype {
+            RecordType::Data => (None, None),
+            RecordType::EndOfFile => (Some(0), None),
+            RecordType::ExtendedLinearAddress => (Some(2), Some(0)),
+            RecordType::StartLinearAddress | RecordType::StartSegmentAddress => (Some(4), Some(0)),
+            // ESA is intentionally unsupported: the library always emits ELA records
+            // when writing hex files (see `IntelHex::write_hex`).
+            // Parsing ESA is still supported.
+            RecordType::ExtendedSegmentAddress => {
+                return Err(IntelHexError::CreateRecordError(
+                    IntelHexErrorKind::RecordNotSupported,
+                ));
+            }
+        };
+        if let Some(expected) = expected_len
+            && length != expected
+        {
+            return Err(IntelHexError::CreateRecordError(
+                IntelHexErrorKind::RecordLengthInvalidForType(rtype, expected, length),
+            ));
+        }
+        if let Some(expected) = expected_address
+            && address as usize != expected
+        {
+            return Err(IntelHexError::CreateRecordError(
+                IntelHexErrorKind::RecordAddressInvalidForType(rtype, expected, address as usize),
+            ));
+        }
+
+        // Header: length, address and record type
+        let header = [
             length as u8,
             (address >> 8) as u8,
             (address & 0xFF) as u8,
             rtype as u8,
         ];
-        v.extend_from_slice(data);
 
-        // Checksum
-        let checksum = Self::calculate_checksum(&v);
+        out.push(b':');
+        let mut sum: u8 = 0;
+        for &b in header.iter().chain(data) {
+            out.extend_from_slice(&HEX_DIGITS[b as usize]);
+            sum = sum.wrapping_add(b);
+        }
 
-        match rtype {
-            RecordType::Data => {
-                // Create record string
-                let record = format!(
-                    ":{:02X}{:04X}00{}{:02X}",
-                    length,
-                    address,
-                    data.iter()
-                        .fold(String::with_capacity(data.len() * 2), |mut buffer, b| {
-                            let _ = write!(buffer, "{b:02X}");
-                            buffer
-                        }),
-                    checksum
-                );
-
-                Ok(record)
-            }
-            RecordType::EndOfFile => Ok(String::from(":00000001FF")),
-            RecordType::ExtendedLinearAddress => {
-                // Check for data length (has to be 1 byte)
-                if length != 2 {
-                    return Err(IntelHexError::CreateRecordError(
-                        IntelHexErrorKind::RecordLengthInvalidForType(rtype, 2, length),
-                    ));
-                }
+        // Checksum (two's complement of the sum)
+        out.extend_from_slice(&HEX_DIGITS[(!sum).wrapping_add(1) as usize]);
 
-                // Check for address (has to be 0x0)
-                if address != 0 {
-                    return Err(IntelHexError::CreateRecordError(
-                        IntelHexErrorKind::RecordAddressInvalidForType(rtype, 0, address as usize),
-                    ));
-                }
+        Ok(())
+    }
 
-                // Create record string
-                let record = format!(
-                    ":{:02X}{:04X}{:02X}{}{:02X}",
-                    length,
-                    address,
-                    rtype as u8,
-                    data.iter()
-                        .fold(String::with_capacity(data.len() * 2), |mut buffer, b| {
-                            let _ = write!(buffer, "{b:02X}");
-                            buffer
-                        }),
-                    checksum
-                );
-
-                Ok(record)
-            }
-            RecordType::StartLinearAddress | RecordType::StartSegmentAddress => {
-                // Check for data length
-                if length != 4 {
-                    return Err(IntelHexError::CreateRecordError(
-                        IntelHexErrorKind::RecordLengthInvalidForType(rtype, 4, length),
-                    ));
-                }
+    /// Parse the record string into Record.
+    ///
+    pub(crate) fn parse(line: &[u8]) -> Result<Self, IntelHexErrorKind> {
+        let mut buf: DecodeBuffer = [0u8; sizes::LARGEST_RECORD_HEX];
 
-                // Check for address
-                if address != 0 {
-                    return Err(IntelHexError::CreateRecordError(
-                        IntelHexErrorKind::RecordAddressInvalidForType(rtype, 0, address as usize),
-                    ));
-                }
+        let (rtype, address, data) = Self::decode(line, &mut buf)?;
+        let data = data.to_vec();
 
-                // Create record string
-                let record = format!(
-                    ":{:02X}{:04X}{:02X}{}{:02X}",
-                    length,
-                    address,
-                    rtype as u8,
-                    data.iter()
-                        .fold(String::with_capacity(data.len() * 2), |mut buffer, b| {
-                            let _ = write!(buffer, "{b:02X}");
-                            buffer
-                        }),
-                    checksum
-                );
-
-                Ok(record)
-            }
-            // ESA is intentionally unsupported: the library always emits ELA records
-            // when writing hex files (see `IntelHex::write_hex`).
-            // Parsing ESA is still supported.
-            RecordType::ExtendedSegmentAddress => Err(IntelHexError::CreateRecordError(
-                IntelHexErrorKind::RecordNotSupported,
-            )),
-        }
+        // Length and checksum were validated during decoding
+        #[allow(clippy::cast_possible_truncation)]
+        let length = data.len() as u8;
+        let checksum = buf[4 + data.len()];
+
+        // Construct and return record instance
+        Ok(Self {
+            length,
+            address,
+            rtype,
+            data,
+            checksum,
+        })
     }
 
-    /// Parse the record string into Record.
+    /// Validate the record string and decode it into `buf` without allocating.
+    /// Returns the record type, address and the data payload (borrowed from `buf`).
     ///
-    pub(crate) fn parse(line: &[u8]) -> Result<Self, IntelHexErrorKind> {
+    pub(crate) fn decode<'a>(
+        line: &[u8],
+        buf: &'a mut DecodeBuffer,
+    ) -> Result<(RecordType, u16, &'a [u8]), IntelHexErrorKind> {
         // Check for start record
         if line[0] != b':' {
             return Err(IntelHexErrorKind::MissingStartCode);
@@ -220,27 +300,14 @@ impl Record {
             return Err(IntelHexErrorKind::RecordNotEvenLength);
         }
 
-        // Validate all characters are hexadecimal
-        if !&line[1..].iter().all(u8::is_ascii_hexdigit) {
+        // Validate and decode hex digits into the buffer (avoids heap allocations of Vec)
+        if !decode_hex(&line[1..], &mut buf[..hex_len / 2]) {
             return Err(IntelHexErrorKind::ContainsInvalidCharacters);
         }
 
-        // Create an empty hex digit buffer with max size equal to the largest record possible.
-        // Avoids heap allocations of Vec.
-        let mut decoded_hex_buf = [0u8; sizes::LARGEST_RECORD_HEX];
-
-        // Decode hex digits into the buffer.
-        // No need to check validity during fast_decode as it is checked above.
-        for (count, i) in (1..hex_len).step_by(2).enumerate() {
-            decoded_hex_buf[count] = fast_decode(line[i], line[i + 1]);
-        }
-
         // Get record length
-        let length = decoded_hex_buf[0];
-
-        // Create a vector of data for checksum calculation
+        let length = buf[0];
         let data_end = 4 + length as usize;
-        let v = decoded_hex_buf[..data_end].to_vec();
 
         // Check if record's end is bigger than the record length itself
         let record_end = data_end + 1; // last byte is checksum
@@ -249,10 +316,10 @@ impl Record {
         }
 
         // Get record type
-        let rtype = RecordType::parse(decoded_hex_buf[3])?;
+        let rtype = RecordType::parse(buf[3])?;
 
         // Get record address
-        let address = u16::from_be_bytes([decoded_hex_buf[1], decoded_hex_buf[2]]);
+        let address = u16::from_be_bytes([buf[1], buf[2]]);
 
         // More sanity checks (for length and address)
         match rtype {
@@ -293,14 +360,9 @@ impl Record {
             ));
         }
 
-        // Get record data payload
-        let data = decoded_hex_buf[4..data_end].to_vec();
-
-        // Get checksum
-        let checksum = decoded_hex_buf[record_end - 1];
-
         // Validate checksum
-        let calc_checksum = Self::calculate_checksum(&v);
+        let checksum = buf[record_end - 1];
+        let calc_checksum = Self::calculate_checksum(&buf[..data_end]);
         if calc_checksum != checksum {
             return Err(IntelHexErrorKind::RecordChecksumMismatch(
                 calc_checksum,
@@ -308,14 +370,7 @@ impl Record {
             ));
         }
 
-        // Construct and return record instance
-        Ok(Self {
-            length,
-            address,
-            rtype,
-            data,
-            checksum,
-        })
+        Ok((rtype, address, &buf[4..data_end]))
     }
 }
 
@@ -607,6 +662,27 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_encode_appends_to_buffer() {
+        // Arrange
+        let mut out = b"prefix".to_vec();
+
+        // Act
+        let res_data = Record::encode(&mut out, 0x0010, RecordType::Data, &[0xDE, 0xAD]);
+        let res_eof = Record::encode(&mut out, 0, RecordType::EndOfFile, &[]);
+        let res_esa = Record::encode(&mut out, 0, RecordType::ExtendedSegmentAddress, &[0, 0]);
+
+        // Assert
+        assert!(res_data.is_ok() && res_eof.is_ok());
+        assert_eq!(
+            res_esa,
+            Err(IntelHexError::CreateRecordError(
+                IntelHexErrorKind::RecordNotSupported
+            ))
+        );
+        assert_eq!(out, b"prefix:02001000DEAD63:00000001FF");
+    }
+
     #[test]
     fn test_fast_decode() {
         assert_eq!(fast_decode(b'0', b'0'), 0x00);
@@ -614,4 +690,40 @@ mod tests {
         assert_eq!(fast_decode(b'0', b'f'), 0x0F);
         assert_eq!(fast_decode(b'c', b'C'), 0xCC);
     }
+
+    #[test]
+    fn test_decode_hex_matches_fast_decode() {
+        // Arrange: every hex digit in every position of a word and in the tail
+        let digits = b"0123456789ABCDEFabcdef";
+        let src: Vec<u8> = digits.iter().chain(digits.iter().rev()).copied().collect();
+        let mut dst = vec![0u8; src.len() / 2];
+
+        // Act
+        let ok = decode_hex(&src, &mut dst);
+
+        // Assert
+        let expected: Vec<u8> = src.chunks(2).map(|p| fast_decode(p[0], p[1])).collect();
+        assert!(ok);
+        assert_eq!(dst, expected);
+    }
+
+    #[test]
+    fn test_decode_hex_rejects_invalid_characters() {
+        // Arrange: replace one digit of a valid string with every possible byte value
+        let valid = b"00112233445566778899AA";
+        let mut dst = [0u8; 11];
+
+        for pos in [0, 7, 8, 21] {
+            for byte in 0..=u8::MAX {
+                let mut src = *valid;
+                src[pos] = byte;
+
+                // Act
+                let ok = decode_hex(&src, &mut dst);
+
+                // Assert
+                assert_eq!(ok, byte.is_ascii_hexdigit(), "byte {byte:#04X} at {pos}");
+            }
+        }
+    }
 }
@@ -1,28 +1,431 @@
+use aho_corasick::AhoCorasick;
 use regex::bytes::Regex;
+use std::fmt;
+use std::str::FromStr;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum SearchType {
     Hex(Vec<u8>),
+    Masked(MaskedPattern),
     Ascii(String),
     Regex(String),
 }
 
+/// Options of a lazy byte pattern search, see
+/// [`IntelHex::search_bytes_iter`](crate::IntelHex::search_bytes_iter).
+///
+/// # Example
+/// ```
+/// use intelhexlib::SearchOptions;
+///
+/// // First 10 matches in the first 64 KiB, also across gaps in erased flash
+/// let options = SearchOptions {
+///     range: Some((0x0, 0xFFFF)),
+///     max_results: Some(10),
+///     gap_fill: Some(0xFF),
+/// };
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SearchOptions {
+    /// Inclusive address window matches have to lie in. `None` searches the whole image.
+    pub range: Option<(usize, usize)>,
+    /// Maximum number of matches. `None` for no limit.
+    pub max_results: Option<usize>,
+    /// Byte the gaps between data chunks are treated as filled with, so that matches may
+    /// span gaps. `None` only finds matches inside contiguous chunks.
+    pub gap_fill: Option<u8>,
+}
+
+/// Byte pattern with wildcards. A data byte `b` matches the pattern byte at the same
+/// position if `b & mask == value`.
+///
+/// # Example
+/// ```
+/// use intelhexlib::MaskedPattern;
+///
+/// // Any byte between 0xDE and 0xBE, high nibble 4 followed by 0x00
+/// let pattern: MaskedPattern = "DE ?? BE 4? 00".parse().unwrap();
+/// assert!(pattern.matches(&[0xDE, 0x12, 0xBE, 0x4A, 0x00]));
+///
+/// // Explicit mask after a '/'
+/// let pattern: MaskedPattern = "80 01 / C0 FF".parse().unwrap();
+/// assert!(pattern.matches(&[0xBF, 0x01]));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MaskedPattern {
+    values: Vec<u8>,
+    masks: Vec<u8>,
+}
+
+impl MaskedPattern {
+    /// Create a pattern from values and masks of the same length.
+    /// Returns `None` if the lengths differ.
+    #[must_use]
+    pub fn new(values: &[u8], masks: &[u8]) -> Option<Self> {
+        (values.len() == masks.len()).then(|| Self {
+            values: values.iter().zip(masks).map(|(v, m)| v & m).collect(),
+            masks: masks.to_vec(),
+        })
+    }
+
+    /// Length of the pattern in bytes.
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns `true` if the pattern has no bytes.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Expected values (with masked-out bits cleared).
+    #[must_use]
+    pub fn values(&self) -> &[u8] {
+        &self.values
+    }
+
+    /// Masks of bits that have to match.
+    #[must_use]
+    pub fn masks(&self) -> &[u8] {
+        &self.masks
+    }
+
+    /// Returns `true` if `data` (of the pattern's length) matches the pattern.
+    #[must_use]
+    pub fn matches(&self, data: &[u8]) -> bool {
+        data.len() == self.len()
+            && data
+                .iter()
+                .zip(&self.values)
+                .zip(&self.masks)
+                .all(|((b, v), m)| b & m == *v)
+    }
+}
+
+impl fmt::Display for MaskedPattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const DIGITS: &[u8; 16] = b"0123456789ABCDEF";
+
+        // Masks that are not made of whole nibbles are written out explicitly after a '/'
+        let explicit_mask = self
+            .masks
+            .iter()
+            .any(|m| !matches!(m >> 4, 0x0 | 0xF) || !matches!(m & 0xF, 0x0 | 0xF));
+
+        let digit = |value: u8, mask: u8| {
+            if explicit_mask || mask == 0xF {
+                DIGITS[value as usize] as char
+            } else {
+                '?'
+            }
+        };
+
+        for (i, (&v, &m)) in self.values.iter().zip(&self.masks).enumerate() {
+            if i > 0 {
+                write!(f, " ")?;
+            }
+            write!(f, "{}{}", digit(v >> 4, m >> 4), digit(v & 0xF, m & 0xF))?;
+        }
+
+        if explicit_mask {
+            write!(f, " /")?;
+            self.masks.iter().try_for_each(|m| write!(f, " {m:02X}"))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Parses the pattern from hex bytes (whitespace is ignored) where a `?` stands for any
+/// nibble, e.g. `DE ?? BE EF` or `4?00`. An explicit byte mask can follow after a `/`,
+/// e.g. `8001 / C0FF`.
+impl FromStr for MaskedPattern {
+    type Err = String;
+
+    #[allow(clippy::cast_possible_truncation)]
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (pattern_str, mask_str) = s.split_once('/').unwrap_or((s, ""));
+
+        let digits: Vec<char> = pattern_str.chars().filter(|c| !c.is_whitespace()).collect();
+        if digits.is_empty() || !digits.len().is_multiple_of(2) {
+            return Err(format!("Invalid masked pattern: {s}"));
+        }
+
+        // Every digit is either a hex digit or a '?' wildcard
+        let nibble = |c: char| match c {
+            '?' => Some((0x0, 0x0)),
+            _ => c.to_digit(16).map(|d| (d as u8, 0xF)),
+        };
+        let mut values = Vec::with_capacity(digits.len() / 2);
+        let mut masks = Vec::with_capacity(digits.len() / 2);
+        for pair in digits.chunks_exact(2) {
+            let (Some((hv, hm)), Some((lv, lm))) = (nibble(pair[0]), nibble(pair[1])) else {
+                return Err(format!("Invalid masked pattern: {s}"));
+            };
+            values.push((hv << 4) | lv);
+            masks.push((hm << 4) | lm);
+        }
+
+        // Explicit mask is combined with the wildcards
+        let mask_str: String = mask_str.chars().filter(|c| !c.is_whitespace()).collect();
+        if s.contains('/') {
+            if mask_str.len() != digits.len() || !mask_str.is_ascii() {
+                return Err(format!("Mask length does not match the pattern: {s}"));
+            }
+            for (i, m) in masks.iter_mut().enumerate() {
+                *m &= u8::from_str_radix(&mask_str[i * 2..i * 2 + 2], 16)
+                    .map_err(|_| format!("Invalid mask: {mask_str}"))?;
+            }
+        }
+
+        Self::new(&values, &masks).ok_or_else(|| format!("Invalid masked pattern: {s}"))
+    }
+}
+
+/// Numeric type of an encoded value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValueKind {
+    U16,
+    U32,
+    U64,
+    I16,
+    I32,
+    I64,
+    F32,
+    F64,
+}
+
+impl ValueKind {
+    /// Size of the encoded value in bytes.
+    #[must_use]
+    pub const fn size(self) -> usize {
+        match self {
+            Self::U16 | Self::I16 => 2,
+            Self::U32 | Self::I32 | Self::F32 => 4,
+            Self::U64 | Self::I64 | Self::F64 => 8,
+        }
+    }
+}
+
+impl fmt::Display for ValueKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::U16 => "u16",
+            Self::U32 => "u32",
+            Self::U64 => "u64",
+            Self::I16 => "i16",
+            Self::I32 => "i32",
+            Self::I64 => "i64",
+            Self::F32 => "f32",
+            Self::F64 => "f64",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// Encoding of a numeric value in the data: its type and byte order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ValueEncoding {
+    pub kind: ValueKind,
+    pub big_endian: bool,
+}
+
+impl fmt::Display for ValueEncoding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let order = if self.big_endian { "BE" } else { "LE" };
+        write!(f, "{} {order}", self.kind)
+    }
+}
+
+/// Numeric value to search for in all encodings it can be stored in.
+///
+/// # Example
+/// ```
+/// use intelhexlib::NumericValue;
+///
+/// // 115200 fits into u32 and u64 and is exactly representable as f32 and f64
+/// let value: NumericValue = "115200".parse().unwrap();
+/// assert_eq!(value.encodings().len(), 8);
+///
+/// // 3.3 is only stored as a float
+/// let value: NumericValue = "3.3".parse().unwrap();
+/// let labels: Vec<String> = value.encodings().iter().map(|(e, _)| e.to_string()).collect();
+/// assert_eq!(labels, ["f32 LE", "f32 BE", "f64 LE", "f64 BE"]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NumericValue {
+    Int(i128),
+    Float(f64),
+}
+
+impl NumericValue {
+    /// All encodings of the value with their bytes: the unsigned (or, if negative, signed)
+    /// integer types the value fits into and the float types that represent it exactly
+    /// (for an integer) or without overflow (for a float), each in little- and big-endian.
+    /// Encodings with the same bytes as a preceding one are left out.
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation)]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn encodings(&self) -> Vec<(ValueEncoding, Vec<u8>)> {
+        let mut kinds: Vec<(ValueKind, Vec<u8>, Vec<u8>)> = Vec::new();
+
+        match *self {
+            Self::Int(v) if v >= 0 => {
+                if let Ok(x) = u16::try_from(v) {
+                    kinds.push((
+                        ValueKind::U16,
+                        x.to_le_bytes().into(),
+                        x.to_be_bytes().into(),
+                    ));
+                }
+                if let Ok(x) = u32::try_from(v) {
+                    kinds.push((
+                        ValueKind::U32,
+                        x.to_le_bytes().into(),
+                        x.to_be_bytes().into(),
+                    ));
+                }
+                if let Ok(x) = u64::try_from(v) {
+                    kinds.push((
+                        ValueKind::U64,
+                        x.to_le_bytes().into(),
+                        x.to_be_bytes().into(),
+                    ));
+                }
+            }
+            Self::Int(v) => {
+                if let Ok(x) = i16::try_from(v) {
+                    kinds.push((
+                        ValueKind::I16,
+                        x.to_le_bytes().into(),
+                        x.to_be_bytes().into(),
+                    ));
+                }
+                if let Ok(x) = i32::try_from(v) {
+                    kinds.push((
+                        ValueKind::I32,
+                        x.to_le_bytes().into(),
+                        x.to_be_bytes().into(),
+                    ));
+                }
+                if let Ok(x) = i64::try_from(v) {
+                    kinds.push((
+                        ValueKind::I64,
+                        x.to_le_bytes().into(),
+                        x.to_be_bytes().into(),
+                    ));
+                }
+            }
+            Self::Float(_) => {}
+        }
+
+        match *self {
+            Self::Int(v) => {
+                let x = v as f32;
+                if x as i128 == v {
+                    kinds.push((
+                        ValueKind::F32,
+                        x.to_le_bytes().into(),
+                        x.to_be_bytes().into(),
+                    ));
+                }
+                let x = v as f64;
+                if x as i128 == v {
+                    kinds.push((
+                        ValueKind::F64,
+                        x.to_le_bytes().into(),
+                        x.to_be_bytes().into(),
+                    ));
+                }
+            }
+            Self::Float(v) => {
+                let x = v as f32;
+                if x.is_finite() {
+                    kinds.push((
+                        ValueKind::F32,
+                        x.to_le_bytes().into(),
+                        x.to_be_bytes().into(),
+                    ));
+                }
+                kinds.push((
+                    ValueKind::F64,
+                    v.to_le_bytes().into(),
+                    v.to_be_bytes().into(),
+                ));
+            }
+        }
+
+        let mut encodings: Vec<(ValueEncoding, Vec<u8>)> = Vec::with_capacity(kinds.len() * 2);
+        for (kind, le, be) in kinds {
+            for (big_endian, bytes) in [(false, le), (true, be)] {
+                if !encodings.iter().any(|(_, b)| *b == bytes) {
+                    encodings.push((ValueEncoding { kind, big_endian }, bytes));
+                }
+            }
+        }
+        encodings
+    }
+}
+
+impl fmt::Display for NumericValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Int(v) => write!(f, "{v}"),
+            Self::Float(v) => write!(f, "{v}"),
+        }
+    }
+}
+
+/// Parses a decimal integer (`115200`, `-5`), a hex integer (`0x1C200`) or a finite
+/// float (`3.3`, `1e-3`).
+impl FromStr for NumericValue {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+
+        let value = s
+            .strip_prefix("0x")
+            .or_else(|| s.strip_prefix("0X"))
+            .map_or_else(
+                || {
+                    s.parse::<i128>().map(Self::Int).ok().or_else(|| {
+                        s.parse::<f64>()
+                            .ok()
+                            .filter(|v| v.is_finite())
+                            .map(Self::Float)
+                    })
+                },
+                |hex| i128::from_str_radix(hex, 16).map(Self::Int).ok(),
+            );
+
+        match value {
+            Some(value) if !value.encodings().is_empty() => Ok(value),
+            Some(_) => Err(format!("Value out of range: {s}")),
+            None => Err(format!("Invalid value: {s}")),
+        }
+    }
+}
+
 /// Searches for a pattern in the hex data.
 pub fn search<'a>(
-    iter: impl Iterator<Item = (&'a usize, &'a Vec<u8>)>,
+    iter: impl Iterator<Item = (&'a usize, &'a [u8])>,
     search_type: &SearchType,
 ) -> Vec<usize> {
     match search_type {
         SearchType::Hex(p) => search_bytes(iter, p),
+        SearchType::Masked(p) => search_masked(iter, p),
         SearchType::Ascii(s) => search_bytes(iter, s.as_bytes()),
         SearchType::Regex(p) => search_regex(iter, p),
     }
 }
 
-/// Slide window search for `BTreeMap<usize, Vec<u8>>`.
+/// Slide window search over contiguous data chunks.
 /// Returns the starting addresses of all matches.
 fn search_bytes<'a>(
-    iter: impl Iterator<Item = (&'a usize, &'a Vec<u8>)>,
+    iter: impl Iterator<Item = (&'a usize, &'a [u8])>,
     pattern: &[u8],
 ) -> Vec<usize> {
     let size = pattern.len();
@@ -45,10 +448,109 @@ fn search_bytes<'a>(
     matches
 }
 
-/// Regex search for `BTreeMap<usize, Vec<u8>>`.
+/// Slide window search for byte windows within a Hamming distance of the pattern.
+/// Lazily yields the starting addresses of matches with their number of mismatching bytes.
+pub fn search_hamming<'a>(
+    iter: impl Iterator<Item = (&'a usize, &'a [u8])> + 'a,
+    pattern: &'a [u8],
+    max_mismatches: usize,
+) -> impl Iterator<Item = (usize, usize)> + 'a {
+    iter.filter(|_| !pattern.is_empty())
+        .flat_map(move |(&addr, data)| {
+            data.windows(pattern.len())
+                .enumerate()
+                .filter_map(move |(offset, window)| {
+                    // Stop counting as soon as the window is too different
+                    let mut mismatches = 0;
+                    for (b, p) in window.iter().zip(pattern) {
+                        if b != p {
+                            mismatches += 1;
+                            if mismatches > max_mismatches {
+                                return None;
+                            }
+                        }
+                    }
+                    Some((addr + offset, mismatches))
+                })
+        })
+}
+
+/// Slide window search with a masked pattern over contiguous data chunks.
+/// Returns the starting addresses of all matches.
+fn search_masked<'a>(
+    iter: impl Iterator<Item = (&'a usize, &'a [u8])>,
+    pattern: &MaskedPattern,
+) -> Vec<usize> {
+    if pattern.is_empty() {
+        return vec![];
+    }
+
+    let mut matches = Vec::new();
+
+    for (&addr, data) in iter {
+        for (offset, window) in data.windows(pattern.len()).enumerate() {
+            if pattern.matches(window) {
+                matches.push(addr + offset);
+            }
+        }
+    }
+
+    matches
+}
+
+/// Multi-pattern search over contiguous data chunks in a single pass.
+/// Returns `(pattern index, address)` of all (also overlapping) matches sorted by address.
+/// Empty patterns never match.
+pub fn search_many<'a, P: AsRef<[u8]>>(
+    iter: impl Iterator<Item = (&'a usize, &'a [u8])>,
+    patterns: &[P],
+) -> Vec<(usize, usize)> {
+    // Keep the original index of every non-empty pattern
+    let (indices, needles): (Vec<usize>, Vec<&[u8]>) = patterns
+        .iter()
+        .enumerate()
+        .map(|(idx, p)| (idx, p.as_ref()))
+        .filter(|(_, p)| !p.is_empty())
+        .unzip();
+
+    if needles.is_empty() {
+        return vec![];
+    }
+    let Ok(ac) = AhoCorasick::new(&needles) else {
+        return vec![];
+    };
+
+    let mut matches = Vec::new();
+
+    for (&addr, data) in iter {
+        for mtch in ac.find_overlapping_iter(data) {
+            matches.push((indices[mtch.pattern().as_usize()], addr + mtch.start()));
+        }
+    }
+
+    matches.sort_unstable_by_key(|&(idx, addr)| (addr, idx));
+    matches
+}
+
+/// Search for a numeric value in all of its encodings in a single pass.
+/// Returns the start addresses of all matches with their encoding, sorted by address.
+pub fn search_value<'a>(
+    iter: impl Iterator<Item = (&'a usize, &'a [u8])>,
+    value: &NumericValue,
+) -> Vec<(usize, ValueEncoding)> {
+    let (encodings, patterns): (Vec<ValueEncoding>, Vec<Vec<u8>>) =
+        value.encodings().into_iter().unzip();
+
+    search_many(iter, &patterns)
+        .into_iter()
+        .map(|(idx, addr)| (addr, encodings[idx]))
+        .collect()
+}
+
+/// Regex search over contiguous data chunks.
 /// Returns the starting addresses of all matches.
 fn search_regex<'a>(
-    iter: impl Iterator<Item = (&'a usize, &'a Vec<u8>)>,
+    iter: impl Iterator<Item = (&'a usize, &'a [u8])>,
     pattern: &str,
 ) -> Vec<usize> {
     let Ok(re) = Regex::new(pattern) else {
@@ -87,7 +589,10 @@ mod tests {
         let map: BTreeMap<usize, Vec<u8>> = BTreeMap::from([(start_addr, random_bytes)]);
 
         // Act
-        let res = search(map.iter(), &SearchType::Hex(pattern));
+        let res = search(
+            map.iter().map(|(k, v)| (k, v.as_slice())),
+            &SearchType::Hex(pattern),
+        );
 
         // Assert
         assert_eq!(res, vec![start_addr + 203]);
@@ -109,7 +614,10 @@ mod tests {
         let map: BTreeMap<usize, Vec<u8>> = BTreeMap::from([(start_addr, random_bytes)]);
 
         // Act
-        let res = search(map.iter(), &SearchType::Ascii("20.71".to_string()));
+        let res = search(
+            map.iter().map(|(k, v)| (k, v.as_slice())),
+            &SearchType::Ascii("20.71".to_string()),
+        );
 
         // Assert
         assert_eq!(res, vec![start_addr + 203]);
@@ -131,9 +639,218 @@ mod tests {
         let map: BTreeMap<usize, Vec<u8>> = BTreeMap::from([(start_addr, random_bytes)]);
 
         // Act
-        let res = search(map.iter(), &SearchType::Regex(r"\d{2}\D{2}L".to_string()));
+        let res = search(
+            map.iter().map(|(k, v)| (k, v.as_slice())),
+            &SearchType::Regex(r"\d{2}\D{2}L".to_string()),
+        );
 
         // Assert
         assert_eq!(res, vec![start_addr + 203]);
     }
+
+    #[test]
+    fn test_masked_pattern_parse_and_display() {
+        // Arrange
+        let inputs = ["DE ?? BE EF", "4?00", "?f", "8001 / C0FF", "12 34 / F0 0F"];
+
+        // Act
+        let parsed: Vec<Result<MaskedPattern, String>> = inputs.iter().map(|s| s.parse()).collect();
+
+        // Assert
+        assert_eq!(
+            parsed[0],
+            Ok(MaskedPattern {
+                values: vec![0xDE, 0x00, 0xBE, 0xEF],
+                masks: vec![0xFF, 0x00, 0xFF, 0xFF],
+            })
+        );
+        assert_eq!(
+            parsed[1],
+            MaskedPattern::new(&[0x40, 0x00], &[0xF0, 0xFF]).ok_or_else(String::new)
+        );
+        assert_eq!(
+            parsed[2],
+            MaskedPattern::new(&[0x0F], &[0x0F]).ok_or_else(String::new)
+        );
+        assert_eq!(
+            parsed[3],
+            MaskedPattern::new(&[0x80, 0x01], &[0xC0, 0xFF]).ok_or_else(String::new)
+        );
+        let displayed: Vec<String> = parsed
+            .iter()
+            .map(|p| p.clone().unwrap_or_default().to_string())
+            .collect();
+        assert_eq!(
+            displayed,
+            vec!["DE ?? BE EF", "4? 00", "?F", "80 01 / C0 FF", "1? ?4"]
+        );
+
+        assert!("".parse::<MaskedPattern>().is_err());
+        assert!("ABC".parse::<MaskedPattern>().is_err());
+        assert!("AG".parse::<MaskedPattern>().is_err());
+        assert!("AB / F".parse::<MaskedPattern>().is_err());
+        assert!("AB / FFFF".parse::<MaskedPattern>().is_err());
+        assert!("AB / XX".parse::<MaskedPattern>().is_err());
+        assert_eq!(MaskedPattern::new(&[0x00], &[]), None);
+    }
+
+    #[test]
+    fn test_search_masked() {
+        // Arrange
+        let map: BTreeMap<usize, Vec<u8>> = BTreeMap::from([
+            (
+                0x1000,
+                vec![0xDE, 0xAD, 0xBE, 0xEF, 0x41, 0x00, 0xDE, 0x00, 0xBE],
+            ),
+            (0x2000, vec![0x4F, 0x00, 0xDE]),
+        ]);
+        let wildcard = "DE ?? BE".parse().unwrap_or_default();
+        let nibble = "4? 00".parse().unwrap_or_default();
+
+        // Act
+        let res_wildcard = search(
+            map.iter().map(|(k, v)| (k, v.as_slice())),
+            &SearchType::Masked(wildcard),
+        );
+        let res_nibble = search(
+            map.iter().map(|(k, v)| (k, v.as_slice())),
+            &SearchType::Masked(nibble),
+        );
+
+        // Assert
+        assert_eq!(res_wildcard, vec![0x1000, 0x1006]);
+        assert_eq!(res_nibble, vec![0x1004, 0x2000]);
+    }
+
+    #[test]
+    fn test_search_many() {
+        // Arrange
+        let map: BTreeMap<usize, Vec<u8>> = BTreeMap::from([
+            (0x1000, vec![0xDE, 0xAD, 0xBE, 0xEF, 0x00, 0xDE, 0xAD]),
+            (0x2000, vec![0xBE, 0xEF, 0x41, 0x42, 0x43]),
+        ]);
+        let patterns: [&[u8]; 5] = [
+            &[0xDE, 0xAD, 0xBE, 0xEF],
+            &[],
+            &[0xBE, 0xEF],
+            b"ABC",
+            &[0xDE, 0xAD],
+        ];
+
+        // Act
+        let res = search_many(map.iter().map(|(k, v)| (k, v.as_slice())), &patterns);
+
+        // Assert: overlapping matches are all reported, none spans the gap between chunks
+        assert_eq!(
+            res,
+            vec![
+                (0, 0x1000),
+                (4, 0x1000),
+                (2, 0x1002),
+                (4, 0x1005),
+                (2, 0x2000),
+                (3, 0x2002),
+            ]
+        );
+        assert!(search_many(map.iter().map(|(k, v)| (k, v.as_slice())), &[b""]).is_empty());
+    }
+
+    #[test]
+    fn test_numeric_value_encodings() {
+        // Arrange
+        let inputs = [
+            "115200", "-2", "0x0101", "3.3", "1e300", "abc", "inf", "1e50000",
+        ];
+
+        // Act
+        let parsed: Vec<Result<NumericValue, String>> = inputs.iter().map(|s| s.parse()).collect();
+        let labels = |i: usize| -> Vec<String> {
+            parsed[i]
+                .as_ref()
+                .map(|v| v.encodings().iter().map(|(e, _)| e.to_string()).collect())
+                .unwrap_or_default()
+        };
+
+        // Assert
+        assert_eq!(
+            labels(0),
+            [
+                "u32 LE", "u32 BE", "u64 LE", "u64 BE", "f32 LE", "f32 BE", "f64 LE", "f64 BE"
+            ]
+        );
+        assert_eq!(
+            labels(1),
+            [
+                "i16 LE", "i16 BE", "i32 LE", "i32 BE", "i64 LE", "i64 BE", "f32 LE", "f32 BE",
+                "f64 LE", "f64 BE"
+            ]
+        );
+        assert_eq!(
+            labels(2),
+            [
+                "u16 LE", "u32 LE", "u32 BE", "u64 LE", "u64 BE", "f32 LE", "f32 BE", "f64 LE",
+                "f64 BE"
+            ]
+        );
+        assert_eq!(labels(3), ["f32 LE", "f32 BE", "f64 LE", "f64 BE"]);
+        assert_eq!(labels(4), ["f64 LE", "f64 BE"]);
+        assert_eq!(parsed[5], Err("Invalid value: abc".to_string()));
+        assert!(parsed[6].is_err());
+        assert!(parsed[7].is_err());
+
+        let encodings = parsed[0].clone().map(|v| v.encodings()).unwrap_or_default();
+        assert_eq!(encodings[0].1, vec![0x00, 0xC2, 0x01, 0x00]);
+        assert_eq!(encodings[5].1, 115_200f32.to_be_bytes());
+    }
+
+    #[test]
+    fn test_search_value() {
+        // Arrange
+        let mut data = vec![0u8; 32];
+        data[2..6].copy_from_slice(&115_200u32.to_be_bytes());
+        data[16..20].copy_from_slice(&115_200f32.to_le_bytes());
+        let map: BTreeMap<usize, Vec<u8>> = BTreeMap::from([(0x1000, data)]);
+        let value = "115200".parse().unwrap_or(NumericValue::Int(0));
+
+        // Act
+        let res = search_value(map.iter().map(|(k, v)| (k, v.as_slice())), &value);
+
+        // Assert
+        let big_endian_u32 = ValueEncoding {
+            kind: ValueKind::U32,
+            big_endian: true,
+        };
+        let little_endian_f32 = ValueEncoding {
+            kind: ValueKind::F32,
+            big_endian: false,
+        };
+        assert_eq!(
+            res,
+            vec![(0x1002, big_endian_u32), (0x1010, little_endian_f32)]
+        );
+    }
+
+    #[test]
+    fn test_search_hamming() {
+        // Arrange
+        let map: BTreeMap<usize, Vec<u8>> = BTreeMap::from([
+            (
+                0x1000,
+                vec![0x10, 0x20, 0x30, 0x40, 0x00, 0x10, 0x21, 0x30, 0x41],
+            ),
+            (0x2000, vec![0x10, 0x20, 0x30]),
+        ]);
+        let pattern = [0x10, 0x20, 0x30, 0x40];
+
+        // Act
+        let chunks = || map.iter().map(|(k, v)| (k, v.as_slice()));
+        let exact: Vec<_> = search_hamming(chunks(), &pattern, 0).collect();
+        let approx: Vec<_> = search_hamming(chunks(), &pattern, 2).collect();
+        let empty = search_hamming(chunks(), &[], 2).next();
+
+        // Assert: windows never span the gap between chunks
+        assert_eq!(exact, vec![(0x1000, 0)]);
+        assert_eq!(approx, vec![(0x1000, 0), (0x1005, 2)]);
+        assert_eq!(empty, None);
+    }
 }
@@ -0,0 +1,763 @@
+//! The `signatures` module finds embedded files in data chunks, like `binwalk` does.
+//! Candidates are located by their magic bytes and confirmed by a header validator that
+//! also decodes a short description and, if the header tells, the length of the file.
+//! See [`SignatureTable`] and [`IntelHex::scan_signatures`](crate::IntelHex::scan_signatures).
+
+use crate::bytes::{be_u16, be_u32, be_u64, le_u16, le_u32, le_u64};
+use crate::record::{LARGEST_RECORD_LINE, Record, RecordType};
+use aho_corasick::AhoCorasick;
+use std::collections::HashMap;
+
+/// Decoded header of an embedded file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignatureInfo {
+    /// Human-readable summary of the header, e.g. `PNG image, 32 x 32, 8-bit RGBA`
+    pub description: String,
+    /// Length of the embedded file in bytes, if known from its header
+    pub length: Option<usize>,
+}
+
+/// Header validator of a signature. Gets the data from the magic bytes to the end of the
+/// data chunk and returns `None` if the header is not valid.
+pub type Validator = fn(&[u8]) -> Option<SignatureInfo>;
+
+/// Signature of an embedded file format: its magic bytes and header validator.
+#[derive(Debug, Clone)]
+pub struct Signature {
+    /// Name of the format, e.g. `gzip`
+    pub name: String,
+    /// Bytes every file of the format starts with
+    pub magic: Vec<u8>,
+    /// Validator of the header that follows the magic bytes
+    pub validate: Validator,
+}
+
+impl Signature {
+    /// Creates a signature of the `name` format.
+    #[must_use]
+    pub fn new(name: &str, magic: &[u8], validate: Validator) -> Self {
+        Self {
+            name: name.to_string(),
+            magic: magic.to_vec(),
+            validate,
+        }
+    }
+}
+
+/// Embedded file found by [`IntelHex::scan_signatures`](crate::IntelHex::scan_signatures).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignatureHit {
+    /// Start address of the embedded file
+    pub address: usize,
+    /// Name of the matching signature
+    pub name: String,
+    /// Decoded header of the embedded file
+    pub info: SignatureInfo,
+}
+
+/// Table of signatures to scan data for.
+///
+/// # Example
+/// ```
+/// use intelhexlib::{Signature, SignatureInfo, SignatureTable};
+///
+/// // Built-in signatures extended with a custom one
+/// let mut table = SignatureTable::new();
+/// table.add(Signature::new("Boot header", b"BOOT", |data| {
+///     let version = *data.get(4)?;
+///     Some(SignatureInfo { description: format!("Boot header v{version}"), length: None })
+/// }));
+///
+/// assert!(table.signatures().iter().any(|s| s.name == "gzip"));
+/// assert!(table.signatures().iter().any(|s| s.name == "Boot header"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct SignatureTable {
+    signatures: Vec<Signature>,
+}
+
+impl Default for SignatureTable {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SignatureTable {
+    /// Creates a table of the built-in signatures: gzip, LZMA, zlib, PNG, DER certificate,
+    /// ELF, Intel HEX, UBI and Squashfs.
+    #[must_use]
+    pub fn new() -> Self {
+        let mut table = Self::empty();
+        table.add(Signature::new("gzip", &[0x1F, 0x8B, 0x08], validate_gzip));
+        table.add(Signature::new("LZMA", &[0x5D, 0x00, 0x00], validate_lzma));
+        for magic in [[0x78, 0x01], [0x78, 0x5E], [0x78, 0x9C], [0x78, 0xDA]] {
+            table.add(Signature::new("zlib", &magic, validate_zlib));
+        }
+        table.add(Signature::new("PNG", b"\x89PNG\r\n\x1A\n", validate_png));
+        table.add(Signature::new(
+            "DER certificate",
+            &[0x30, 0x82],
+            validate_der,
+        ));
+        table.add(Signature::new("ELF", b"\x7FELF", validate_elf));
+        table.add(Signature::new(
+            "Intel HEX",
+            b":02000004",
+            validate_intel_hex,
+        ));
+        table.add(Signature::new("Intel HEX", b":10", validate_intel_hex));
+        table.add(Signature::new("UBI", b"UBI#", validate_ubi));
+        table.add(Signature::new("Squashfs", b"hsqs", validate_squashfs));
+        table.add(Signature::new("Squashfs", b"sqsh", validate_squashfs));
+        table
+    }
+
+    /// Creates a table without any signatures.
+    #[must_use]
+    pub const fn empty() -> Self {
+        Self {
+            signatures: Vec::new(),
+        }
+    }
+
+    /// Add a signature to the table.
+    pub fn add(&mut self, signature: Signature) {
+        self.signatures.push(signature);
+    }
+
+    /// Signatures of the table.
+    #[must_use]
+    pub fn signatures(&self) -> &[Signature] {
+        &self.signatures
+    }
+}
+
+/// Find the embedded files of all signatures of the table. Files do not span gaps between
+/// chunks. Hits inside an earlier hit of the same format with a known length (e.g. every
+/// record of an embedded Intel HEX file) are skipped. Lengths that would reach past the end
+/// of the address space are dropped. Returns hits sorted by address.
+pub fn scan_signatures<'a>(
+    iter: impl Iterator<Item = (&'a usize, &'a [u8])>,
+    table: &SignatureTable,
+) -> Vec<SignatureHit> {
+    let magics: Vec<&[u8]> = table
+        .signatures
+        .iter()
+        .map(|s| s.magic.as_slice())
+        .collect();
+    let Ok(ac) = AhoCorasick::new(&magics) else {
+        return Vec::new();
+    };
+
+    let mut hits: Vec<SignatureHit> = Vec::new();
+    // End address of the hits with a known length by format. Candidates are found in
+    // ascending address order, so a candidate below the end lies inside an earlier hit.
+    let mut covered_to: HashMap<&str, usize> = HashMap::new();
+    for (&addr, data) in iter {
+        for m in ac.find_overlapping_iter(data) {
+            let signature = &table.signatures[m.pattern().as_usize()];
+            let address = addr + m.start();
+
+            if covered_to
+                .get(signature.name.as_str())
+                .is_some_and(|&end| address < end)
+            {
+                continue;
+            }
+
+            if let Some(mut info) = (signature.validate)(&data[m.start()..]) {
+                match info.length.and_then(|len| address.checked_add(len)) {
+                    Some(end) => {
+                        let covered = covered_to.entry(&signature.name).or_default();
+                        *covered = (*covered).max(end);
+                    }
+                    None => info.length = None,
+                }
+                hits.push(SignatureHit {
+                    address,
+                    name: signature.name.clone(),
+                    info,
+                });
+            }
+        }
+    }
+
+    hits.sort_by_key(|hit| hit.address);
+    hits
+}
+
+// =============================== HEADER VALIDATORS ===============================
+
+/// gzip member header (RFC 1952). The compressed length is not stored in the header.
+fn validate_gzip(data: &[u8]) -> Option<SignatureInfo> {
+    const FEXTRA: u8 = 0x04;
+    const FNAME: u8 = 0x08;
+
+    let flags = *data.get(3)?;
+    if flags & 0xE0 != 0 {
+        return None;
+    }
+    let mtime = le_u32(data, 4)?;
+    let os = match *data.get(9)? {
+        0 => "FAT",
+        3 => "Unix",
+        7 => "Macintosh",
+        11 => "NTFS",
+        255 => "unknown",
+        _ => return None,
+    };
+
+    let name = if flags & FNAME == 0 {
+        String::new()
+    } else {
+        let mut offset = 10;
+        if flags & FEXTRA != 0 {
+            offset += 2 + le_u16(data, offset)? as usize;
+        }
+        let bytes = data.get(offset..)?.split(|&b| b == 0).next()?;
+        if bytes.len() > 255 || !bytes.iter().all(u8::is_ascii_graphic) {
+            return None;
+        }
+        format!(", original name: \"{}\"", String::from_utf8_lossy(bytes))
+    };
+
+    Some(SignatureInfo {
+        description: format!("gzip compressed data{name}, OS: {os}, mtime: {mtime}"),
+        length: None,
+    })
+}
+
+/// Header of an `.lzma` (LZMA-alone) stream with the default properties (lc=3, lp=0, pb=2).
+fn validate_lzma(data: &[u8]) -> Option<SignatureInfo> {
+    let dict_size = le_u32(data, 1)?;
+    if !(0x1_0000..=0x6000_0000).contains(&dict_size) {
+        return None;
+    }
+    let uncompressed = le_u64(data, 5)?;
+    let uncompressed = match uncompressed {
+        u64::MAX => "unknown".to_string(),
+        1..=0xFFFF_FFFF => format!("{uncompressed} bytes"),
+        _ => return None,
+    };
+
+    Some(SignatureInfo {
+        description: format!(
+            "LZMA compressed data, dictionary size: {dict_size} bytes, uncompressed size: {uncompressed}"
+        ),
+        length: None,
+    })
+}
+
+/// zlib stream header (RFC 1950) followed by a deflate block of a valid type.
+fn validate_zlib(data: &[u8]) -> Option<SignatureInfo> {
+    let header = be_u16(data, 0)?;
+    let block_type = (*data.get(2)? >> 1) & 0b11;
+    if !header.is_multiple_of(31) || block_type == 0b11 {
+        return None;
+    }
+    let level = match data[1] >> 6 {
+        0 => "fastest",
+        1 => "fast",
+        2 => "default",
+        _ => "best",
+    };
+
+    Some(SignatureInfo {
+        description: format!("zlib compressed data, compression level: {level}"),
+        length: None,
+    })
+}
+
+/// PNG image: IHDR chunk and, if the whole image is present, the offset past its IEND chunk.
+fn validate_png(data: &[u8]) -> Option<SignatureInfo> {
+    if be_u32(data, 8)? != 13 || data.get(12..16)? != b"IHDR" {
+        return None;
+    }
+    let width = be_u32(data, 16)?;
+    let height = be_u32(data, 20)?;
+    let depth = *data.get(24)?;
+    let color = match *data.get(25)? {
+        0 => "grayscale",
+        2 => "RGB",
+        3 => "palette",
+        4 => "grayscale with alpha",
+        6 => "RGBA",
+        _ => return None,
+    };
+    if width == 0 || height == 0 {
+        return None;
+    }
+
+    // Walk the chunks up to IEND
+    let mut offset = 8;
+    let mut length = None;
+    while let Some(chunk_len) = be_u32(data, offset) {
+        let chunk_type = data.get(offset + 4..offset + 8)?;
+        if !chunk_type.iter().all(u8::is_ascii_alphabetic) {
+            return None;
+        }
+        let chunk_end = usize::try_from(chunk_len).ok()?.checked_add(12)?;
+        offset = offset.checked_add(chunk_end)?;
+        if chunk_type == b"IEND" {
+            length = (offset <= data.len()).then_some(offset);
+            break;
+        }
+    }
+
+    Some(SignatureInfo {
+        description: format!("PNG image, {width} x {height}, {depth}-bit {color}"),
+        length,
+    })
+}
+
+/// X.509 certificate: a DER sequence holding the `tbsCertificate` sequence that starts
+/// with an explicit version.
+fn validate_der(data: &[u8]) -> Option<SignatureInfo> {
+    let outer = be_u16(data, 2)? as usize;
+    if data.get(4..6)? != [0x30, 0x82] || be_u16(data, 6)? as usize + 4 > outer {
+        return None;
+    }
+    let version = match data.get(8..13)? {
+        [0xA0, 0x03, 0x02, 0x01, v @ 0..=2] => v + 1,
+        _ => return None,
+    };
+
+    Some(SignatureInfo {
+        description: format!("DER certificate, X.509 v{version}"),
+        length: Some(outer + 4),
+    })
+}
+
+/// ELF header. The length ends with the section header table, if there is one.
+fn validate_elf(data: &[u8]) -> Option<SignatureInfo> {
+    let is_64 = match *data.get(4)? {
+        1 => false,
+        2 => true,
+        _ => return None,
+    };
+    let le = match *data.get(5)? {
+        1 => true,
+        2 => false,
+        _ => return None,
+    };
+    let u16_at = |offset| {
+        if le {
+            le_u16(data, offset)
+        } else {
+            be_u16(data, offset)
+        }
+    };
+    let u32_at = |offset| {
+        if le {
+            le_u32(data, offset)
+        } else {
+            be_u32(data, offset)
+        }
+    };
+    let u64_at = |offset| {
+        if le {
+            le_u64(data, offset)
+        } else {
+            be_u64(data, offset)
+        }
+    };
+
+    if *data.get(6)? != 1 || u32_at(20)? != 1 {
+        return None;
+    }
+    let kind = match u16_at(16)? {
+        1 => "relocatable",
+        2 => "executable",
+        3 => "shared object",
+        4 => "core file",
+        _ => return None,
+    };
+    let machine = match u16_at(18)? {
+        3 => "x86".to_string(),
+        8 => "MIPS".to_string(),
+        20 => "PowerPC".to_string(),
+        40 => "ARM".to_string(),
+        62 => "x86-64".to_string(),
+        83 => "AVR".to_string(),
+        94 => "Xtensa".to_string(),
+        183 => "AArch64".to_string(),
+        243 => "RISC-V".to_string(),
+        other => format!("machine {other}"),
+    };
+
+    let (sh_offset, sh_entry_size, sh_count) = if is_64 {
+        (u64_at(40)?, u16_at(58)?, u16_at(60)?)
+    } else {
+        (u64::from(u32_at(32)?), u16_at(46)?, u16_at(48)?)
+    };
+    // Section header table past the address space (e.g. a crafted offset) leaves it unknown
+    let length = (sh_offset != 0)
+        .then(|| usize::try_from(sh_offset).ok())
+        .flatten()
+        .and_then(|offset| {
+            (sh_entry_size as usize)
+                .checked_mul(sh_count as usize)
+                .and_then(|table_size| offset.checked_add(table_size))
+        });
+
+    Some(SignatureInfo {
+        description: format!(
+            "ELF {}-bit {} {kind}, {machine}",
+            if is_64 { 64 } else { 32 },
+            if le { "LSB" } else { "MSB" }
+        ),
+        length,
+    })
+}
+
+/// Intel HEX text: valid records up to and including the end-of-file record. A file
+/// without one (e.g. truncated) ends with its last valid record, if it has at least two.
+/// Its records are then covered by the hit and not walked again from each of them.
+fn validate_intel_hex(data: &[u8]) -> Option<SignatureInfo> {
+    let mut offset = 0;
+    let mut records = 0;
+    let mut data_bytes = 0;
+    let mut complete = false;
+
+    while let Some(rest) = data.get(offset..).filter(|rest| !rest.is_empty()) {
+        // A record line is not longer than the largest record and its line break
+        let line_len = match rest
+            .iter()
+            .take(LARGEST_RECORD_LINE + 2)
+            .position(|&b| b == b'\n')
+        {
+            Some(len) => len,
+            None if rest.len() <= LARGEST_RECORD_LINE + 1 => rest.len(),
+            None => break,
+        };
+        let line = rest[..line_len]
+            .strip_suffix(b"\r")
+            .unwrap_or(&rest[..line_len]);
+        let Ok(record) = Record::parse(line) else {
+            break;
+        };
+
+        records += 1;
+        data_bytes += record.data.len();
+        offset += (line_len + 1).min(rest.len());
+
+        if record.rtype == RecordType::EndOfFile {
+            complete = true;
+            break;
+        }
+    }
+
+    if !complete && records < 2 {
+        return None;
+    }
+    let truncated = if complete {
+        ""
+    } else {
+        ", no end-of-file record"
+    };
+
+    Some(SignatureInfo {
+        description: format!("Intel HEX, {records} records, {data_bytes} data bytes{truncated}"),
+        length: Some(offset),
+    })
+}
+
+/// UBI erase counter header.
+fn validate_ubi(data: &[u8]) -> Option<SignatureInfo> {
+    let version = *data.get(4)?;
+    let erase_count = be_u64(data, 8)?;
+    let vid_offset = be_u32(data, 16)?;
+    let data_offset = be_u32(data, 20)?;
+    if version != 1 || vid_offset == 0 || vid_offset >= data_offset {
+        return None;
+    }
+
+    Some(SignatureInfo {
+        description: format!(
+            "UBI erase count header, version: {version}, EC: {erase_count}, \
+             VID header offset: 0x{vid_offset:X}, data offset: 0x{data_offset:X}"
+        ),
+        length: None,
+    })
+}
+
+/// Squashfs 4.x superblock, little- (`hsqs`) or big-endian (`sqsh`).
+fn validate_squashfs(data: &[u8]) -> Option<SignatureInfo> {
+    let le = data.get(..4)? == b"hsqs";
+    let u16_at = |offset| {
+        if le {
+            le_u16(data, offset)
+        } else {
+            be_u16(data, offset)
+        }
+    };
+    let u32_at = |offset| {
+        if le {
+            le_u32(data, offset)
+        } else {
+            be_u32(data, offset)
+        }
+    };
+    let u64_at = |offset| {
+        if le {
+            le_u64(data, offset)
+        } else {
+            be_u64(data, offset)
+        }
+    };
+
+    let inodes = u32_at(4)?;
+    let block_size = u32_at(12)?;
+    let block_log = u16_at(22)?;
+    let (major, minor) = (u16_at(28)?, u16_at(30)?);
+    if major != 4 || !(12..=20).contains(&block_log) || block_size != 1 << block_log {
+        return None;
+    }
+    let compression = match u16_at(20)? {
+        1 => "gzip",
+        2 => "lzma",
+        3 => "lzo",
+        4 => "xz",
+        5 => "lz4",
+        6 => "zstd",
+        _ => return None,
+    };
+    let size = usize::try_from(u64_at(40)?).ok()?;
+
+    Some(SignatureInfo {
+        description: format!(
+            "Squashfs filesystem, {} endian, version {major}.{minor}, compression: {compression}, \
+             size: {size} bytes, {inodes} inodes, block size: {block_size} bytes",
+            if le { "little" } else { "big" }
+        ),
+        length: Some(size),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Minimal PNG image: signature, IHDR, empty IDAT and IEND chunks (CRCs are not checked)
+    fn png_image() -> Vec<u8> {
+        let mut png = b"\x89PNG\r\n\x1A\n".to_vec();
+        png.extend_from_slice(&[0, 0, 0, 13]);
+        png.extend_from_slice(b"IHDR");
+        png.extend_from_slice(&[0, 0, 0, 32, 0, 0, 0, 16, 8, 6, 0, 0, 0]);
+        png.extend_from_slice(&[0; 4]);
+        png.extend_from_slice(&[0, 0, 0, 0]);
+        png.extend_from_slice(b"IDAT");
+        png.extend_from_slice(&[0; 4]);
+        png.extend_from_slice(&[0, 0, 0, 0]);
+        png.extend_from_slice(b"IEND");
+        png.extend_from_slice(&[0; 4]);
+        png
+    }
+
+    /// 32-bit little-endian ARM executable header with 4 sections at 0x200
+    fn elf_header() -> Vec<u8> {
+        let mut elf = vec![0; 52];
+        elf[..7].copy_from_slice(b"\x7FELF\x01\x01\x01");
+        elf[16..18].copy_from_slice(&2u16.to_le_bytes());
+        elf[18..20].copy_from_slice(&40u16.to_le_bytes());
+        elf[20..24].copy_from_slice(&1u32.to_le_bytes());
+        elf[32..36].copy_from_slice(&0x200u32.to_le_bytes());
+        elf[46..48].copy_from_slice(&40u16.to_le_bytes());
+        elf[48..50].copy_from_slice(&4u16.to_le_bytes());
+        elf
+    }
+
+    #[test]
+    fn test_scan_signatures_builtin() {
+        // Arrange
+        let mut data = vec![0xFF; 0x10];
+        data.extend_from_slice(&[0x1F, 0x8B, 0x08, 0x08, 0, 0, 0, 0, 0, 3]);
+        data.extend_from_slice(b"fw.bin\x00");
+        data.extend_from_slice(&[0x00; 9]);
+        let png_at = data.len();
+        data.extend(png_image());
+        let zlib_at = data.len();
+        data.extend_from_slice(&[0x78, 0x9C, 0x4B, 0x4C]);
+        let elf_at = data.len();
+        data.extend(elf_header());
+        let hex_at = data.len();
+        data.extend_from_slice(b":0200000400FFFB\r\n:0400000001020304F2\r\n:00000001FF\r\n");
+        data.extend_from_slice(&[0x00; 4]);
+        let chunks = [(0x1000, data.as_slice())];
+
+        // Act
+        let hits = scan_signatures(
+            chunks.iter().map(|(addr, data)| (addr, *data)),
+            &SignatureTable::new(),
+        );
+
+        // Assert
+        let found: Vec<(usize, &str, Option<usize>)> = hits
+            .iter()
+            .map(|hit| (hit.address, hit.name.as_str(), hit.info.length))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                (0x1010, "gzip", None),
+                (0x1000 + png_at, "PNG", Some(57)),
+                (0x1000 + zlib_at, "zlib", None),
+                (0x1000 + elf_at, "ELF", Some(0x200 + 4 * 40)),
+                (0x1000 + hex_at, "Intel HEX", Some(51)),
+            ]
+        );
+        assert_eq!(
+            hits[0].info.description,
+            "gzip compressed data, original name: \"fw.bin\", OS: Unix, mtime: 0"
+        );
+        assert_eq!(hits[1].info.description, "PNG image, 32 x 16, 8-bit RGBA");
+        assert_eq!(
+            hits[2].info.description,
+            "zlib compressed data, compression level: default"
+        );
+        assert_eq!(hits[3].info.description, "ELF 32-bit LSB executable, ARM");
+        assert_eq!(
+            hits[4].info.description,
+            "Intel HEX, 3 records, 6 data bytes"
+        );
+    }
+
+    #[test]
+    fn test_scan_signatures_invalid_headers() {
+        // Arrange - magic bytes followed by invalid headers
+        let mut data = Vec::new();
+        data.extend_from_slice(&[0x1F, 0x8B, 0x08, 0xFF, 0, 0, 0, 0, 0, 3]);
+        data.extend_from_slice(&[0x78, 0x9C, 0x06]);
+        data.extend_from_slice(b"\x7FELF\x03\x01\x01");
+        data.extend_from_slice(b":10000000\r\n");
+        data.extend_from_slice(b"hsqs");
+        data.extend_from_slice(&[0x00; 0x60]);
+        let chunks = [(0x0, data.as_slice())];
+
+        // Act
+        let hits = scan_signatures(
+            chunks.iter().map(|(addr, data)| (addr, *data)),
+            &SignatureTable::new(),
+        );
+        let none = scan_signatures(
+            chunks.iter().map(|(addr, data)| (addr, *data)),
+            &SignatureTable::empty(),
+        );
+
+        // Assert
+        assert_eq!(hits, Vec::new());
+        assert_eq!(none, Vec::new());
+    }
+
+    #[test]
+    fn test_validate_squashfs_and_ubi() {
+        // Arrange
+        let mut squashfs = vec![0; 96];
+        squashfs[..4].copy_from_slice(b"hsqs");
+        squashfs[4..8].copy_from_slice(&12u32.to_le_bytes());
+        squashfs[12..16].copy_from_slice(&0x2_0000u32.to_le_bytes());
+        squashfs[20..22].copy_from_slice(&4u16.to_le_bytes());
+        squashfs[22..24].copy_from_slice(&17u16.to_le_bytes());
+        squashfs[28..30].copy_from_slice(&4u16.to_le_bytes());
+        squashfs[40..48].copy_from_slice(&0x1000u64.to_le_bytes());
+        let mut ubi = vec![0; 64];
+        ubi[..5].copy_from_slice(b"UBI#\x01");
+        ubi[16..20].copy_from_slice(&0x800u32.to_be_bytes());
+        ubi[20..24].copy_from_slice(&0x1000u32.to_be_bytes());
+
+        // Act
+        let squashfs_info = validate_squashfs(&squashfs);
+        let ubi_info = validate_ubi(&ubi);
+
+        // Assert
+        assert_eq!(
+            squashfs_info,
+            Some(SignatureInfo {
+                description: "Squashfs filesystem, little endian, version 4.0, compression: xz, \
+                              size: 4096 bytes, 12 inodes, block size: 131072 bytes"
+                    .to_string(),
+                length: Some(0x1000),
+            })
+        );
+        assert_eq!(
+            ubi_info.map(|info| info.description).unwrap_or_default(),
+            "UBI erase count header, version: 1, EC: 0, VID header offset: 0x800, data offset: 0x1000"
+        );
+    }
+
+    #[test]
+    fn test_scan_signatures_out_of_range_lengths() {
+        // Arrange - Squashfs size and ELF section header offset at the end of the address space
+        let mut squashfs = vec![0; 96];
+        squashfs[..4].copy_from_slice(b"hsqs");
+        squashfs[12..16].copy_from_slice(&0x2_0000u32.to_le_bytes());
+        squashfs[20..22].copy_from_slice(&4u16.to_le_bytes());
+        squashfs[22..24].copy_from_slice(&17u16.to_le_bytes());
+        squashfs[28..30].copy_from_slice(&4u16.to_le_bytes());
+        squashfs[40..48].copy_from_slice(&u64::MAX.to_le_bytes());
+        let mut elf = vec![0; 64];
+        elf[..7].copy_from_slice(b"\x7FELF\x02\x01\x01");
+        elf[16..18].copy_from_slice(&2u16.to_le_bytes());
+        elf[18..20].copy_from_slice(&183u16.to_le_bytes());
+        elf[20..24].copy_from_slice(&1u32.to_le_bytes());
+        elf[40..48].copy_from_slice(&u64::MAX.to_le_bytes());
+        elf[58..60].copy_from_slice(&64u16.to_le_bytes());
+        elf[60..62].copy_from_slice(&8u16.to_le_bytes());
+        let mut data = squashfs.clone();
+        data.extend(&squashfs);
+        data.extend(&elf);
+        let chunks = [(0x10, data.as_slice())];
+
+        // Act
+        let hits = scan_signatures(
+            chunks.iter().map(|(addr, data)| (addr, *data)),
+            &SignatureTable::new(),
+        );
+
+        // Assert - the lengths are dropped, so the second Squashfs header is found too
+        let found: Vec<(usize, &str, Option<usize>)> = hits
+            .iter()
+            .map(|hit| (hit.address, hit.name.as_str(), hit.info.length))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                (0x10, "Squashfs", None),
+                (0x70, "Squashfs", None),
+                (0xD0, "ELF", None),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validate_intel_hex_truncated() {
+        // Arrange - data records without an end-of-file record, and a magic without a line end
+        let records = b":10000000000102030405060708090A0B0C0D0E0F78\r\n".repeat(1000);
+        let mut data = records.clone();
+        data.extend_from_slice(b"garbage\r\n");
+        let mut binary = b":10".to_vec();
+        binary.extend_from_slice(&[0x30; 0x1000]);
+
+        // Act
+        let hits = scan_signatures(
+            [(&0x0, data.as_slice()), (&0x10000, binary.as_slice())].into_iter(),
+            &SignatureTable::new(),
+        );
+        let single = validate_intel_hex(b":0400000001020304F2\r\ngarbage");
+
+        // Assert
+        assert_eq!(
+            hits,
+            vec![SignatureHit {
+                address: 0x0,
+                name: "Intel HEX".to_string(),
+                info: SignatureInfo {
+                    description: "Intel HEX, 1000 records, 16000 data bytes, no end-of-file record"
+                        .to_string(),
+                    length: Some(records.len()),
+                },
+            }]
+        );
+        assert_eq!(single, None);
+    }
+}
@@ -0,0 +1,28 @@
+//! The `snapshot` module defines [`Snapshot`] - a saved state of the data of an
+//! [`IntelHex`](crate::IntelHex) instance, taken via
+//! [`IntelHex::snapshot`](crate::IntelHex::snapshot) and brought back via
+//! [`IntelHex::restore`](crate::IntelHex::restore).
+//!
+//! Snapshots share all data chunks with the instance they were taken from, so keeping
+//! several versions of an image (e.g. original, patched, signed) costs only the memory
+//! of the chunks that actually differ between them.
+
+use crate::buffer::ChunkMap;
+use crate::provenance::Provenance;
+
+/// Saved data, start address record and source tags of an `IntelHex` instance.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Snapshot {
+    pub(crate) buffer: ChunkMap,
+    pub(crate) size: usize,
+    pub(crate) start_addr: Option<[u8; 18]>,
+    pub(crate) provenance: Option<Provenance>,
+}
+
+impl Snapshot {
+    /// Size of the saved payload (data bytes).
+    #[must_use]
+    pub const fn size(&self) -> usize {
+        self.size
+    }
+}
@@ -0,0 +1,162 @@
+//! The `stats` module computes byte statistics of data chunks: byte histograms and Shannon
+//! entropy. Entropy close to 8 bits per byte hints at compressed or encrypted data, close
+//! to 0 at erased flash or padding. See [`IntelHex::entropy`](crate::IntelHex::entropy) and
+//! [`IntelHex::histogram`](crate::IntelHex::histogram).
+
+/// Number of occurrences of every byte value.
+pub type Histogram = [usize; 256];
+
+/// Add the bytes of a data chunk starting at `addr` that fall into `[start, end)` to the
+/// histogram.
+pub fn add_to_histogram(
+    histogram: &mut Histogram,
+    addr: usize,
+    data: &[u8],
+    start: usize,
+    end: usize,
+) {
+    let from = start.saturating_sub(addr).min(data.len());
+    let to = end.saturating_sub(addr).min(data.len());
+    for &b in &data[from..to.max(from)] {
+        histogram[b as usize] += 1;
+    }
+}
+
+/// Shannon entropy of a histogram in bits per byte, from 0.0 (one byte value only)
+/// to 8.0 (all byte values equally frequent). Returns 0.0 for an empty histogram.
+#[allow(clippy::cast_precision_loss)]
+pub fn shannon_entropy(histogram: &Histogram) -> f64 {
+    let total: usize = histogram.iter().sum();
+    if total == 0 {
+        return 0.0;
+    }
+
+    let total = total as f64;
+    let entropy: f64 = histogram
+        .iter()
+        .filter(|&&count| count > 0)
+        .map(|&count| {
+            let p = count as f64 / total;
+            -p * p.log2()
+        })
+        .sum();
+
+    // Avoid reporting -0.0 for a single byte value
+    entropy.max(0.0)
+}
+
+/// Entropy of every block of `block_size` bytes that holds data. Blocks are aligned to
+/// multiples of `block_size` and only the bytes present in a block are taken into account.
+/// Returns `(block start address, entropy)` sorted by address. `block_size` must not be 0.
+pub fn block_entropy<'a>(
+    iter: impl Iterator<Item = (&'a usize, &'a [u8])>,
+    block_size: usize,
+) -> Vec<(usize, f64)> {
+    let mut blocks = Vec::new();
+    let mut current: Option<(usize, Histogram)> = None;
+
+    for (&addr, data) in iter {
+        let mut offset = 0;
+        while offset < data.len() {
+            let byte_addr = addr + offset;
+            let block_start = byte_addr - byte_addr % block_size;
+            let block_end = block_start.saturating_add(block_size);
+
+            // Flush the previous block once the data moves past it
+            if current
+                .as_ref()
+                .is_some_and(|(start, _)| *start != block_start)
+                && let Some((start, histogram)) = current.take()
+            {
+                blocks.push((start, shannon_entropy(&histogram)));
+            }
+            let (_, histogram) = current.get_or_insert((block_start, [0; 256]));
+
+            let take = (block_end - byte_addr).min(data.len() - offset);
+            for &b in &data[offset..offset + take] {
+                histogram[b as usize] += 1;
+            }
+            offset += take;
+        }
+    }
+
+    if let Some((start, histogram)) = current {
+        blocks.push((start, shannon_entropy(&histogram)));
+    }
+
+    blocks
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_shannon_entropy() {
+        // Arrange
+        let uniform = [4; 256];
+        let mut single = [0; 256];
+        single[0xFF] = 100;
+        let mut two = [0; 256];
+        two[0x00] = 8;
+        two[0x01] = 8;
+
+        // Act
+        let entropies = [
+            shannon_entropy(&uniform),
+            shannon_entropy(&single),
+            shannon_entropy(&two),
+            shannon_entropy(&[0; 256]),
+        ];
+
+        // Assert
+        assert!((entropies[0] - 8.0).abs() < 1e-9);
+        assert!(entropies[1].abs() < 1e-9);
+        assert!((entropies[2] - 1.0).abs() < 1e-9);
+        assert!(entropies[3].abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_block_entropy() {
+        // Arrange
+        let counter: Vec<u8> = (0..=255).collect();
+        let erased = vec![0xFF; 0x80];
+        let tail = vec![0x00, 0x01];
+        let chunks = [
+            (0x100, counter.as_slice()),
+            (0x200, erased.as_slice()),
+            (0x37E, tail.as_slice()),
+        ];
+
+        // Act
+        let blocks = block_entropy(chunks.iter().map(|(addr, data)| (addr, *data)), 0x100);
+        let small = block_entropy(chunks.iter().map(|(addr, data)| (addr, *data)), 0x10);
+
+        // Assert
+        assert_eq!(
+            blocks.iter().map(|(addr, _)| *addr).collect::<Vec<_>>(),
+            vec![0x100, 0x200, 0x300]
+        );
+        assert!((blocks[0].1 - 8.0).abs() < 1e-9);
+        assert!(blocks[1].1.abs() < 1e-9);
+        assert!((blocks[2].1 - 1.0).abs() < 1e-9);
+        assert_eq!(small.len(), 16 + 8 + 1);
+        assert!((small[0].1 - 4.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_add_to_histogram() {
+        // Arrange
+        let mut histogram = [0; 256];
+
+        // Act
+        add_to_histogram(&mut histogram, 0x10, &[1, 2, 2, 3, 3, 3], 0x11, 0x15);
+        add_to_histogram(&mut histogram, 0x10, &[1, 2, 3], 0x20, 0x30);
+
+        // Assert
+        assert_eq!(histogram[1], 0);
+        assert_eq!(histogram[2], 2);
+        assert_eq!(histogram[3], 2);
+        assert_eq!(histogram.iter().sum::<usize>(), 4);
+    }
+}
@@ -0,0 +1,326 @@
+//! The `strings` module extracts printable text runs from data chunks, like the `strings`
+//! tool does. Runs are searched in ASCII, UTF-8 and UTF-16 (little- and big-endian) and do
+//! not span gaps between chunks. See [`IntelHex::strings`](crate::IntelHex::strings).
+
+use std::fmt;
+use std::str::FromStr;
+
+/// Text encoding of an extracted string.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StringEncoding {
+    /// Printable 7-bit ASCII characters and tabs
+    Ascii,
+    /// Printable UTF-8 characters. If ASCII strings are extracted as well, only runs with
+    /// at least one multibyte character are reported as UTF-8.
+    Utf8,
+    /// Printable UTF-16 little-endian characters below U+0800 (Latin, Greek, Cyrillic,
+    /// Hebrew, Arabic, ...). Higher code points would make most random data look like text.
+    Utf16Le,
+    /// Same as [`StringEncoding::Utf16Le`], big-endian
+    Utf16Be,
+}
+
+impl StringEncoding {
+    /// All supported encodings.
+    pub const ALL: [Self; 4] = [Self::Ascii, Self::Utf8, Self::Utf16Le, Self::Utf16Be];
+}
+
+impl fmt::Display for StringEncoding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Ascii => "ASCII",
+            Self::Utf8 => "UTF-8",
+            Self::Utf16Le => "UTF-16LE",
+            Self::Utf16Be => "UTF-16BE",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// Parses `ascii`, `utf8`, `utf16le` or `utf16be` (case-insensitive, dashes are optional).
+impl FromStr for StringEncoding {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().replace('-', "").as_str() {
+            "ascii" => Ok(Self::Ascii),
+            "utf8" => Ok(Self::Utf8),
+            "utf16le" => Ok(Self::Utf16Le),
+            "utf16be" => Ok(Self::Utf16Be),
+            _ => Err(format!("Unknown string encoding: {s}")),
+        }
+    }
+}
+
+/// Run of characters as `(start, end, text)` with offsets in the data
+type Run = (usize, usize, String);
+
+/// Find all strings of at least `min_len` characters in a data chunk starting at `addr`.
+/// Returns `(address, encoding, text)` sorted by address.
+pub fn find_strings(
+    addr: usize,
+    data: &[u8],
+    min_len: usize,
+    encodings: &[StringEncoding],
+) -> Vec<(usize, StringEncoding, String)> {
+    let min_len = min_len.max(1);
+    let mut found = Vec::new();
+
+    let ascii = encodings.contains(&StringEncoding::Ascii);
+    let utf8_runs = if encodings.contains(&StringEncoding::Utf8) {
+        // Pure ASCII runs are left to the ASCII scan, if any
+        let mut runs = find_utf8(data, min_len);
+        runs.retain(|(_, _, text)| !(ascii && text.is_ascii()));
+        runs
+    } else {
+        Vec::new()
+    };
+
+    if ascii {
+        // Skip ASCII runs that are part of a UTF-8 run. Both are sorted and disjoint,
+        // so a single sweep over the UTF-8 runs suffices.
+        let mut utf8 = utf8_runs.iter().peekable();
+        for (start, _, text) in find_ascii(data, min_len) {
+            while utf8.next_if(|(_, end, _)| *end <= start).is_some() {}
+            let inside_utf8 = utf8
+                .peek()
+                .is_some_and(|(utf8_start, _, _)| *utf8_start <= start);
+            if !inside_utf8 {
+                found.push((addr + start, StringEncoding::Ascii, text));
+            }
+        }
+    }
+    for (start, _, text) in utf8_runs {
+        found.push((addr + start, StringEncoding::Utf8, text));
+    }
+
+    // A UTF-16 string shifted by one byte mostly decodes in the other byte order too.
+    // Keep the longer of overlapping runs, little-endian if they are equally long.
+    let le_runs = if encodings.contains(&StringEncoding::Utf16Le) {
+        find_utf16(data, min_len, false)
+    } else {
+        Vec::new()
+    };
+    let be_runs = if encodings.contains(&StringEncoding::Utf16Be) {
+        find_utf16(data, min_len, true)
+    } else {
+        Vec::new()
+    };
+    for run in without_longer_overlaps(&le_runs, &be_runs, false) {
+        found.push((addr + run.0, StringEncoding::Utf16Le, run.2.clone()));
+    }
+    for run in without_longer_overlaps(&be_runs, &le_runs, true) {
+        found.push((addr + run.0, StringEncoding::Utf16Be, run.2.clone()));
+    }
+
+    found.sort_by_key(|(address, _, _)| *address);
+    found
+}
+
+/// Runs of `runs` that no run of `others` overlaps with more characters (or as many, if
+/// `ties_lose`). Both lists have to be sorted and disjoint, so each run of `others` is
+/// passed once for every run of `runs` it overlaps.
+fn without_longer_overlaps<'a>(
+    runs: &'a [Run],
+    others: &'a [Run],
+    ties_lose: bool,
+) -> impl Iterator<Item = &'a Run> {
+    let chars = |run: &Run| run.2.chars().count();
+    let mut first = 0;
+
+    runs.iter().filter(move |run| {
+        // Skip the runs ending before this one, they end before all following runs too
+        while others.get(first).is_some_and(|other| other.1 <= run.0) {
+            first += 1;
+        }
+        !others[first..]
+            .iter()
+            .take_while(|other| other.0 < run.1)
+            .any(|other| chars(other) > chars(run) || (ties_lose && chars(other) == chars(run)))
+    })
+}
+
+/// Printable ASCII character (or a tab).
+const fn is_printable_ascii(b: u8) -> bool {
+    matches!(b, 0x20..=0x7E | b'\t')
+}
+
+/// Printable character of any script (or a tab).
+fn is_printable(c: char) -> bool {
+    !c.is_control() || c == '\t'
+}
+
+/// Runs of printable ASCII characters.
+fn find_ascii(data: &[u8], min_len: usize) -> Vec<Run> {
+    let mut runs = Vec::new();
+    let mut start = 0;
+
+    for (i, &b) in data.iter().chain(std::iter::once(&0)).enumerate() {
+        if !is_printable_ascii(b) {
+            if i - start >= min_len {
+                let text = data[start..i].iter().map(|&b| b as char).collect();
+                runs.push((start, i, text));
+            }
+            start = i + 1;
+        }
+    }
+
+    runs
+}
+
+/// Runs of printable UTF-8 characters.
+fn find_utf8(data: &[u8], min_len: usize) -> Vec<Run> {
+    let mut runs = Vec::new();
+    let mut start = 0;
+    let mut text = String::new();
+    let mut chars = 0;
+    let mut i = 0;
+
+    while i <= data.len() {
+        // Decode the character at `i` (the longest valid prefix is at most 4 bytes long)
+        let decoded = data.get(i..data.len().min(i + 4)).and_then(|rest| {
+            let valid = match std::str::from_utf8(rest) {
+                Ok(s) => s,
+                Err(e) => std::str::from_utf8(&rest[..e.valid_up_to()]).unwrap_or_default(),
+            };
+            valid.chars().next().filter(|&c| is_printable(c))
+        });
+
+        if let Some(c) = decoded {
+            text.push(c);
+            chars += 1;
+            i += c.len_utf8();
+            continue;
+        }
+
+        if chars >= min_len {
+            runs.push((start, i, std::mem::take(&mut text)));
+        }
+        text.clear();
+        chars = 0;
+        i += 1;
+        start = i;
+    }
+
+    runs
+}
+
+/// Runs of printable UTF-16 characters below U+0800. Runs may start at odd offsets.
+fn find_utf16(data: &[u8], min_len: usize, big_endian: bool) -> Vec<Run> {
+    let decode = |i: usize| -> Option<char> {
+        let unit = [*data.get(i)?, *data.get(i + 1)?];
+        let unit = if big_endian {
+            u16::from_be_bytes(unit)
+        } else {
+            u16::from_le_bytes(unit)
+        };
+        char::from_u32(u32::from(unit)).filter(|&c| (c as u32) < 0x800 && is_printable(c))
+    };
+
+    let mut runs = Vec::new();
+    let mut i = 0;
+
+    while i + 1 < data.len() {
+        let mut end = i;
+        let mut text = String::new();
+        while let Some(c) = decode(end) {
+            text.push(c);
+            end += 2;
+        }
+
+        if text.chars().count() >= min_len {
+            runs.push((i, end, text));
+            i = end;
+        } else {
+            i += 1;
+        }
+    }
+
+    runs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_strings_all_encodings() {
+        // Arrange
+        let mut data = vec![0x00, 0x01];
+        data.extend_from_slice(b"Hello\tworld\x00");
+        data.extend_from_slice("Grüße".as_bytes());
+        data.extend_from_slice(&[0x00, 0x00]);
+        data.extend("Flash".encode_utf16().flat_map(u16::to_le_bytes));
+        data.extend_from_slice(&[0x00, 0x00, 0x80]);
+        data.extend("Boot".encode_utf16().flat_map(u16::to_be_bytes));
+
+        // Act
+        let found = find_strings(0x1000, &data, 4, &StringEncoding::ALL);
+        let utf8_only = find_strings(0x1000, &data, 4, &[StringEncoding::Utf8]);
+
+        // Assert
+        assert_eq!(
+            found,
+            vec![
+                (0x1002, StringEncoding::Ascii, "Hello\tworld".to_string()),
+                (0x100E, StringEncoding::Utf8, "Grüße".to_string()),
+                (0x1017, StringEncoding::Utf16Le, "Flash".to_string()),
+                (0x1024, StringEncoding::Utf16Be, "Boot".to_string()),
+            ]
+        );
+        assert_eq!(
+            utf8_only[0],
+            (0x1002, StringEncoding::Utf8, "Hello\tworld".to_string())
+        );
+        assert_eq!(
+            utf8_only[1],
+            (0x100E, StringEncoding::Utf8, "Grüße".to_string())
+        );
+    }
+
+    #[test]
+    fn test_find_strings_min_len_and_parse() {
+        // Arrange
+        let data = b"abc\x00abcd\x00";
+
+        // Act
+        let found = find_strings(0x0, data, 4, &[StringEncoding::Ascii]);
+        let parsed: Vec<Result<StringEncoding, String>> = ["ASCII", "utf-8", "Utf16LE", "utf32"]
+            .iter()
+            .map(|s| s.parse())
+            .collect();
+
+        // Assert
+        assert_eq!(
+            found,
+            vec![(0x4, StringEncoding::Ascii, "abcd".to_string())]
+        );
+        assert_eq!(parsed[0], Ok(StringEncoding::Ascii));
+        assert_eq!(parsed[1], Ok(StringEncoding::Utf8));
+        assert_eq!(parsed[2], Ok(StringEncoding::Utf16Le));
+        assert_eq!(parsed[3], Err("Unknown string encoding: utf32".to_string()));
+    }
+
+    #[test]
+    fn test_find_strings_skips_overlapping_runs() {
+        // Arrange
+        let mut data = Vec::new();
+        data.extend_from_slice("abcd€efgh\x00abcd€efgh\x00wxyz\x00".as_bytes());
+        data.extend_from_slice(&[0x00, 0x54, 0x00, 0x65, 0x00, 0x78, 0x00, 0x74, 0x00, 0x00]);
+
+        // Act
+        let found = find_strings(0x0, &data, 4, &StringEncoding::ALL);
+
+        // Assert
+        // The ASCII runs inside the UTF-8 runs and the big-endian reading of "Text" are skipped
+        assert_eq!(
+            found,
+            vec![
+                (0x00, StringEncoding::Utf8, "abcd€efgh".to_string()),
+                (0x0C, StringEncoding::Utf8, "abcd€efgh".to_string()),
+                (0x18, StringEncoding::Ascii, "wxyz".to_string()),
+                (0x1E, StringEncoding::Utf16Le, "Text".to_string()),
+            ]
+        );
+    }
+}
@@ -0,0 +1,720 @@
+//! The `symbols` module defines [`SymbolTable`] - an address index of named symbols with
+//! their sizes, loaded from linker map files (GNU ld, IAR ILINK, Keil / ARM armlink) or
+//! from the `.symtab` section of ELF files.
+
+use crate::bytes::{be_u16, be_u32, be_u64, le_u16, le_u32, le_u64};
+use crate::error::{IntelHexError, IntelHexErrorKind};
+use std::path::Path;
+
+/// Named address of a function or variable.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Symbol {
+    /// Name of the symbol, e.g. `g_calib_table`
+    pub name: String,
+    /// Start address (without the Thumb bit for ARM functions)
+    pub address: usize,
+    /// Size in bytes, 0 if unknown
+    pub size: usize,
+}
+
+impl Symbol {
+    /// Check if the address is within the symbol. A symbol of unknown size only
+    /// contains its start address.
+    #[must_use]
+    pub const fn contains(&self, address: usize) -> bool {
+        self.address <= address
+            && address - self.address < if self.size == 0 { 1 } else { self.size }
+    }
+}
+
+/// Address index of the symbols of a firmware image.
+///
+/// Loaded with [`SymbolTable::from_file`], it names the symbol containing an address
+/// ([`SymbolTable::symbol_at`]) or finds a symbol by name ([`SymbolTable::find`]).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SymbolTable {
+    /// Symbols sorted by their address and name
+    symbols: Vec<Symbol>,
+    /// Size of the largest symbol. Bounds the search for the symbol containing an address.
+    max_size: usize,
+}
+
+impl SymbolTable {
+    /// Creates empty `SymbolTable` instance.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            symbols: Vec::new(),
+            max_size: 0,
+        }
+    }
+
+    /// Load symbols from an ELF file or a linker map file. The format is detected
+    /// from the content of the file.
+    ///
+    /// # Errors
+    /// Returns an error if the file cannot be read or its format is not supported.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, IntelHexError> {
+        let data = std::fs::read(path)?;
+        if data.starts_with(b"\x7FELF") {
+            Self::from_elf(&data)
+        } else {
+            Self::from_map(&String::from_utf8_lossy(&data))
+        }
+    }
+
+    /// Load the function, object and untyped symbols of the `.symtab` section of an ELF file.
+    /// Undefined, absolute and ARM mapping symbols (`$t`, `$d`, ...) are skipped.
+    ///
+    /// # Errors
+    /// Returns an error if the ELF file is malformed or has no symbol table.
+    pub fn from_elf(data: &[u8]) -> Result<Self, IntelHexError> {
+        let symbols = parse_elf(data).map_err(IntelHexError::ParseSymbolsError)?;
+        Ok(Self::from_symbols(symbols))
+    }
+
+    /// Load symbols from a GNU ld, IAR ILINK or Keil / ARM armlink map file. Sizes missing
+    /// in GNU ld map files are derived from the next symbol in the same input section.
+    ///
+    /// # Errors
+    /// Returns an error if the text is not a supported map file.
+    ///
+    /// # Example
+    /// ```
+    /// use intelhexlib::SymbolTable;
+    ///
+    /// let map = "\
+    /// Linker script and memory map
+    ///
+    ///  .data.g_calib_table
+    ///                 0x20000000       0x40 build/calib.o
+    ///                 0x20000000                g_calib_table
+    /// ";
+    /// let symbols = SymbolTable::from_map(map).unwrap();
+    ///
+    /// let symbol = symbols.find("g_calib_table").unwrap();
+    /// assert_eq!((symbol.address, symbol.size), (0x2000_0000, 0x40));
+    /// assert_eq!(symbols.symbol_at(0x2000_0010), Some(symbol));
+    /// ```
+    pub fn from_map(text: &str) -> Result<Self, IntelHexError> {
+        let symbols = if let Some(start) = text.find("Image Symbol Table") {
+            parse_keil_map(&text[start..])
+        } else if let Some(start) = text.find("ENTRY LIST") {
+            parse_iar_map(&text[start..])
+        } else if let Some(start) = text.find("Linker script and memory map") {
+            parse_gnu_map(&text[start..])
+        } else {
+            return Err(IntelHexError::ParseSymbolsError(
+                IntelHexErrorKind::SymbolFormatUnknown,
+            ));
+        };
+        Ok(Self::from_symbols(symbols))
+    }
+
+    /// Build the table from symbols in any order. Duplicates (same name and address)
+    /// are merged, keeping the larger size.
+    fn from_symbols(mut symbols: Vec<Symbol>) -> Self {
+        symbols.sort_by(|a, b| (a.address, &a.name, b.size).cmp(&(b.address, &b.name, a.size)));
+        symbols.dedup_by(|next, kept| next.address == kept.address && next.name == kept.name);
+        let max_size = symbols.iter().map(|s| s.size).max().unwrap_or(0);
+        Self { symbols, max_size }
+    }
+
+    /// Add a symbol.
+    pub fn add(&mut self, symbol: Symbol) {
+        let pos = self
+            .symbols
+            .partition_point(|s| (s.address, &s.name) < (symbol.address, &symbol.name));
+        self.max_size = self.max_size.max(symbol.size);
+        self.symbols.insert(pos, symbol);
+    }
+
+    /// All symbols sorted by their address.
+    #[must_use]
+    pub fn symbols(&self) -> &[Symbol] {
+        &self.symbols
+    }
+
+    /// Number of symbols.
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.symbols.len()
+    }
+
+    /// Returns true if there are no symbols.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.symbols.is_empty()
+    }
+
+    /// First symbol with the given name.
+    #[must_use]
+    pub fn find(&self, name: &str) -> Option<&Symbol> {
+        self.symbols.iter().find(|s| s.name == name)
+    }
+
+    /// Symbol that contains the address. If symbols overlap, the one starting closest
+    /// to the address is returned.
+    #[must_use]
+    pub fn symbol_at(&self, address: usize) -> Option<&Symbol> {
+        let pos = self.symbols.partition_point(|s| s.address <= address);
+        self.symbols[..pos]
+            .iter()
+            .rev()
+            .take_while(|s| address - s.address < self.max_size.max(1))
+            .find(|s| s.contains(address))
+    }
+
+    /// Symbols starting in the address range `[start, end)`, sorted by their address.
+    #[must_use]
+    pub fn starting_in(&self, start: usize, end: usize) -> &[Symbol] {
+        let first = self.symbols.partition_point(|s| s.address < start);
+        let last = self.symbols.partition_point(|s| s.address < end);
+        &self.symbols[first..last.max(first)]
+    }
+}
+
+// ================================== MAP FILES ==================================
+
+/// Parse a hexadecimal number with optional `0x` prefix and `'` digit separators (IAR)
+fn parse_hex(token: &str) -> Option<usize> {
+    let digits = token
+        .strip_prefix("0x")
+        .or_else(|| token.strip_prefix("0X"))?;
+    usize::from_str_radix(&digits.replace('\'', ""), 16).ok()
+}
+
+/// Name of a symbol in a map file: no section names, fill patterns or assignments
+fn is_symbol_name(token: &str) -> bool {
+    !token.starts_with(['.', '*', '(', '[', '-'])
+        && !token.contains('=')
+        && parse_hex(token).is_none()
+}
+
+/// GNU ld map file, from the "Linker script and memory map" section on. Symbols are listed
+/// as `address name` lines after the input section (`name address size object`) they belong
+/// to. Their size is the distance to the next symbol or the end of the section.
+fn parse_gnu_map(text: &str) -> Vec<Symbol> {
+    // Name, address and end of the section the symbol is in
+    let mut found: Vec<(String, usize, usize)> = Vec::new();
+    let mut section_end = 0;
+
+    for line in text.lines() {
+        let tokens: Vec<&str> = line.split_whitespace().collect();
+        let numbers: Vec<Option<usize>> = tokens.iter().take(3).map(|t| parse_hex(t)).collect();
+        match (tokens.as_slice(), numbers.as_slice()) {
+            // Input or output section, the name may be wrapped to the previous line
+            (_, [Some(addr), Some(size), ..] | [_, Some(addr), Some(size), ..]) => {
+                section_end = addr.saturating_add(*size);
+            }
+            ([_, name], [Some(addr), _]) if is_symbol_name(name) => {
+                found.push(((*name).to_string(), *addr, section_end));
+            }
+            _ => {}
+        }
+    }
+
+    // Size up to the next symbol with a higher address, within the section
+    found.sort_by_key(|(_, addr, _)| *addr);
+    let mut symbols = Vec::with_capacity(found.len());
+    for (i, (name, address, end)) in found.iter().enumerate() {
+        let next = found[i..]
+            .iter()
+            .map(|(_, addr, _)| *addr)
+            .find(|addr| addr > address)
+            .unwrap_or(usize::MAX);
+        symbols.push(Symbol {
+            name: name.clone(),
+            address: *address,
+            size: next.min(*end).saturating_sub(*address),
+        });
+    }
+    symbols
+}
+
+/// IAR ILINK map file, from the "ENTRY LIST" section on. Entries are listed as
+/// `name address [size] type ...`; long names are wrapped to their own line. Code
+/// addresses are stored with the Thumb bit set.
+fn parse_iar_map(text: &str) -> Vec<Symbol> {
+    let mut symbols = Vec::new();
+    let mut wrapped_name: Option<&str> = None;
+
+    for line in text.lines() {
+        let tokens: Vec<&str> = line.split_whitespace().collect();
+        let (name, rest) = match tokens.as_slice() {
+            [name] if is_symbol_name(name) => {
+                wrapped_name = Some(*name);
+                continue;
+            }
+            [first, ..] if parse_hex(first).is_some() => match wrapped_name.take() {
+                Some(name) => (name, &tokens[..]),
+                None => continue,
+            },
+            [name, rest @ ..] if is_symbol_name(name) => (*name, rest),
+            _ => continue,
+        };
+        wrapped_name = None;
+
+        let Some(address) = rest.first().and_then(|t| parse_hex(t)) else {
+            continue;
+        };
+        let (size, kind) = rest
+            .get(1)
+            .and_then(|t| parse_hex(t))
+            .map_or_else(|| (0, rest.get(1)), |size| (size, rest.get(2)));
+        let address = if kind == Some(&"Code") {
+            address & !1
+        } else {
+            address
+        };
+
+        symbols.push(Symbol {
+            name: name.to_string(),
+            address,
+            size,
+        });
+    }
+    symbols
+}
+
+/// Keil / ARM armlink map file, from the "Image Symbol Table" on. Symbols are listed as
+/// `name value type size object`. Numbers and sections are skipped; Thumb code addresses
+/// are stored with the Thumb bit set.
+fn parse_keil_map(text: &str) -> Vec<Symbol> {
+    // The memory map of the image follows the symbol table
+    let text = text
+        .find("Memory Map of the image")
+        .map_or(text, |end| &text[..end]);
+
+    let mut symbols = Vec::new();
+    for line in text.lines() {
+        let tokens: Vec<&str> = line.split_whitespace().collect();
+        let Some((name, value, rest)) = (match tokens.as_slice() {
+            [name, value, rest @ ..] if is_symbol_name(name) => {
+                parse_hex(value).map(|value| (*name, value, rest))
+            }
+            _ => None,
+        }) else {
+            continue;
+        };
+
+        let (address, size) = match rest {
+            ["Thumb", "Code", size, ..] => (value & !1, size),
+            ["ARM", "Code", size, ..] | ["Data", size, ..] => (value, size),
+            _ => continue,
+        };
+        let Ok(size) = size.parse() else {
+            continue;
+        };
+
+        symbols.push(Symbol {
+            name: name.to_string(),
+            address,
+            size,
+        });
+    }
+    symbols
+}
+
+// ================================== ELF FILES ==================================
+
+/// Section type of a symbol table
+const SHT_SYMTAB: u32 = 2;
+/// Machine type of 32-bit ARM
+const EM_ARM: u16 = 40;
+
+/// Reader of ELF header fields in the byte order of the file
+struct ElfReader<'a> {
+    data: &'a [u8],
+    le: bool,
+    is_64: bool,
+}
+
+impl ElfReader<'_> {
+    fn u16(&self, offset: usize) -> Result<u16, IntelHexErrorKind> {
+        let value = if self.le {
+            le_u16(self.data, offset)
+        } else {
+            be_u16(self.data, offset)
+        };
+        value.ok_or(IntelHexErrorKind::ElfMalformed)
+    }
+
+    fn u32(&self, offset: usize) -> Result<u32, IntelHexErrorKind> {
+        let value = if self.le {
+            le_u32(self.data, offset)
+        } else {
+            be_u32(self.data, offset)
+        };
+        value.ok_or(IntelHexErrorKind::ElfMalformed)
+    }
+
+    /// Address-sized field: 4 bytes in 32-bit files, 8 bytes in 64-bit files
+    fn addr(&self, offset: usize) -> Result<usize, IntelHexErrorKind> {
+        let value = match (self.is_64, self.le) {
+            (false, _) => u64::from(self.u32(offset)?),
+            (true, true) => le_u64(self.data, offset).ok_or(IntelHexErrorKind::ElfMalformed)?,
+            (true, false) => be_u64(self.data, offset).ok_or(IntelHexErrorKind::ElfMalformed)?,
+        };
+        usize::try_from(value).map_err(|_| IntelHexErrorKind::ElfMalformed)
+    }
+}
+
+/// Offset of a field `delta` bytes after `base`, an error if it is past the address space
+fn field(base: usize, delta: usize) -> Result<usize, IntelHexErrorKind> {
+    base.checked_add(delta)
+        .ok_or(IntelHexErrorKind::ElfMalformed)
+}
+
+fn parse_elf(data: &[u8]) -> Result<Vec<Symbol>, IntelHexErrorKind> {
+    let elf = ElfReader {
+        data,
+        le: data.get(5) != Some(&2),
+        is_64: data.get(4) == Some(&2),
+    };
+    let machine = elf.u16(18)?;
+
+    // Section header table
+    let (sh_offset, sh_entry_size, sh_count) = if elf.is_64 {
+        (elf.addr(40)?, elf.u16(58)?, elf.u16(60)?)
+    } else {
+        (elf.addr(32)?, elf.u16(46)?, elf.u16(48)?)
+    };
+    let section = |index: usize| {
+        index
+            .checked_mul(sh_entry_size as usize)
+            .ok_or(IntelHexErrorKind::ElfMalformed)
+            .and_then(|offset| field(sh_offset, offset))
+    };
+
+    // Offset, size and linked section of a section
+    let section_info = |header: usize| -> Result<(usize, usize, usize), IntelHexErrorKind> {
+        if elf.is_64 {
+            Ok((
+                elf.addr(field(header, 24)?)?,
+                elf.addr(field(header, 32)?)?,
+                elf.u32(field(header, 40)?)? as usize,
+            ))
+        } else {
+            Ok((
+                elf.addr(field(header, 16)?)?,
+                elf.addr(field(header, 20)?)?,
+                elf.u32(field(header, 24)?)? as usize,
+            ))
+        }
+    };
+
+    let mut symtab = None;
+    for index in 0..sh_count as usize {
+        if elf.u32(field(section(index)?, 4)?)? == SHT_SYMTAB {
+            symtab = Some(section(index)?);
+            break;
+        }
+    }
+    let symtab = symtab.ok_or(IntelHexErrorKind::ElfNoSymbolTable)?;
+    let (sym_offset, sym_size, strtab_index) = section_info(symtab)?;
+    let (str_offset, str_size, _) = section_info(section(strtab_index)?)?;
+    let strtab = data
+        .get(str_offset..field(str_offset, str_size)?)
+        .ok_or(IntelHexErrorKind::ElfMalformed)?;
+
+    let entry_size = if elf.is_64 { 24 } else { 16 };
+    let mut symbols = Vec::new();
+    for entry in (sym_offset..field(sym_offset, sym_size)?).step_by(entry_size) {
+        let (name, info, shndx, value, size) = if elf.is_64 {
+            (
+                elf.u32(entry)? as usize,
+                *data
+                    .get(field(entry, 4)?)
+                    .ok_or(IntelHexErrorKind::ElfMalformed)?,
+                elf.u16(field(entry, 6)?)?,
+                elf.addr(field(entry, 8)?)?,
+                elf.addr(field(entry, 16)?)?,
+            )
+        } else {
+            (
+                elf.u32(entry)? as usize,
+                *data
+                    .get(field(entry, 12)?)
+                    .ok_or(IntelHexErrorKind::ElfMalformed)?,
+                elf.u16(field(entry, 14)?)?,
+                elf.addr(field(entry, 4)?)?,
+                elf.addr(field(entry, 8)?)?,
+            )
+        };
+
+        // Untyped, object and function symbols defined in a section
+        let kind = info & 0x0F;
+        if kind > 2 || shndx == 0 || shndx >= 0xFF00 {
+            continue;
+        }
+        let name = strtab
+            .get(name..)
+            .and_then(|s| s.split(|&b| b == 0).next())
+            .map(String::from_utf8_lossy)
+            .unwrap_or_default();
+        if name.is_empty() || name.starts_with('$') {
+            continue;
+        }
+
+        let address = if kind == 2 && machine == EM_ARM {
+            value & !1
+        } else {
+            value
+        };
+        symbols.push(Symbol {
+            name: name.into_owned(),
+            address,
+            size,
+        });
+    }
+    Ok(symbols)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 32-bit little-endian ARM ELF file with a symbol table of the given
+    /// `(name, value, size, info)` symbols
+    fn elf(symbols: &[(&str, u32, u32, u8)]) -> Vec<u8> {
+        let mut strtab = vec![0u8];
+        let mut symtab = vec![0u8; 16]; // null symbol
+        for (name, value, size, info) in symbols {
+            let name_offset = u32::try_from(strtab.len()).unwrap_or_default();
+            strtab.extend_from_slice(name.as_bytes());
+            strtab.push(0);
+            symtab.extend_from_slice(&name_offset.to_le_bytes());
+            symtab.extend_from_slice(&value.to_le_bytes());
+            symtab.extend_from_slice(&size.to_le_bytes());
+            symtab.extend_from_slice(&[*info, 0, 1, 0]); // section 1
+        }
+
+        let mut data = vec![0u8; 52];
+        data[..7].copy_from_slice(b"\x7FELF\x01\x01\x01");
+        data[18] = 40; // ARM
+        let symtab_offset = data.len();
+        data.extend_from_slice(&symtab);
+        let strtab_offset = data.len();
+        data.extend_from_slice(&strtab);
+
+        // Section headers: null, symtab (linked to 2), strtab
+        let sh_offset = data.len();
+        data.extend_from_slice(&[0; 40]);
+        for (kind, offset, size, link) in [
+            (2u32, symtab_offset, symtab.len(), 2u32),
+            (3, strtab_offset, strtab.len(), 0),
+        ] {
+            let mut header = [0u8; 40];
+            header[4..8].copy_from_slice(&kind.to_le_bytes());
+            let offset = u32::try_from(offset).unwrap_or_default();
+            let size = u32::try_from(size).unwrap_or_default();
+            header[16..20].copy_from_slice(&offset.to_le_bytes());
+            header[20..24].copy_from_slice(&size.to_le_bytes());
+            header[24..28].copy_from_slice(&link.to_le_bytes());
+            data.extend_from_slice(&header);
+        }
+        let sh_offset = u32::try_from(sh_offset).unwrap_or_default();
+        data[32..36].copy_from_slice(&sh_offset.to_le_bytes());
+        data[46] = 40; // section header size
+        data[48] = 3; // section header count
+        data
+    }
+
+    #[test]
+    fn test_from_elf_valid() {
+        // Arrange - function (Thumb), object, ARM mapping symbol and file symbol
+        let data = elf(&[
+            ("main", 0x0800_0101, 0x2C, 0x12),
+            ("g_calib_table", 0x2000_0000, 0x40, 0x11),
+            ("$t", 0x0800_0100, 0, 0x00),
+            ("main.c", 0, 0, 0x04),
+        ]);
+
+        // Act
+        let symbols = SymbolTable::from_elf(&data).unwrap_or_default();
+
+        // Assert
+        assert_eq!(
+            symbols.symbols(),
+            &[
+                Symbol {
+                    name: "main".to_string(),
+                    address: 0x0800_0100,
+                    size: 0x2C,
+                },
+                Symbol {
+                    name: "g_calib_table".to_string(),
+                    address: 0x2000_0000,
+                    size: 0x40,
+                },
+            ]
+        );
+        assert_eq!(
+            symbols.symbol_at(0x0800_012B).map(|s| s.name.as_str()),
+            Some("main")
+        );
+        assert_eq!(symbols.symbol_at(0x0800_012C), None);
+    }
+
+    #[test]
+    fn test_from_elf_invalid() {
+        // Arrange
+        // Symbol table section turned into a program data section
+        let mut stripped = elf(&[]);
+        let sh_offset = le_u32(&stripped, 32).unwrap_or_default() as usize;
+        stripped[sh_offset + 44] = 1;
+        let mut truncated = elf(&[("main", 0x101, 4, 0x12)]);
+        truncated.truncate(truncated.len() - 30); // into the string table header
+        // 64-bit file with its section header table at the end of the address space
+        let mut crafted = vec![0u8; 64];
+        crafted[..7].copy_from_slice(b"\x7FELF\x02\x01\x01");
+        crafted[40..48].copy_from_slice(&u64::MAX.to_le_bytes());
+        crafted[58] = 64; // section header size
+        crafted[60] = 1; // section header count
+
+        // Act
+        let res1 = SymbolTable::from_elf(&stripped);
+        let res2 = SymbolTable::from_elf(&truncated);
+        let res3 = SymbolTable::from_elf(&crafted);
+
+        // Assert
+        assert_eq!(
+            res1,
+            Err(IntelHexError::ParseSymbolsError(
+                IntelHexErrorKind::ElfNoSymbolTable
+            ))
+        );
+        assert_eq!(
+            res2,
+            Err(IntelHexError::ParseSymbolsError(
+                IntelHexErrorKind::ElfMalformed
+            ))
+        );
+        assert_eq!(
+            res3,
+            Err(IntelHexError::ParseSymbolsError(
+                IntelHexErrorKind::ElfMalformed
+            ))
+        );
+    }
+
+    #[test]
+    fn test_from_map_gnu() {
+        // Arrange
+        let map = "\
+Memory Configuration
+
+Name             Origin             Length             Attributes
+FLASH            0x08000000         0x00020000         xr
+
+Linker script and memory map
+
+LOAD build/main.o
+.text           0x08000000      0x130
+ .text          0x08000000      0x100 build/main.o
+                0x08000000                Reset_Handler
+                0x08000040                main
+ .text.helper   0x08000100       0x30 build/helper.o
+                0x08000100                helper
+                0x20000400                _estack = 0x20000400
+";
+
+        // Act
+        let symbols = SymbolTable::from_map(map).unwrap_or_default();
+
+        // Assert
+        let found: Vec<(&str, usize, usize)> = symbols
+            .symbols()
+            .iter()
+            .map(|s| (s.name.as_str(), s.address, s.size))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("Reset_Handler", 0x0800_0000, 0x40),
+                ("main", 0x0800_0040, 0xC0),
+                ("helper", 0x0800_0100, 0x30),
+            ]
+        );
+        assert_eq!(symbols.starting_in(0x0800_0040, 0x0800_0110).len(), 2);
+    }
+
+    #[test]
+    fn test_from_map_iar_and_keil() {
+        // Arrange
+        let iar = "\
+*******************************************************************************
+*** ENTRY LIST
+***
+
+Entry                       Address   Size  Type      Object
+-----                       -------   ----  ----      ------
+.iar.init_table$$Base    0x0800'0234          --   Gb  - Linker created -
+__iar_program_start_with_a_long_name
+                         0x0800'0101         Code  Gb  cstartup_M.o [4]
+g_calib_table            0x2000'0000   0x40  Data  Gb  calib.o [1]
+main                     0x0800'0141   0x2c  Code  Gb  main.o [1]
+";
+        let keil = "\
+    Image Symbol Table
+
+    Local Symbols
+
+    Symbol Name                              Value     Ov Type        Size  Object(Section)
+
+    ../Src/main.c                            0x00000000   Number         0  main.o ABSOLUTE
+    .text                                    0x08000000   Section      308  startup.o(.text)
+
+    Global Symbols
+
+    main                                     0x08000189   Thumb Code    40  main.o(.text.main)
+    g_calib_table                            0x20000000   Data          64  calib.o(.data)
+
+==============================================================================
+
+Memory Map of the image
+
+    0x08000000   0x08000000   0x00000194   Data   RO            3    RESET               startup.o
+";
+
+        // Act
+        let iar = SymbolTable::from_map(iar).unwrap_or_default();
+        let keil = SymbolTable::from_map(keil).unwrap_or_default();
+        let unknown = SymbolTable::from_map("main 0x0800");
+
+        // Assert
+        let found = |table: &SymbolTable| -> Vec<(String, usize, usize)> {
+            table
+                .symbols()
+                .iter()
+                .map(|s| (s.name.clone(), s.address, s.size))
+                .collect()
+        };
+        assert_eq!(
+            found(&iar),
+            vec![
+                (
+                    "__iar_program_start_with_a_long_name".to_string(),
+                    0x0800_0100,
+                    0
+                ),
+                ("main".to_string(), 0x0800_0140, 0x2C),
+                ("g_calib_table".to_string(), 0x2000_0000, 0x40),
+            ]
+        );
+        assert_eq!(
+            found(&keil),
+            vec![
+                ("main".to_string(), 0x0800_0188, 40),
+                ("g_calib_table".to_string(), 0x2000_0000, 64),
+            ]
+        );
+        assert_eq!(
+            unknown,
+            Err(IntelHexError::ParseSymbolsError(
+                IntelHexErrorKind::SymbolFormatUnknown
+            ))
+        );
+    }
+}
@@ -0,0 +1,1307 @@
+//! The `templates` module defines [`Template`] - layouts of firmware headers, config blocks
+//! or calibration tables written as a subset of C struct declarations - and decodes them
+//! at an address of the image into a tree of [`TemplateField`]s. See
+//! [`IntelHex::decode_template`](crate::IntelHex::decode_template).
+//!
+//! Supported are:
+//! - `struct` definitions and `typedef`s (of structs, scalars and arrays), nested structs
+//!   (by tag, typedef or inline) and multi-dimensional arrays.
+//! - Fixed-width types (`uint8_t` ... `int64_t`, `u8` ... `s64`), `char`, `bool`, `float`,
+//!   `double`, the C integer types (`long` is 32 bits), enums (32 bits) and pointers
+//!   (32 bits).
+//! - Endianness: the `__le16` ... `__be64` types, and `#pragma endian big` / `little`
+//!   for all following fields. Other fields use the byte order given when decoding.
+//! - `#define`d constants and simple `+ - *` expressions as array sizes.
+//!
+//! Fields are packed: there is no padding between them, as with `#pragma pack(1)`.
+//! Other preprocessor directives, `const`, `volatile` and `__attribute__((...))` are ignored.
+//! Structs are limited to `MAX_STRUCT_SIZE` bytes and `MAX_STRUCT_VALUES` decoded values.
+
+use crate::error::{IntelHexError, IntelHexErrorKind};
+use crate::search::NumericValue;
+use crate::xrefs::read_word;
+use std::collections::HashMap;
+use std::fmt::{self, Write};
+
+/// Largest size of a struct in bytes
+const MAX_STRUCT_SIZE: usize = 16 * 1024 * 1024;
+/// Largest number of values (scalars and texts) of a struct. Every value is decoded into
+/// a field of the tree.
+const MAX_STRUCT_VALUES: usize = 0x1_0000;
+
+/// Type of a scalar value, e.g. of a template field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScalarType {
+    /// Unsigned integer of the given size in bytes
+    Unsigned(usize),
+    /// Two's complement signed integer of the given size in bytes
+    Signed(usize),
+    /// IEEE 754 float of the given size in bytes (4 or 8)
+    Float(usize),
+    /// Single byte character. Arrays of characters are decoded as text.
+    Char,
+}
+
+impl ScalarType {
+    /// Size of the type in bytes.
+    #[must_use]
+    pub const fn size(self) -> usize {
+        match self {
+            Self::Unsigned(size) | Self::Signed(size) | Self::Float(size) => size,
+            Self::Char => 1,
+        }
+    }
+
+    /// Encode a value given as text into its bytes in the byte order. Integers are given
+    /// in decimal or hex (`0x1F`), floats in decimal.
+    ///
+    /// Returns `None` if the value is invalid or out of range of the type, or the type has
+    /// no 1, 2, 4 or 8 byte (floats: 4 or 8 byte) encoding.
+    ///
+    /// # Example
+    /// ```
+    /// use intelhexlib::ScalarType;
+    ///
+    /// assert_eq!(ScalarType::Unsigned(4).encode("0x1234", false), Some(vec![0x34, 0x12, 0, 0]));
+    /// assert_eq!(ScalarType::Float(4).encode("1.5", true), Some(vec![0x3F, 0xC0, 0, 0]));
+    /// assert_eq!(ScalarType::Signed(1).encode("-129", false), None);
+    /// ```
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation)]
+    #[allow(clippy::cast_sign_loss)]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn encode(self, input: &str, big_endian: bool) -> Option<Vec<u8>> {
+        if !matches!(self.size(), 1 | 2 | 4 | 8) {
+            return None;
+        }
+        let value: NumericValue = input.parse().ok()?;
+        let bits = match (self, value) {
+            (Self::Unsigned(size), NumericValue::Int(v)) => {
+                (v >= 0 && v < 1i128 << (size * 8)).then_some(v as u64)?
+            }
+            (Self::Char, NumericValue::Int(v)) => u8::try_from(v).ok().map(u64::from)?,
+            (Self::Signed(size), NumericValue::Int(v)) => {
+                let half = 1i128 << (size * 8 - 1);
+                (-half <= v && v < half).then_some(v as i64 as u64)?
+            }
+            (Self::Float(4), value) => {
+                let v = match value {
+                    NumericValue::Int(v) => v as f32,
+                    NumericValue::Float(v) => v as f32,
+                };
+                u64::from(v.is_finite().then_some(v.to_bits())?)
+            }
+            (Self::Float(8), value) => match value {
+                NumericValue::Int(v) => (v as f64).to_bits(),
+                NumericValue::Float(v) => v.to_bits(),
+            },
+            _ => return None,
+        };
+
+        let mut bytes = bits.to_le_bytes()[..self.size()].to_vec();
+        if big_endian {
+            bytes.reverse();
+        }
+        Some(bytes)
+    }
+}
+
+/// Type of a field in a struct definition
+#[derive(Debug, Clone)]
+enum FieldType {
+    /// Scalar with its byte order (`None` - given when decoding) and type name in the template
+    Scalar {
+        scalar: ScalarType,
+        big_endian: Option<bool>,
+        name: String,
+    },
+    /// Index of a struct definition
+    Struct(usize),
+}
+
+/// Field of a struct definition
+#[derive(Debug, Clone)]
+struct FieldDef {
+    name: String,
+    ty: FieldType,
+    /// Array dimensions, outermost first. Empty for a single value.
+    dims: Vec<usize>,
+}
+
+/// Struct definition with its packed size
+#[derive(Debug, Clone)]
+struct StructDef {
+    /// Tag or typedef name, empty for anonymous structs
+    name: String,
+    fields: Vec<FieldDef>,
+    size: usize,
+    /// Number of scalar and text values
+    values: usize,
+}
+
+/// Parsed structure template: the struct definitions of a C header.
+///
+/// # Example
+/// ```
+/// use intelhexlib::Template;
+///
+/// let template = Template::parse(
+///     "struct section { uint32_t offset; uint32_t size; };
+///      typedef struct {
+///          __be32 magic;
+///          char name[16];
+///          struct section sections[4];
+///      } header_t;",
+/// )
+/// .unwrap();
+///
+/// assert_eq!(template.struct_names(), ["section", "header_t"]);
+/// assert_eq!(template.size_of("header_t"), Some(52));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Template {
+    structs: Vec<StructDef>,
+    /// Struct tags and typedef names of structs with the index of the struct,
+    /// in the order of their definition
+    names: Vec<(String, usize)>,
+}
+
+impl Template {
+    /// Parse the struct definitions of a C header.
+    ///
+    /// # Errors
+    /// Returns an error with the line number if the text is not a supported C declaration.
+    pub fn parse(text: &str) -> Result<Self, IntelHexError> {
+        let tokens =
+            tokenize(text).map_err(|(kind, line)| IntelHexError::ParseTemplateError(kind, line))?;
+
+        let mut parser = Parser {
+            tokens,
+            pos: 0,
+            endian: None,
+            structs: Vec::new(),
+            names: Vec::new(),
+            tags: HashMap::new(),
+            typedefs: HashMap::new(),
+        };
+        parser
+            .parse_items()
+            .map_err(|(kind, line)| IntelHexError::ParseTemplateError(kind, line))?;
+
+        Ok(Self {
+            structs: parser.structs,
+            names: parser.names,
+        })
+    }
+
+    /// Names of the structs (tags and typedef names) in the order of their definition.
+    #[must_use]
+    pub fn struct_names(&self) -> Vec<&str> {
+        self.names.iter().map(|(name, _)| name.as_str()).collect()
+    }
+
+    /// Size of the struct in bytes, `None` if there is no struct with the name.
+    #[must_use]
+    pub fn size_of(&self, name: &str) -> Option<usize> {
+        self.find(name).map(|idx| self.structs[idx].size)
+    }
+
+    /// Returns true if the template defines no named structs.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    /// Index of the struct with the name. Of redefined names the last definition is used.
+    fn find(&self, name: &str) -> Option<usize> {
+        self.names
+            .iter()
+            .rev()
+            .find(|(n, _)| n == name)
+            .map(|&(_, idx)| idx)
+    }
+}
+
+/// Size of a single value of the type in bytes
+fn type_size(structs: &[StructDef], ty: &FieldType) -> usize {
+    match ty {
+        FieldType::Scalar { scalar, .. } => scalar.size(),
+        FieldType::Struct(idx) => structs[*idx].size,
+    }
+}
+
+/// Number of values (scalars and texts) of a single value of the type
+fn type_values(structs: &[StructDef], ty: &FieldType) -> usize {
+    match ty {
+        FieldType::Scalar { .. } => 1,
+        FieldType::Struct(idx) => structs[*idx].values,
+    }
+}
+
+/// Name of the type, e.g. `uint32_t` or `header_t`
+fn type_name(structs: &[StructDef], ty: &FieldType) -> String {
+    match ty {
+        FieldType::Scalar { name, .. } => name.clone(),
+        FieldType::Struct(idx) if structs[*idx].name.is_empty() => "struct".to_string(),
+        FieldType::Struct(idx) => structs[*idx].name.clone(),
+    }
+}
+
+/// Kind of a decoded [`TemplateField`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldKind {
+    /// Struct, its fields are the children
+    Struct,
+    /// Array, its elements are the children
+    Array,
+    /// Single value
+    Scalar(ScalarType),
+    /// Array of characters, decoded as text up to the first NUL
+    Text,
+}
+
+/// Decoded value of a scalar or text field.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FieldValue {
+    Unsigned(u64),
+    Signed(i64),
+    Float(f64),
+    /// Text with non-printable characters replaced by '.'
+    Text(String),
+}
+
+impl fmt::Display for FieldValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unsigned(v) => write!(f, "{v}"),
+            Self::Signed(v) => write!(f, "{v}"),
+            Self::Float(v) => write!(f, "{v}"),
+            Self::Text(v) => write!(f, "{v}"),
+        }
+    }
+}
+
+/// Field of a template decoded at an address, see
+/// [`IntelHex::decode_template`](crate::IntelHex::decode_template).
+#[derive(Debug, Clone, PartialEq)]
+pub struct TemplateField {
+    /// Name of the field, `name[i]` for array elements
+    pub name: String,
+    /// Name of the type, e.g. `uint32_t`, `header_t` or `uint8_t[4]`
+    pub type_name: String,
+    /// First address of the field
+    pub address: usize,
+    /// Size in bytes
+    pub size: usize,
+    pub kind: FieldKind,
+    /// Byte order of a scalar field
+    pub big_endian: bool,
+    /// Value of a scalar or text field. `None` for structs, arrays and fields that are
+    /// not fully populated.
+    pub value: Option<FieldValue>,
+    /// Fields of a struct or elements of an array
+    pub children: Vec<Self>,
+}
+
+impl TemplateField {
+    /// Scalar and text fields in address order.
+    #[must_use]
+    pub fn leaves(&self) -> Vec<&Self> {
+        let mut leaves = Vec::new();
+        self.collect_leaves(&mut leaves);
+        leaves
+    }
+
+    fn collect_leaves<'a>(&'a self, leaves: &mut Vec<&'a Self>) {
+        match self.kind {
+            FieldKind::Scalar(_) | FieldKind::Text => leaves.push(self),
+            FieldKind::Struct | FieldKind::Array => {
+                for child in &self.children {
+                    child.collect_leaves(leaves);
+                }
+            }
+        }
+    }
+
+    /// Encode a new value of a scalar or text field into its bytes. Integers are given in
+    /// decimal or hex (`0x1F`), floats in decimal. Text is ASCII, terminated with a NUL if
+    /// it is shorter than the field. The bytes after the NUL are left alone.
+    ///
+    /// Returns `None` if the value is invalid or out of range of the type, or the field
+    /// is a struct or array.
+    #[must_use]
+    pub fn encode(&self, input: &str) -> Option<Vec<u8>> {
+        let scalar = match self.kind {
+            FieldKind::Scalar(scalar) => scalar,
+            FieldKind::Text => {
+                if !input.is_ascii() || input.len() > self.size {
+                    return None;
+                }
+                let mut bytes = input.as_bytes().to_vec();
+                if bytes.len() < self.size {
+                    bytes.push(0);
+                }
+                return Some(bytes);
+            }
+            FieldKind::Struct | FieldKind::Array => return None,
+        };
+
+        scalar.encode(input, self.big_endian)
+    }
+}
+
+/// Decode the struct `name` of the template at `address`. `bytes` are the bytes of the
+/// struct (`None` for gaps) and `big_endian` is the byte order of the fields without one
+/// in the template. Returns `None` if there is no struct with the name.
+pub fn decode_template(
+    template: &Template,
+    name: &str,
+    address: usize,
+    big_endian: bool,
+    bytes: &[Option<u8>],
+) -> Option<TemplateField> {
+    let idx = template.find(name)?;
+    let decoder = Decoder {
+        template,
+        base: address,
+        bytes,
+        big_endian,
+    };
+    Some(decoder.decode(name.to_string(), &FieldType::Struct(idx), &[], address))
+}
+
+/// Decoder of the fields of one struct
+struct Decoder<'a> {
+    template: &'a Template,
+    /// Address of the first byte in `bytes`
+    base: usize,
+    bytes: &'a [Option<u8>],
+    big_endian: bool,
+}
+
+impl Decoder<'_> {
+    /// Decode a field of the type with the array dimensions at the address
+    fn decode(
+        &self,
+        name: String,
+        ty: &FieldType,
+        dims: &[usize],
+        address: usize,
+    ) -> TemplateField {
+        let structs = &self.template.structs;
+        let size = type_size(structs, ty) * dims.iter().product::<usize>();
+        let mut field = TemplateField {
+            name,
+            type_name: type_name(structs, ty),
+            address,
+            size,
+            kind: FieldKind::Struct,
+            big_endian: self.big_endian,
+            value: None,
+            children: Vec::new(),
+        };
+        for dim in dims {
+            let _ = write!(field.type_name, "[{dim}]");
+        }
+
+        match (ty, dims) {
+            (
+                FieldType::Scalar {
+                    scalar: ScalarType::Char,
+                    ..
+                },
+                [_],
+            ) => {
+                field.kind = FieldKind::Text;
+                field.value = self.read(address, size).map(|bytes| {
+                    let text = bytes
+                        .iter()
+                        .take_while(|&&b| b != 0)
+                        .map(|&b| {
+                            if (0x20..0x7F).contains(&b) {
+                                b as char
+                            } else {
+                                '.'
+                            }
+                        })
+                        .collect();
+                    FieldValue::Text(text)
+                });
+            }
+            (_, [count, inner @ ..]) => {
+                field.kind = FieldKind::Array;
+                let stride = size / (*count).max(1);
+                field.children = (0..*count)
+                    .map(|i| {
+                        let name = format!("{}[{i}]", field.name);
+                        self.decode(name, ty, inner, address + i * stride)
+                    })
+                    .collect();
+            }
+            (FieldType::Struct(idx), []) => {
+                let mut offset = address;
+                for def in &self.template.structs[*idx].fields {
+                    let child = self.decode(def.name.clone(), &def.ty, &def.dims, offset);
+                    offset += child.size;
+                    field.children.push(child);
+                }
+            }
+            (
+                FieldType::Scalar {
+                    scalar, big_endian, ..
+                },
+                [],
+            ) => {
+                field.kind = FieldKind::Scalar(*scalar);
+                field.big_endian = big_endian.unwrap_or(self.big_endian);
+                field.value = self
+                    .read(address, size)
+                    .map(|bytes| decode_scalar(*scalar, &bytes, field.big_endian));
+            }
+        }
+
+        field
+    }
+
+    /// Bytes of the range, `None` if any of them is not populated
+    fn read(&self, address: usize, len: usize) -> Option<Vec<u8>> {
+        let offset = address - self.base;
+        self.bytes
+            .get(offset..offset + len)?
+            .iter()
+            .copied()
+            .collect()
+    }
+}
+
+/// Decode the bytes of a scalar
+#[allow(clippy::cast_possible_truncation)]
+#[allow(clippy::cast_possible_wrap)]
+fn decode_scalar(scalar: ScalarType, bytes: &[u8], big_endian: bool) -> FieldValue {
+    let bits = read_word(bytes, big_endian);
+    match scalar {
+        ScalarType::Unsigned(_) | ScalarType::Char => FieldValue::Unsigned(bits),
+        ScalarType::Signed(size) => {
+            // Sign-extend to 64 bits
+            let shift = 64 - size * 8;
+            FieldValue::Signed(((bits << shift) as i64) >> shift)
+        }
+        ScalarType::Float(4) => FieldValue::Float(f64::from(f32::from_bits(bits as u32))),
+        ScalarType::Float(_) => FieldValue::Float(f64::from_bits(bits)),
+    }
+}
+
+/// Token of a template with the number of its line
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Ident(String),
+    Number(usize),
+    Punct(char),
+    /// `#pragma endian big` (true) or `little` (false)
+    Endian(bool),
+}
+
+type ParseResult<T> = Result<T, (IntelHexErrorKind, usize)>;
+
+/// Split the template into tokens. Comments are dropped, `#define`d constants replaced
+/// by their values, and qualifiers and attributes removed.
+fn tokenize(text: &str) -> ParseResult<Vec<(Token, usize)>> {
+    let mut tokens = Vec::new();
+    let mut defines: HashMap<String, Vec<Token>> = HashMap::new();
+    let mut in_comment = false;
+
+    for (idx, line) in text.lines().enumerate() {
+        let line_no = idx + 1;
+
+        if !in_comment && let Some(directive) = line.trim_start().strip_prefix('#') {
+            let directive = directive.trim_start();
+            let words: Vec<&str> = directive.split_whitespace().collect();
+            match words.as_slice() {
+                // Object-like macros only, function-like ones (`NAME(x)`) are skipped
+                ["define", name, ..] if !name.contains('(') => {
+                    let value = directive
+                        .trim_start_matches("define")
+                        .trim_start()
+                        .trim_start_matches(name);
+                    let mut value_tokens = Vec::new();
+                    let mut in_define_comment = false;
+                    tokenize_line(
+                        value,
+                        line_no,
+                        &defines,
+                        &mut in_define_comment,
+                        &mut value_tokens,
+                    )?;
+                    let value_tokens = value_tokens.into_iter().map(|(t, _)| t).collect();
+                    defines.insert((*name).to_string(), value_tokens);
+                }
+                ["pragma", "endian", order, ..] => match *order {
+                    "big" => tokens.push((Token::Endian(true), line_no)),
+                    "little" => tokens.push((Token::Endian(false), line_no)),
+                    _ => {
+                        return Err((
+                            IntelHexErrorKind::TemplateSyntax("'big' or 'little'".to_string()),
+                            line_no,
+                        ));
+                    }
+                },
+                _ => {}
+            }
+            continue;
+        }
+
+        tokenize_line(line, line_no, &defines, &mut in_comment, &mut tokens)?;
+    }
+
+    Ok(strip_qualifiers(tokens))
+}
+
+/// Split a line into tokens, replacing the defined macros by their tokens.
+/// `in_comment` is the state of a block comment carried over between lines.
+fn tokenize_line(
+    line: &str,
+    line_no: usize,
+    defines: &HashMap<String, Vec<Token>>,
+    in_comment: &mut bool,
+    tokens: &mut Vec<(Token, usize)>,
+) -> ParseResult<()> {
+    let chars: Vec<char> = line.chars().collect();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+
+        if *in_comment {
+            if c == '*' && next == Some('/') {
+                *in_comment = false;
+                i += 1;
+            }
+            i += 1;
+            continue;
+        }
+
+        if c == '/' && next == Some('/') {
+            break;
+        } else if c == '/' && next == Some('*') {
+            *in_comment = true;
+            i += 2;
+        } else if c.is_ascii_alphanumeric() || c == '_' {
+            let start = i;
+            while i < chars.len() && (chars[i].is_ascii_alphanumeric() || chars[i] == '_') {
+                i += 1;
+            }
+            let word: String = chars[start..i].iter().collect();
+            if c.is_ascii_digit() {
+                let value = parse_number(&word).ok_or_else(|| {
+                    (
+                        IntelHexErrorKind::TemplateSyntax("number".to_string()),
+                        line_no,
+                    )
+                })?;
+                tokens.push((Token::Number(value), line_no));
+            } else if let Some(value) = defines.get(&word) {
+                // Keep the precedence of expressions, e.g. `N * 2` with `#define N 4 + 1`
+                let is_expression =
+                    value.len() > 1 && value.iter().all(|t| !matches!(t, Token::Ident(_)));
+                if is_expression {
+                    tokens.push((Token::Punct('('), line_no));
+                }
+                tokens.extend(value.iter().map(|t| (t.clone(), line_no)));
+                if is_expression {
+                    tokens.push((Token::Punct(')'), line_no));
+                }
+            } else {
+                tokens.push((Token::Ident(word), line_no));
+            }
+        } else {
+            if !c.is_whitespace() {
+                tokens.push((Token::Punct(c), line_no));
+            }
+            i += 1;
+        }
+    }
+    Ok(())
+}
+
+/// Remove qualifiers and `__attribute__((...))` that do not change the layout
+fn strip_qualifiers(tokens: Vec<(Token, usize)>) -> Vec<(Token, usize)> {
+    let mut result = Vec::with_capacity(tokens.len());
+    let mut iter = tokens.into_iter().peekable();
+
+    while let Some((token, line)) = iter.next() {
+        match &token {
+            Token::Ident(word) if matches!(word.as_str(), "const" | "volatile" | "__packed") => {}
+            Token::Ident(word) if matches!(word.as_str(), "__attribute__" | "__attribute") => {
+                // Skip the balanced parentheses after the attribute, e.g. `((aligned(4)))`
+                let mut depth = 0usize;
+                while let Some((next, _)) = iter.peek() {
+                    match next {
+                        Token::Punct('(') => depth += 1,
+                        Token::Punct(')') => depth = depth.saturating_sub(1),
+                        _ if depth == 0 => break,
+                        _ => {}
+                    }
+                    iter.next();
+                    if depth == 0 {
+                        break;
+                    }
+                }
+            }
+            _ => result.push((token, line)),
+        }
+    }
+
+    result
+}
+
+/// Parse a decimal or hex integer with optional `u` / `l` suffixes
+fn parse_number(text: &str) -> Option<usize> {
+    let text = text.trim_end_matches(['u', 'U', 'l', 'L']);
+    text.strip_prefix("0x")
+        .or_else(|| text.strip_prefix("0X"))
+        .map_or_else(
+            || text.parse().ok(),
+            |hex| usize::from_str_radix(hex, 16).ok(),
+        )
+}
+
+/// Scalar type of a fixed-width type name with its byte order, if the name fixes it
+fn fixed_type(name: &str) -> Option<(ScalarType, Option<bool>)> {
+    let scalar = match name {
+        "uint8_t" | "u8" | "__u8" => ScalarType::Unsigned(1),
+        "uint16_t" | "u16" | "__u16" => ScalarType::Unsigned(2),
+        "uint32_t" | "u32" | "__u32" => ScalarType::Unsigned(4),
+        "uint64_t" | "u64" | "__u64" => ScalarType::Unsigned(8),
+        "int8_t" | "s8" | "i8" | "__s8" => ScalarType::Signed(1),
+        "int16_t" | "s16" | "i16" | "__s16" => ScalarType::Signed(2),
+        "int32_t" | "s32" | "i32" | "__s32" => ScalarType::Signed(4),
+        "int64_t" | "s64" | "i64" | "__s64" => ScalarType::Signed(8),
+        "f32" => ScalarType::Float(4),
+        "f64" => ScalarType::Float(8),
+        "__le16" => return Some((ScalarType::Unsigned(2), Some(false))),
+        "__le32" => return Some((ScalarType::Unsigned(4), Some(false))),
+        "__le64" => return Some((ScalarType::Unsigned(8), Some(false))),
+        "__be16" => return Some((ScalarType::Unsigned(2), Some(true))),
+        "__be32" => return Some((ScalarType::Unsigned(4), Some(true))),
+        "__be64" => return Some((ScalarType::Unsigned(8), Some(true))),
+        _ => return None,
+    };
+    Some((scalar, None))
+}
+
+/// Words of the built-in C types, e.g. `unsigned long long`
+const BUILTIN_WORDS: [&str; 10] = [
+    "unsigned", "signed", "char", "short", "int", "long", "float", "double", "bool", "_Bool",
+];
+
+/// Recursive descent parser of the struct definitions
+struct Parser {
+    tokens: Vec<(Token, usize)>,
+    pos: usize,
+    /// Byte order set by the last `#pragma endian`
+    endian: Option<bool>,
+    structs: Vec<StructDef>,
+    /// Struct tags and typedef names of structs, see [`Template`]
+    names: Vec<(String, usize)>,
+    /// Struct tags and the index of their definition
+    tags: HashMap<String, usize>,
+    /// Typedef names with their type and array dimensions
+    typedefs: HashMap<String, (FieldType, Vec<usize>)>,
+}
+
+impl Parser {
+    /// Apply the `#pragma endian` tokens before the current token
+    fn skip_pragmas(&mut self) {
+        while let Some((Token::Endian(big_endian), _)) = self.tokens.get(self.pos) {
+            self.endian = Some(*big_endian);
+            self.pos += 1;
+        }
+    }
+
+    fn peek(&mut self) -> Option<&Token> {
+        self.skip_pragmas();
+        self.tokens.get(self.pos).map(|(token, _)| token)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        self.skip_pragmas();
+        let token = self.tokens.get(self.pos).map(|(token, _)| token.clone());
+        self.pos += 1;
+        token
+    }
+
+    /// Consume the token if it is the punctuation character
+    fn eat(&mut self, c: char) -> bool {
+        let found = self.peek() == Some(&Token::Punct(c));
+        if found {
+            self.pos += 1;
+        }
+        found
+    }
+
+    /// Error at the line of the current token
+    fn error<T>(&self, kind: IntelHexErrorKind) -> ParseResult<T> {
+        let line = self
+            .tokens
+            .get(self.pos.min(self.tokens.len().saturating_sub(1)))
+            .map_or(0, |(_, line)| *line);
+        Err((kind, line))
+    }
+
+    fn syntax_error<T>(&self, expected: &str) -> ParseResult<T> {
+        self.error(IntelHexErrorKind::TemplateSyntax(expected.to_string()))
+    }
+
+    fn expect(&mut self, c: char) -> ParseResult<()> {
+        if self.eat(c) {
+            Ok(())
+        } else {
+            self.syntax_error(&format!("'{c}'"))
+        }
+    }
+
+    fn ident(&mut self, what: &str) -> ParseResult<String> {
+        match self.peek() {
+            Some(Token::Ident(word)) => {
+                let word = word.clone();
+                self.pos += 1;
+                Ok(word)
+            }
+            _ => self.syntax_error(what),
+        }
+    }
+
+    /// Top level: struct definitions and typedefs
+    fn parse_items(&mut self) -> ParseResult<()> {
+        while let Some(token) = self.peek().cloned() {
+            match token {
+                Token::Punct(';') => self.pos += 1,
+                Token::Ident(word) if word == "typedef" => {
+                    self.pos += 1;
+                    let (ty, dims) = self.parse_type()?;
+                    loop {
+                        let (name, ty, dims) = self.parse_declarator(&ty, &dims)?;
+                        if let FieldType::Struct(idx) = ty
+                            && dims.is_empty()
+                        {
+                            // Anonymous structs are named by their typedef
+                            if self.structs[idx].name.is_empty() {
+                                self.structs[idx].name.clone_from(&name);
+                            }
+                            self.names.push((name.clone(), idx));
+                        }
+                        self.typedefs.insert(name, (ty, dims));
+                        if !self.eat(',') {
+                            break;
+                        }
+                    }
+                    self.expect(';')?;
+                }
+                Token::Ident(word) if matches!(word.as_str(), "struct" | "union" | "enum") => {
+                    self.parse_type()?;
+                    self.expect(';')?;
+                }
+                _ => return self.syntax_error("struct or typedef"),
+            }
+        }
+        Ok(())
+    }
+
+    /// Type of a declaration with the array dimensions of a typedef
+    fn parse_type(&mut self) -> ParseResult<(FieldType, Vec<usize>)> {
+        let word = self.ident("type")?;
+        let big_endian = self.endian;
+
+        let ty = match word.as_str() {
+            "struct" => self.parse_struct()?,
+            "union" => {
+                return self.error(IntelHexErrorKind::TemplateUnsupported("union".to_string()));
+            }
+            "enum" => {
+                let tag = match self.peek() {
+                    Some(Token::Ident(_)) => self.ident("enum tag")?,
+                    _ => String::new(),
+                };
+                if self.eat('{') {
+                    self.skip_block()?;
+                }
+                FieldType::Scalar {
+                    scalar: ScalarType::Signed(4),
+                    big_endian,
+                    name: format!("enum {tag}").trim_end().to_string(),
+                }
+            }
+            _ if BUILTIN_WORDS.contains(&word.as_str()) => self.parse_builtin(word)?,
+            _ => {
+                if let Some((scalar, fixed_endian)) = fixed_type(&word) {
+                    FieldType::Scalar {
+                        scalar,
+                        big_endian: fixed_endian.or(big_endian),
+                        name: word,
+                    }
+                } else if let Some((ty, dims)) = self.typedefs.get(&word) {
+                    let ty = match ty {
+                        // Show the typedef name of scalars, arrays show their element type
+                        FieldType::Scalar {
+                            scalar,
+                            big_endian: fixed_endian,
+                            name,
+                        } => FieldType::Scalar {
+                            scalar: *scalar,
+                            big_endian: fixed_endian.or(big_endian),
+                            name: if dims.is_empty() { word } else { name.clone() },
+                        },
+                        FieldType::Struct(idx) => FieldType::Struct(*idx),
+                    };
+                    return Ok((ty, dims.clone()));
+                } else {
+                    return self.error(IntelHexErrorKind::TemplateUnknownType(word));
+                }
+            }
+        };
+
+        Ok((ty, Vec::new()))
+    }
+
+    /// Built-in C type of one or more words, e.g. `unsigned short int`. `long` is 32 bits.
+    fn parse_builtin(&mut self, first: String) -> ParseResult<FieldType> {
+        let mut words = vec![first];
+        while let Some(Token::Ident(word)) = self.peek()
+            && BUILTIN_WORDS.contains(&word.as_str())
+        {
+            words.push(word.clone());
+            self.pos += 1;
+        }
+
+        let has = |name: &str| words.iter().any(|w| w == name);
+        let longs = words.iter().filter(|w| *w == "long").count();
+        let size = if has("char") || has("bool") || has("_Bool") {
+            1
+        } else if has("short") {
+            2
+        } else if longs >= 2 {
+            8
+        } else {
+            4
+        };
+
+        let scalar = if has("double") && longs > 0 {
+            return self.error(IntelHexErrorKind::TemplateUnsupported(
+                "long double".to_string(),
+            ));
+        } else if has("double") {
+            ScalarType::Float(8)
+        } else if has("float") {
+            ScalarType::Float(4)
+        } else if has("unsigned") || has("bool") || has("_Bool") {
+            ScalarType::Unsigned(size)
+        } else if has("char") && !has("signed") {
+            ScalarType::Char
+        } else {
+            ScalarType::Signed(size)
+        };
+
+        Ok(FieldType::Scalar {
+            scalar,
+            big_endian: self.endian,
+            name: words.join(" "),
+        })
+    }
+
+    /// Struct reference by tag or struct definition (after `struct`)
+    fn parse_struct(&mut self) -> ParseResult<FieldType> {
+        let tag = match self.peek() {
+            Some(Token::Ident(_)) => Some(self.ident("struct tag")?),
+            _ => None,
+        };
+
+        if !self.eat('{') {
+            let Some(tag) = tag else {
+                return self.syntax_error("struct tag or '{'");
+            };
+            return match self.tags.get(&tag).copied() {
+                Some(idx) => Ok(FieldType::Struct(idx)),
+                // Pointers may refer to structs not defined (yet), e.g. `struct node *next`
+                None if self.peek() == Some(&Token::Punct('*')) => Ok(FieldType::Scalar {
+                    scalar: ScalarType::Unsigned(4),
+                    big_endian: self.endian,
+                    name: format!("struct {tag}"),
+                }),
+                None => self.error(IntelHexErrorKind::TemplateUnknownType(format!(
+                    "struct {tag}"
+                ))),
+            };
+        }
+
+        let mut fields = Vec::new();
+        while !self.eat('}') {
+            if self.peek().is_none() {
+                return self.syntax_error("'}'");
+            }
+            let (ty, dims) = self.parse_type()?;
+            loop {
+                let (name, ty, dims) = self.parse_declarator(&ty, &dims)?;
+                if self.peek() == Some(&Token::Punct(':')) {
+                    return self.error(IntelHexErrorKind::TemplateUnsupported(
+                        "bit-fields".to_string(),
+                    ));
+                }
+                fields.push(FieldDef { name, ty, dims });
+                if !self.eat(',') {
+                    break;
+                }
+            }
+            self.expect(';')?;
+        }
+
+        let (size, values) = self.struct_layout(&fields)?;
+        let idx = self.structs.len();
+        self.structs.push(StructDef {
+            name: tag.clone().unwrap_or_default(),
+            fields,
+            size,
+            values,
+        });
+        if let Some(tag) = tag {
+            self.names.push((tag.clone(), idx));
+            self.tags.insert(tag, idx);
+        }
+        Ok(FieldType::Struct(idx))
+    }
+
+    /// Size in bytes and number of values of a struct with the fields. An error if it is
+    /// larger than `MAX_STRUCT_SIZE` or has more than `MAX_STRUCT_VALUES` values.
+    fn struct_layout(&self, fields: &[FieldDef]) -> ParseResult<(usize, usize)> {
+        let too_large = |what: String| {
+            self.error::<(usize, usize)>(IntelHexErrorKind::TemplateUnsupported(what))
+        };
+
+        let mut size = 0usize;
+        let mut values = 0usize;
+        for field in fields {
+            let count = field
+                .dims
+                .iter()
+                .try_fold(1usize, |count, &dim| count.checked_mul(dim));
+            // Character arrays are a single text value
+            let is_text = matches!(
+                (&field.ty, field.dims.as_slice()),
+                (
+                    FieldType::Scalar {
+                        scalar: ScalarType::Char,
+                        ..
+                    },
+                    [_]
+                )
+            );
+            let field_values = if is_text {
+                Some(1)
+            } else {
+                count.and_then(|count| count.checked_mul(type_values(&self.structs, &field.ty)))
+            };
+
+            size = match count
+                .and_then(|count| count.checked_mul(type_size(&self.structs, &field.ty)))
+                .and_then(|field_size| size.checked_add(field_size))
+            {
+                Some(size) if size <= MAX_STRUCT_SIZE => size,
+                _ => return too_large(format!("structs larger than {MAX_STRUCT_SIZE} bytes")),
+            };
+            values = match field_values.and_then(|v| values.checked_add(v)) {
+                Some(values) if values <= MAX_STRUCT_VALUES => values,
+                _ => {
+                    return too_large(format!("structs with more than {MAX_STRUCT_VALUES} values"));
+                }
+            };
+        }
+        Ok((size, values))
+    }
+
+    /// Name with pointer and array declarators, e.g. `*next` or `table[4][8]`.
+    /// The dimensions of the declarator come before those of the type.
+    fn parse_declarator(
+        &mut self,
+        ty: &FieldType,
+        type_dims: &[usize],
+    ) -> ParseResult<(String, FieldType, Vec<usize>)> {
+        let mut ty = ty.clone();
+        let mut type_dims = type_dims.to_vec();
+        while self.eat('*') {
+            // Pointers are 32-bit addresses
+            let name = format!("{} *", type_name(&self.structs, &ty));
+            ty = FieldType::Scalar {
+                scalar: ScalarType::Unsigned(4),
+                big_endian: self.endian,
+                name,
+            };
+            type_dims.clear();
+        }
+
+        let name = self.ident("field name")?;
+        let mut dims = Vec::new();
+        while self.eat('[') {
+            dims.push(self.parse_size()?);
+            self.expect(']')?;
+        }
+        dims.extend(type_dims);
+        Ok((name, ty, dims))
+    }
+
+    /// Array size: numbers (or defined constants) with `+`, `-` and `*`
+    fn parse_size(&mut self) -> ParseResult<usize> {
+        let mut sum = 0usize;
+        let mut negative = false;
+        loop {
+            let mut product = self.parse_size_operand()?;
+            while self.eat('*') {
+                product = product.saturating_mul(self.parse_size_operand()?);
+            }
+            sum = if negative {
+                sum.checked_sub(product)
+                    .map_or_else(|| self.syntax_error("positive array size"), Ok)?
+            } else {
+                sum.saturating_add(product)
+            };
+
+            if self.eat('+') {
+                negative = false;
+            } else if self.eat('-') {
+                negative = true;
+            } else {
+                return Ok(sum);
+            }
+        }
+    }
+
+    fn parse_size_operand(&mut self) -> ParseResult<usize> {
+        if self.eat('(') {
+            let value = self.parse_size()?;
+            self.expect(')')?;
+            return Ok(value);
+        }
+        match self.peek() {
+            Some(&Token::Number(value)) => {
+                self.pos += 1;
+                Ok(value)
+            }
+            Some(Token::Ident(word)) => {
+                let word = word.clone();
+                self.error(IntelHexErrorKind::TemplateUnknownType(format!(
+                    "constant {word}"
+                )))
+            }
+            _ => self.syntax_error("array size"),
+        }
+    }
+
+    /// Skip the tokens up to the `}` that closes the block
+    fn skip_block(&mut self) -> ParseResult<()> {
+        let mut depth = 1usize;
+        while depth > 0 {
+            match self.next() {
+                Some(Token::Punct('{')) => depth += 1,
+                Some(Token::Punct('}')) => depth -= 1,
+                Some(_) => {}
+                None => return self.syntax_error("'}'"),
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HEADER: &str = "
+        #define ONE 2 - 1   // expression, keeps its precedence
+
+        /* Section of the image */
+        struct section {
+            uint32_t offset;
+            uint16_t size;
+        } __attribute__((packed));
+
+        #pragma endian big
+        typedef struct header {
+            uint32_t magic;
+            char name[6];
+            int8_t trim[2][2];
+            struct section sections[ONE * 2];
+            __le16 crc;
+            float gain;
+        } header_t;
+    ";
+
+    fn decode(template: &Template, name: &str, data: &[u8]) -> Option<TemplateField> {
+        let bytes: Vec<Option<u8>> = data.iter().copied().map(Some).collect();
+        decode_template(template, name, 0x100, false, &bytes)
+    }
+
+    #[test]
+    fn test_template_decode_valid() {
+        // Arrange
+        let template = Template::parse(HEADER).unwrap_or_default();
+        let data = [
+            0xDE, 0xAD, 0xBE, 0xEF, // magic (big-endian)
+            b'B', b'O', b'O', b'T', 0x00, 0x7F, // name
+            0xFF, 0x01, 0x80, 0x7F, // trim
+            0x00, 0x00, 0x00, 0x10, 0x00, 0x20, // sections[0]
+            0x00, 0x00, 0x01, 0x00, 0x00, 0x30, // sections[1]
+            0x34, 0x12, // crc (little-endian)
+            0x3F, 0xC0, 0x00, 0x00, // gain = 1.5
+        ];
+
+        // Act
+        let header = decode(&template, "header_t", &data);
+
+        // Assert
+        assert_eq!(template.struct_names(), ["section", "header", "header_t"]);
+        assert_eq!(template.size_of("header_t"), Some(data.len()));
+        assert_eq!(template.size_of("header"), Some(data.len()));
+
+        let header = header.unwrap_or_else(|| unreachable!());
+        let value = |path: &[usize]| {
+            let mut field = &header;
+            for &i in path {
+                field = &field.children[i];
+            }
+            (field.address, field.value.clone())
+        };
+        assert_eq!(
+            value(&[0]),
+            (0x100, Some(FieldValue::Unsigned(0xDEAD_BEEF)))
+        );
+        assert_eq!(value(&[1]), (0x104, Some(FieldValue::Text("BOOT".into()))));
+        assert_eq!(value(&[2, 0, 0]), (0x10A, Some(FieldValue::Signed(-1))));
+        assert_eq!(value(&[2, 1, 0]), (0x10C, Some(FieldValue::Signed(-128))));
+        // The section struct was defined before the pragma and stays little-endian
+        assert_eq!(
+            value(&[3, 1, 0]),
+            (0x114, Some(FieldValue::Unsigned(0x0001_0000)))
+        );
+        assert_eq!(
+            value(&[3, 1, 1]),
+            (0x118, Some(FieldValue::Unsigned(0x3000)))
+        );
+        assert_eq!(value(&[4]), (0x11A, Some(FieldValue::Unsigned(0x1234))));
+        assert_eq!(value(&[5]), (0x11C, Some(FieldValue::Float(1.5))));
+
+        assert_eq!(header.type_name, "header");
+        assert_eq!(header.children[2].type_name, "int8_t[2][2]");
+        assert_eq!(header.children[3].children[1].name, "sections[1]");
+        assert_eq!(header.leaves().len(), 12);
+    }
+
+    #[test]
+    fn test_template_decode_gaps() {
+        // Arrange
+        let template = Template::parse("struct s { uint16_t a; uint16_t b; };").unwrap_or_default();
+        let bytes = [Some(0x01), Some(0x00), None, Some(0x02)];
+
+        // Act
+        let decoded = decode_template(&template, "s", 0, false, &bytes);
+        let missing = decode_template(&template, "t", 0, false, &bytes);
+
+        // Assert
+        let decoded = decoded.unwrap_or_else(|| unreachable!());
+        assert_eq!(decoded.children[0].value, Some(FieldValue::Unsigned(1)));
+        assert_eq!(decoded.children[1].value, None);
+        assert_eq!(missing, None);
+    }
+
+    #[test]
+    fn test_template_parse_invalid() {
+        // Arrange
+        let cases = [
+            (
+                "struct a {\n  uint32_t x;\n  foo_t y;\n};",
+                IntelHexErrorKind::TemplateUnknownType("foo_t".to_string()),
+                3,
+            ),
+            (
+                "struct a {\n  uint32_t x : 3;\n};",
+                IntelHexErrorKind::TemplateUnsupported("bit-fields".to_string()),
+                2,
+            ),
+            (
+                "union u { uint8_t a; };",
+                IntelHexErrorKind::TemplateUnsupported("union".to_string()),
+                1,
+            ),
+            (
+                "struct a {\n  uint32_t x\n};",
+                IntelHexErrorKind::TemplateSyntax("';'".to_string()),
+                3,
+            ),
+            (
+                "struct a { struct b inner; };",
+                IntelHexErrorKind::TemplateUnknownType("struct b".to_string()),
+                1,
+            ),
+            (
+                "struct a {\n  uint64_t a[0x2000000000000000];\n};",
+                IntelHexErrorKind::TemplateUnsupported(
+                    "structs larger than 16777216 bytes".to_string(),
+                ),
+                3,
+            ),
+            (
+                "struct a {\n  uint8_t a[0xFFFFFFFFFFFFFFFF];\n  uint8_t b;\n};",
+                IntelHexErrorKind::TemplateUnsupported(
+                    "structs larger than 16777216 bytes".to_string(),
+                ),
+                4,
+            ),
+            (
+                "struct a { uint8_t a[0x8000]; struct { char c[8]; uint8_t d; } b[0x8000]; };",
+                IntelHexErrorKind::TemplateUnsupported(
+                    "structs with more than 65536 values".to_string(),
+                ),
+                1,
+            ),
+        ];
+
+        for (text, kind, line) in cases {
+            // Act
+            let result = Template::parse(text);
+
+            // Assert
+            assert_eq!(
+                result.err(),
+                Some(IntelHexError::ParseTemplateError(kind, line)),
+                "{text}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_template_encode() {
+        // Arrange
+        let template =
+            Template::parse("struct s { uint16_t u; int8_t i; __be32 f; float g; char name[4]; };")
+                .unwrap_or_default();
+        let decoded = decode(&template, "s", &[0; 15]).unwrap_or_else(|| unreachable!());
+        let [u, i, f, g, name] = decoded.children.as_slice() else {
+            unreachable!()
+        };
+
+        // Act & Assert
+        assert_eq!(u.encode("0x1234"), Some(vec![0x34, 0x12]));
+        assert_eq!(u.encode("65536"), None);
+        assert_eq!(u.encode("-1"), None);
+        assert_eq!(i.encode("-128"), Some(vec![0x80]));
+        assert_eq!(i.encode("128"), None);
+        assert_eq!(f.encode("1"), Some(vec![0x00, 0x00, 0x00, 0x01]));
+        assert_eq!(g.encode("1.5"), Some(vec![0x00, 0x00, 0xC0, 0x3F]));
+        assert_eq!(g.encode("abc"), None);
+        assert_eq!(name.encode("AB"), Some(vec![b'A', b'B', 0]));
+        assert_eq!(name.encode("ABCD"), Some(b"ABCD".to_vec()));
+        assert_eq!(name.encode("ABCDE"), None);
+        assert_eq!(decoded.encode("1"), None);
+        assert_eq!(ScalarType::Unsigned(3).encode("1", false), None);
+        assert_eq!(ScalarType::Signed(16).encode("1", false), None);
+        assert_eq!(ScalarType::Signed(0).encode("0", false), None);
+        assert_eq!(ScalarType::Float(2).encode("1.5", false), None);
+    }
+}
@@ -0,0 +1,181 @@
+//! The `transform` module defines [`Transform`] - in-place transformations of data bytes,
+//! such as word byte-swaps or bit-order reversal. Transforms are applied to address ranges
+//! of an [`IntelHex`](crate::IntelHex) instance via
+//! [`IntelHex::transform_range`](crate::IntelHex::transform_range).
+
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Transform {
+    /// Swap the byte order of every 16-bit word
+    Swap16,
+    /// Swap the byte order of every 32-bit word
+    Swap32,
+    /// Swap the byte order of every 64-bit word
+    Swap64,
+    /// Reverse the bit order within every byte (MSB <-> LSB)
+    ReverseBits,
+    /// Invert every bit
+    Invert,
+    /// XOR with a repeating key. The key starts over at the beginning of the range.
+    Xor(Vec<u8>),
+}
+
+impl Transform {
+    /// Size of the word (in bytes) the transform operates on.
+    /// The length of a transformed range has to be a multiple of it.
+    #[must_use]
+    pub const fn word_size(&self) -> usize {
+        match self {
+            Self::Swap16 => 2,
+            Self::Swap32 => 4,
+            Self::Swap64 => 8,
+            Self::ReverseBits | Self::Invert | Self::Xor(_) => 1,
+        }
+    }
+
+    /// Apply the transform to the data slice in place.
+    /// Trailing bytes that do not form a full word are left untouched.
+    pub(crate) fn apply(&self, data: &mut [u8]) {
+        match self {
+            Self::Swap16 | Self::Swap32 | Self::Swap64 => {
+                for word in data.chunks_exact_mut(self.word_size()) {
+                    word.reverse();
+                }
+            }
+            Self::ReverseBits => {
+                for b in data {
+                    *b = b.reverse_bits();
+                }
+            }
+            Self::Invert => {
+                for b in data {
+                    *b = !*b;
+                }
+            }
+            Self::Xor(key) => {
+                for (b, k) in data.iter_mut().zip(key.iter().cycle()) {
+                    *b ^= k;
+                }
+            }
+        }
+    }
+}
+
+impl fmt::Display for Transform {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Swap16 => write!(f, "swap16"),
+            Self::Swap32 => write!(f, "swap32"),
+            Self::Swap64 => write!(f, "swap64"),
+            Self::ReverseBits => write!(f, "reverse-bits"),
+            Self::Invert => write!(f, "invert"),
+            Self::Xor(key) => {
+                write!(f, "xor:")?;
+                key.iter().try_for_each(|b| write!(f, "{b:02X}"))
+            }
+        }
+    }
+}
+
+/// Parses the transform from its name, e.g. `swap16`, `reverse-bits`, `invert`
+/// or `xor:A55A` (XOR key given as hex bytes).
+impl FromStr for Transform {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "swap16" => Ok(Self::Swap16),
+            "swap32" => Ok(Self::Swap32),
+            "swap64" => Ok(Self::Swap64),
+            "reverse-bits" => Ok(Self::ReverseBits),
+            "invert" => Ok(Self::Invert),
+            other => {
+                let Some(key_str) = other.strip_prefix("xor:") else {
+                    return Err(format!("Unknown transform: {s}"));
+                };
+                let key_str = key_str.trim_start_matches("0x");
+                if key_str.is_empty()
+                    || !key_str.len().is_multiple_of(2)
+                    || !key_str.bytes().all(|b| b.is_ascii_hexdigit())
+                {
+                    return Err(format!("Invalid XOR key: {key_str}"));
+                }
+                // All chars are ASCII hex digits, so every pair is a valid byte
+                let key = key_str
+                    .as_bytes()
+                    .chunks_exact(2)
+                    .map(|pair| (hex_digit(pair[0]) << 4) | hex_digit(pair[1]))
+                    .collect();
+                Ok(Self::Xor(key))
+            }
+        }
+    }
+}
+
+/// Value of an ASCII hex digit (the digit must be valid).
+const fn hex_digit(digit: u8) -> u8 {
+    match digit {
+        b'0'..=b'9' => digit - b'0',
+        b'a'..=b'f' => digit - b'a' + 10,
+        _ => digit - b'A' + 10,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply_swaps() {
+        // Arrange
+        let data: Vec<u8> = (0..8).collect();
+
+        // Act
+        let mut d16 = data.clone();
+        Transform::Swap16.apply(&mut d16);
+        let mut d32 = data.clone();
+        Transform::Swap32.apply(&mut d32);
+        let mut d64 = data;
+        Transform::Swap64.apply(&mut d64);
+
+        // Assert
+        assert_eq!(d16, vec![1, 0, 3, 2, 5, 4, 7, 6]);
+        assert_eq!(d32, vec![3, 2, 1, 0, 7, 6, 5, 4]);
+        assert_eq!(d64, vec![7, 6, 5, 4, 3, 2, 1, 0]);
+    }
+
+    #[test]
+    fn test_apply_bitwise() {
+        // Arrange
+        let data = vec![0b0000_0001, 0b1100_0000, 0xFF, 0x00];
+
+        // Act
+        let mut reversed = data.clone();
+        Transform::ReverseBits.apply(&mut reversed);
+        let mut inverted = data.clone();
+        Transform::Invert.apply(&mut inverted);
+        let mut xored = data;
+        Transform::Xor(vec![0xAA, 0x55]).apply(&mut xored);
+
+        // Assert
+        assert_eq!(reversed, vec![0b1000_0000, 0b0000_0011, 0xFF, 0x00]);
+        assert_eq!(inverted, vec![0b1111_1110, 0b0011_1111, 0x00, 0xFF]);
+        assert_eq!(xored, vec![0xAB, 0x95, 0x55, 0x55]);
+    }
+
+    #[test]
+    fn test_parse_and_display() {
+        assert_eq!("swap16".parse(), Ok(Transform::Swap16));
+        assert_eq!("SWAP64".parse(), Ok(Transform::Swap64));
+        assert_eq!("reverse-bits".parse(), Ok(Transform::ReverseBits));
+        assert_eq!("xor:a55a".parse(), Ok(Transform::Xor(vec![0xA5, 0x5A])));
+        assert_eq!(Transform::Xor(vec![0xA5, 0x5A]).to_string(), "xor:A55A");
+        assert!("xor:".parse::<Transform>().is_err());
+        assert!("xor:ABC".parse::<Transform>().is_err());
+        assert!("xor:aé0".parse::<Transform>().is_err());
+        assert!("xor:+1".parse::<Transform>().is_err());
+        assert!("swap128".parse::<Transform>().is_err());
+    }
+}
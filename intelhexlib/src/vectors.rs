@@ -0,0 +1,239 @@
+//! The `vectors` module finds ARM Cortex-M vector tables in data chunks. A table is
+//! plausible if its initial stack pointer points into RAM and its reset, NMI and hard fault
+//! entries are Thumb addresses inside the image. See
+//! [`IntelHex::vector_tables`](crate::IntelHex::vector_tables).
+
+/// Alignment of vector tables (the smallest alignment `VTOR` supports)
+const TABLE_ALIGN: usize = 0x80;
+/// Number of entries of the largest table: 16 system entries and 496 interrupts
+const MAX_ENTRIES: usize = 512;
+/// Addresses a plausible initial stack pointer lies in (code region above `0x1000_0000`
+/// used for RAM by several vendors, and the SRAM region)
+const RAM_RANGE: std::ops::RangeInclusive<u32> = 0x1000_0000..=0x3FFF_FFFF;
+
+/// Name of the exception of a vector table entry, e.g. `Reset` for entry 1 or `IRQ0`
+/// for entry 16.
+///
+/// # Example
+/// ```
+/// use intelhexlib::exception_name;
+///
+/// assert_eq!(exception_name(3), "HardFault");
+/// assert_eq!(exception_name(21), "IRQ5");
+/// ```
+#[must_use]
+pub fn exception_name(index: usize) -> String {
+    let name = match index {
+        0 => "Initial SP",
+        1 => "Reset",
+        2 => "NMI",
+        3 => "HardFault",
+        4 => "MemManage",
+        5 => "BusFault",
+        6 => "UsageFault",
+        7..=10 | 13 => "Reserved",
+        11 => "SVCall",
+        12 => "DebugMonitor",
+        14 => "PendSV",
+        15 => "SysTick",
+        _ => return format!("IRQ{}", index - 16),
+    };
+    name.to_string()
+}
+
+/// Cortex-M vector table found by [`IntelHex::vector_tables`](crate::IntelHex::vector_tables).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VectorTable {
+    /// Start address of the table
+    pub address: usize,
+    /// Initial value of the main stack pointer (entry 0)
+    pub initial_sp: u32,
+    /// Raw values of entries 1 and up: handler addresses with the Thumb bit set, or 0
+    pub vectors: Vec<u32>,
+}
+
+impl VectorTable {
+    /// Number of entries including the initial stack pointer.
+    #[must_use]
+    pub const fn entry_count(&self) -> usize {
+        self.vectors.len() + 1
+    }
+
+    /// Address of the reset handler (without the Thumb bit).
+    #[must_use]
+    pub fn reset_handler(&self) -> usize {
+        self.vectors.first().map_or(0, |&v| (v & !1) as usize)
+    }
+
+    /// Handlers of the table as `(entry index, exception name, handler address)`.
+    /// The Thumb bit is cleared; empty and reserved entries are skipped.
+    pub fn handlers(&self) -> impl Iterator<Item = (usize, String, usize)> + '_ {
+        self.vectors
+            .iter()
+            .enumerate()
+            .map(|(i, &value)| (i + 1, value))
+            .filter(|&(index, value)| value != 0 && !matches!(index, 7..=10 | 13))
+            .map(|(index, value)| (index, exception_name(index), (value & !1) as usize))
+    }
+}
+
+/// Find plausible vector tables at `TABLE_ALIGN`-aligned addresses. Tables do not span gaps
+/// between chunks. Returns tables sorted by address.
+pub fn find_vector_tables<'a>(
+    iter: impl Iterator<Item = (&'a usize, &'a [u8])>,
+) -> Vec<VectorTable> {
+    let chunks: Vec<(usize, &[u8])> = iter.map(|(&addr, data)| (addr, data)).collect();
+
+    // Thumb address (odd) of an instruction inside the image
+    let is_handler = |value: u32| {
+        let addr = (value & !1) as usize;
+        let idx = chunks.partition_point(|(start, _)| *start <= addr);
+        value & 1 == 1 && idx > 0 && addr < chunks[idx - 1].0 + chunks[idx - 1].1.len()
+    };
+
+    let mut tables = Vec::new();
+    for &(start, data) in &chunks {
+        let mut offset = (TABLE_ALIGN - start % TABLE_ALIGN) % TABLE_ALIGN;
+        while offset + 16 * 4 <= data.len() {
+            if let Some(table) = decode_table(start + offset, &data[offset..], &is_handler) {
+                // Continue after the table
+                offset += (table.entry_count() * 4).next_multiple_of(TABLE_ALIGN);
+                tables.push(table);
+            } else {
+                offset += TABLE_ALIGN;
+            }
+        }
+    }
+
+    tables
+}
+
+/// Decode the vector table at the start of `data` if it is plausible.
+fn decode_table(
+    address: usize,
+    data: &[u8],
+    is_handler: &impl Fn(u32) -> bool,
+) -> Option<VectorTable> {
+    let word = |i: usize| {
+        let w = data.get(i * 4..i * 4 + 4)?;
+        Some(u32::from_le_bytes([w[0], w[1], w[2], w[3]]))
+    };
+
+    // Most offsets fail on the initial SP or the reset handler, check them before the rest
+    let initial_sp = word(0)?;
+    if !initial_sp.is_multiple_of(4) || !RAM_RANGE.contains(&initial_sp) {
+        return None;
+    }
+    if !is_handler(word(1)?) {
+        return None;
+    }
+
+    let words: Vec<u32> = data
+        .chunks_exact(4)
+        .take(MAX_ENTRIES)
+        .map(|w| u32::from_le_bytes([w[0], w[1], w[2], w[3]]))
+        .collect();
+
+    // NMI and HardFault handlers are mandatory too
+    if !words[2..4].iter().all(|&w| is_handler(w)) {
+        return None;
+    }
+    // Configurable system handlers are optional
+    if ![4, 5, 6, 11, 12, 14, 15]
+        .iter()
+        .all(|&i| words[i] == 0 || is_handler(words[i]))
+    {
+        return None;
+    }
+
+    // Interrupt handlers up to the first implausible entry, without trailing empty ones
+    let irqs = words[16..]
+        .iter()
+        .take_while(|&&w| w == 0 || is_handler(w))
+        .count();
+    let mut len = 16 + irqs;
+    while len > 16 && words[len - 1] == 0 {
+        len -= 1;
+    }
+
+    Some(VectorTable {
+        address,
+        initial_sp,
+        vectors: words[1..len].to_vec(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Vector table at `base` with handlers inside a 0x200 bytes image followed by `irqs`
+    fn image(base: u32, irqs: &[u32]) -> Vec<u8> {
+        let mut words = vec![0x2000_5000, base + 0x101, base + 0x105, base + 0x107];
+        words.extend_from_slice(&[0; 11]);
+        words.push(base + 0x109); // SysTick
+        words.extend_from_slice(irqs);
+
+        let mut data: Vec<u8> = words.iter().flat_map(|w| w.to_le_bytes()).collect();
+        data.resize(0x200, 0x00);
+        data
+    }
+
+    #[test]
+    fn test_find_vector_tables_valid() {
+        // Arrange
+        let base = 0x0800_0000;
+        let data = image(base, &[base + 0x10B, 0, base + 0x10D, 0, 0]);
+        let chunks = [(base as usize, data.as_slice())];
+
+        // Act
+        let tables = find_vector_tables(chunks.iter().map(|(addr, data)| (addr, *data)));
+
+        // Assert
+        assert_eq!(tables.len(), 1);
+        let table = &tables[0];
+        assert_eq!(table.address, 0x0800_0000);
+        assert_eq!(table.initial_sp, 0x2000_5000);
+        assert_eq!(table.entry_count(), 19);
+        assert_eq!(table.reset_handler(), 0x0800_0100);
+        let handlers: Vec<(usize, String, usize)> = table.handlers().collect();
+        assert_eq!(
+            handlers,
+            vec![
+                (1, "Reset".to_string(), 0x0800_0100),
+                (2, "NMI".to_string(), 0x0800_0104),
+                (3, "HardFault".to_string(), 0x0800_0106),
+                (15, "SysTick".to_string(), 0x0800_0108),
+                (16, "IRQ0".to_string(), 0x0800_010A),
+                (18, "IRQ2".to_string(), 0x0800_010C),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_find_vector_tables_invalid() {
+        // Arrange - handlers outside of the image, even (ARM) handlers, SP outside of RAM
+        let base = 0x0800_0000;
+        let outside = image(0x0900_0000, &[]);
+        let mut even = image(base, &[]);
+        even[4] = 0x00;
+        let mut rom_sp = image(base, &[]);
+        rom_sp[3] = 0x08;
+        let unaligned = image(base + 0x40, &[]);
+
+        // Act
+        let found: Vec<usize> = [outside, even, rom_sp]
+            .iter()
+            .map(|data| {
+                let chunks = [(base as usize, data.as_slice())];
+                find_vector_tables(chunks.iter().map(|(addr, data)| (addr, *data))).len()
+            })
+            .collect();
+        let chunks = [(base as usize + 0x40, unaligned.as_slice())];
+        let shifted = find_vector_tables(chunks.iter().map(|(addr, data)| (addr, *data)));
+
+        // Assert
+        assert_eq!(found, vec![0, 0, 0]);
+        assert_eq!(shifted, Vec::new());
+    }
+}
@@ -0,0 +1,223 @@
+//! The `xrefs` module finds cross-references in data chunks: 16, 32 or 64-bit words whose
+//! value is an address inside the populated part of the image. See
+//! [`IntelHex::references`](crate::IntelHex::references).
+
+use crate::search::{ValueEncoding, ValueKind};
+use std::collections::BTreeMap;
+
+/// Pointer encodings: unsigned 16, 32 and 64-bit words in little- and big-endian.
+pub const POINTER_ENCODINGS: [ValueEncoding; 6] = [
+    ValueEncoding {
+        kind: ValueKind::U16,
+        big_endian: false,
+    },
+    ValueEncoding {
+        kind: ValueKind::U16,
+        big_endian: true,
+    },
+    ValueEncoding {
+        kind: ValueKind::U32,
+        big_endian: false,
+    },
+    ValueEncoding {
+        kind: ValueKind::U32,
+        big_endian: true,
+    },
+    ValueEncoding {
+        kind: ValueKind::U64,
+        big_endian: false,
+    },
+    ValueEncoding {
+        kind: ValueKind::U64,
+        big_endian: true,
+    },
+];
+
+/// Word referring to an address, found by [`IntelHex::references`](crate::IntelHex::references).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Reference {
+    /// Address of the referring word
+    pub address: usize,
+    /// Size and byte order of the referring word
+    pub encoding: ValueEncoding,
+}
+
+/// Index of cross-references: maps every referenced address (target) to the words
+/// referring to it, sorted by their address.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReferenceIndex {
+    targets: BTreeMap<usize, Vec<Reference>>,
+}
+
+impl ReferenceIndex {
+    /// Words referring to `target`. Empty if the address is not referenced.
+    #[must_use]
+    pub fn referrers(&self, target: usize) -> &[Reference] {
+        self.targets.get(&target).map_or(&[], Vec::as_slice)
+    }
+
+    /// Iterate over the referenced addresses and their referrers in ascending address order.
+    pub fn iter(&self) -> impl Iterator<Item = (usize, &[Reference])> {
+        self.targets
+            .iter()
+            .map(|(&target, refs)| (target, refs.as_slice()))
+    }
+
+    /// Number of referenced addresses.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.targets.len()
+    }
+
+    /// Returns true if no address is referenced.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.targets.is_empty()
+    }
+}
+
+/// Find words of the given `encodings` whose value is an address inside a chunk and passes
+/// `filter`. Only the size and byte order of an encoding are used; every word is read as
+/// unsigned. If `aligned` is set, words are only read at addresses that are a multiple of
+/// their size. Words do not span gaps between chunks.
+pub fn find_references<'a>(
+    iter: impl Iterator<Item = (&'a usize, &'a [u8])>,
+    encodings: &[ValueEncoding],
+    aligned: bool,
+    filter: impl Fn(usize) -> bool,
+) -> ReferenceIndex {
+    let chunks: Vec<(usize, &[u8])> = iter.map(|(&addr, data)| (addr, data)).collect();
+
+    // Address inside the image
+    let is_populated = |addr: usize| {
+        let idx = chunks.partition_point(|(start, _)| *start <= addr);
+        idx > 0 && addr < chunks[idx - 1].0 + chunks[idx - 1].1.len()
+    };
+
+    let mut targets: BTreeMap<usize, Vec<Reference>> = BTreeMap::new();
+    for &(start, data) in &chunks {
+        for &encoding in encodings {
+            let size = encoding.kind.size();
+            let (first, step) = if aligned {
+                ((size - start % size) % size, size)
+            } else {
+                (0, 1)
+            };
+
+            let mut offset = first;
+            while offset + size <= data.len() {
+                let target = read_word(&data[offset..offset + size], encoding.big_endian);
+                if let Ok(target) = usize::try_from(target)
+                    && is_populated(target)
+                    && filter(target)
+                {
+                    targets.entry(target).or_default().push(Reference {
+                        address: start + offset,
+                        encoding,
+                    });
+                }
+                offset += step;
+            }
+        }
+    }
+
+    // Referrers of every target in ascending address order
+    for refs in targets.values_mut() {
+        refs.sort_by_key(|r| r.address);
+    }
+
+    ReferenceIndex { targets }
+}
+
+/// Read an unsigned word of up to 8 bytes.
+pub fn read_word(bytes: &[u8], big_endian: bool) -> u64 {
+    let fold = |acc: u64, &b: &u8| (acc << 8) | u64::from(b);
+    if big_endian {
+        bytes.iter().fold(0, fold)
+    } else {
+        bytes.iter().rev().fold(0, fold)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_references_valid() {
+        // Arrange - u32 LE pointer to 0x1008 at 0x1000, u16 BE pointer to 0x1004 at 0x100C
+        let data = [
+            0x08, 0x10, 0x00, 0x00, 0xAA, 0xBB, 0xCC, 0xDD, // 0x1000
+            0x00, 0x00, 0x00, 0x00, 0x10, 0x04, 0x00, 0x00, // 0x1008
+        ];
+        let chunks = [(0x1000, data.as_slice())];
+
+        // Act
+        let index = find_references(
+            chunks.iter().map(|(addr, data)| (addr, *data)),
+            &POINTER_ENCODINGS,
+            true,
+            |_| true,
+        );
+
+        // Assert
+        assert_eq!(index.len(), 2);
+        // The low half of the u32 pointer is a u16 pointer as well
+        assert_eq!(
+            index.referrers(0x1008),
+            &[
+                Reference {
+                    address: 0x1000,
+                    encoding: POINTER_ENCODINGS[0],
+                },
+                Reference {
+                    address: 0x1000,
+                    encoding: POINTER_ENCODINGS[2],
+                }
+            ]
+        );
+        assert_eq!(
+            index.referrers(0x1004),
+            &[Reference {
+                address: 0x100C,
+                encoding: POINTER_ENCODINGS[1],
+            }]
+        );
+        assert_eq!(index.referrers(0x1000), &[]);
+    }
+
+    #[test]
+    fn test_find_references_unaligned_and_filtered() {
+        // Arrange - u16 LE pointers to 0x2003 at the odd address 0x2001 and to 0x2000 at 0x2004
+        let data = [0xFF, 0x03, 0x20, 0xFF, 0x00, 0x20];
+        let chunks = [(0x2000, data.as_slice())];
+        let encodings = &POINTER_ENCODINGS[..1];
+
+        // Act
+        let aligned = find_references(
+            chunks.iter().map(|(addr, data)| (addr, *data)),
+            encodings,
+            true,
+            |_| true,
+        );
+        let unaligned = find_references(
+            chunks.iter().map(|(addr, data)| (addr, *data)),
+            encodings,
+            false,
+            |_| true,
+        );
+        let filtered = find_references(
+            chunks.iter().map(|(addr, data)| (addr, *data)),
+            encodings,
+            false,
+            |target| target == 0x2003,
+        );
+
+        // Assert
+        let targets = |index: &ReferenceIndex| index.iter().map(|(t, _)| t).collect::<Vec<_>>();
+        assert_eq!(targets(&aligned), vec![0x2000]);
+        assert_eq!(targets(&unaligned), vec![0x2000, 0x2003]);
+        assert_eq!(targets(&filtered), vec![0x2003]);
+        assert_eq!(filtered.referrers(0x2003)[0].address, 0x2001);
+    }
+}
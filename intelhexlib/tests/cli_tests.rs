@@ -123,6 +123,68 @@ fn test_ihex_shows_info_invalid() {
     );
 }
 
+#[test]
+fn test_ihex_shows_info_vector_table() {
+    // Arrange
+    let path_str = "tests/fixtures/cortex_m.hex";
+
+    // Act
+    let output = Command::new(HEXCLI_EXE)
+        .args(["info", path_str])
+        .output()
+        .expect("Failed to run ihex");
+
+    // Assert
+    assert!(
+        output.status.success(),
+        "command failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    for expected in [
+        "Vector Table: 0x0800_0000 (20 entries)",
+        "  Initial SP    0x2000_5000",
+        "  Reset         0x0800_0100",
+        "  SysTick       0x0800_0108",
+        "  IRQs          3 handlers",
+    ] {
+        assert!(
+            stdout.contains(expected),
+            "stdout did not contain expected line '{expected}':\n{stdout}"
+        );
+    }
+}
+
+#[test]
+fn test_ihex_shows_info_layout_per_source() {
+    // Arrange
+    let boot_path = "tests/fixtures/ih_valid_1.hex";
+    let app_path = "tests/fixtures/ih_valid_3.hex";
+
+    // Act
+    let output = Command::new(HEXCLI_EXE)
+        .args(["info", boot_path, app_path])
+        .output()
+        .expect("Failed to run ihex");
+
+    // Assert
+    assert!(
+        output.status.success(),
+        "command failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("Layout")
+            && stdout.contains("0x0000_0000   0x0000_0003         4 bytes   ih_valid_1.hex")
+            && stdout.contains("0x0000_0100   0x0000_0133        52 bytes   ih_valid_3.hex")
+            && stdout.contains("0x0001_C200   0x0001_C23F        64 bytes   ih_valid_1.hex"),
+        "stdout did not contain the per-source layout table:\n{stdout}"
+    );
+}
+
 #[test]
 fn test_ihex_relocate_valid() {
     // Arrange
@@ -652,3 +714,581 @@ fn test_ihex_merge_invalid() {
         "stderr did not contain expected error text:\n{stderr}"
     );
 }
+
+#[test]
+fn test_ihex_transform_valid() {
+    // Arrange
+    let in_path_str = "tests/fixtures/ih_valid_1.hex";
+    let out_path_str = "build/t6-cli/ih.hex";
+
+    // Act
+    let output = Command::new(HEXCLI_EXE)
+        .args([
+            "transform",
+            in_path_str,
+            out_path_str,
+            "--op",
+            "swap16",
+            "--range",
+            "0x0:0x3",
+        ])
+        .output()
+        .expect("Failed to run ihex");
+
+    // Assert
+    assert!(
+        output.status.success(),
+        "command failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let ih = IntelHex::from_hex(out_path_str).expect("Failed to load transformed file");
+    assert_eq!(ih.read_range(0x0, 4), Some(vec![0x00, 0xFA, 0x02, 0x00]));
+
+    // Act - XOR every chunk (no range)
+    let output = Command::new(HEXCLI_EXE)
+        .args(["transform", in_path_str, out_path_str, "--op", "xor:FF"])
+        .output()
+        .expect("Failed to run ihex");
+
+    // Assert
+    assert!(
+        output.status.success(),
+        "command failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let ih = IntelHex::from_hex(out_path_str).expect("Failed to load transformed file");
+    assert_eq!(ih.read_range(0x0, 4), Some(vec![0x05, 0xFF, 0xFF, 0xFD]));
+
+    // Act - chunks that are not whole 8-byte words are skipped
+    let output = Command::new(HEXCLI_EXE)
+        .args(["transform", in_path_str, out_path_str, "--op", "swap64"])
+        .output()
+        .expect("Failed to run ihex");
+
+    // Assert
+    assert!(
+        output.status.success(),
+        "command failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("Skipped 0x00000000 - 0x00000003 (4 bytes)"),
+        "stderr did not contain expected warning:\n{stderr}"
+    );
+
+    let ih = IntelHex::from_hex(out_path_str).expect("Failed to load transformed file");
+    assert_eq!(ih.read_range(0x0, 4), Some(vec![0xFA, 0x00, 0x00, 0x02]));
+}
+
+#[test]
+fn test_ihex_transform_invalid() {
+    // Arrange
+    let in_path_str = "tests/fixtures/ih_valid_1.hex";
+    let out_path_str = "build/t7-cli/ih.hex";
+
+    // Act - unknown transform
+    let output = Command::new(HEXCLI_EXE)
+        .args(["transform", in_path_str, out_path_str, "--op", "swap128"])
+        .output()
+        .expect("Failed to run ihex");
+
+    // Assert
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("Unknown transform"),
+        "stderr did not contain expected error text:\n{stderr}"
+    );
+
+    // Act - XOR key with non-hex chars
+    let output = Command::new(HEXCLI_EXE)
+        .args(["transform", in_path_str, out_path_str, "--op", "xor:aé0"])
+        .output()
+        .expect("Failed to run ihex");
+
+    // Assert
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("Invalid XOR key"),
+        "stderr did not contain expected error text:\n{stderr}"
+    );
+
+    // Act - range is not a multiple of the word size
+    let output = Command::new(HEXCLI_EXE)
+        .args([
+            "transform",
+            in_path_str,
+            out_path_str,
+            "--op",
+            "swap32",
+            "--range",
+            "0x0:0x2",
+        ])
+        .output()
+        .expect("Failed to run ihex");
+
+    // Assert
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("not a multiple of the 4-byte word"),
+        "stderr did not contain expected error text:\n{stderr}"
+    );
+}
+
+#[test]
+fn test_ihex_split_and_join_lanes_valid() {
+    // Arrange
+    let in_path_str = "tests/fixtures/ih_valid_1.hex";
+    let even_path_str = "build/t8-cli/even.hex";
+    let odd_path_str = "build/t8-cli/odd.hex";
+    let out_path_str = "build/t8-cli/joined.hex";
+
+    // Act
+    let output_split = Command::new(HEXCLI_EXE)
+        .args(["split-lanes", in_path_str, even_path_str, odd_path_str])
+        .output()
+        .expect("Failed to run ihex");
+    let output_join = Command::new(HEXCLI_EXE)
+        .args(["join-lanes", out_path_str, even_path_str, odd_path_str])
+        .output()
+        .expect("Failed to run ihex");
+
+    // Assert
+    assert!(
+        output_split.status.success(),
+        "command failed: {}",
+        String::from_utf8_lossy(&output_split.stderr)
+    );
+    assert!(
+        output_join.status.success(),
+        "command failed: {}",
+        String::from_utf8_lossy(&output_join.stderr)
+    );
+
+    let ih = IntelHex::from_hex(in_path_str).expect("Failed to load input file");
+    let even = IntelHex::from_hex(even_path_str).expect("Failed to load even lane");
+    let odd = IntelHex::from_hex(odd_path_str).expect("Failed to load odd lane");
+    let joined = IntelHex::from_hex(out_path_str).expect("Failed to load joined file");
+
+    assert_eq!(even.read_range(0x0, 2), Some(vec![0xFA, 0x00]));
+    assert_eq!(odd.read_range(0x0, 2), Some(vec![0x00, 0x02]));
+    assert!(joined.bytes().eq(ih.bytes()));
+}
+
+#[test]
+fn test_ihex_split_lanes_invalid() {
+    // Arrange
+    let in_path_str = "tests/fixtures/ih_valid_1.hex";
+
+    // Act - only one lane output given
+    let output = Command::new(HEXCLI_EXE)
+        .args([
+            "split-lanes",
+            in_path_str,
+            "build/t9-cli/lane0.hex",
+            "--gap-fill",
+            "0x00",
+        ])
+        .output()
+        .expect("Failed to run ihex");
+
+    // Assert
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("At least two lane output paths are required"),
+        "stderr did not contain expected error text:\n{stderr}"
+    );
+}
+
+#[test]
+#[allow(clippy::literal_string_with_formatting_args)]
+fn test_ihex_split_valid() {
+    // Arrange
+    let in_path_str = "tests/fixtures/ih_valid_1.hex";
+    let template = "build/t10-cli/{name}_{start:08X}.bin";
+
+    // Act
+    let output_every = Command::new(HEXCLI_EXE)
+        .args(["split", in_path_str, template, "--every", "0x20"])
+        .output()
+        .expect("Failed to run ihex");
+    let output_region = Command::new(HEXCLI_EXE)
+        .args([
+            "split",
+            in_path_str,
+            "build/t10-cli/{index}_{name}.hex",
+            "--region",
+            "boot:0x0:0xFFFF",
+            "--region",
+            "app:0x10000:0x1FFFF",
+        ])
+        .output()
+        .expect("Failed to run ihex");
+
+    // Assert
+    assert!(
+        output_every.status.success(),
+        "command failed: {}",
+        String::from_utf8_lossy(&output_every.stderr)
+    );
+    assert!(
+        output_region.status.success(),
+        "command failed: {}",
+        String::from_utf8_lossy(&output_region.stderr)
+    );
+
+    for path in [
+        "build/t10-cli/part0_00000000.bin",
+        "build/t10-cli/part1_0001C200.bin",
+        "build/t10-cli/part2_0001C220.bin",
+    ] {
+        let metadata = std::fs::metadata(path).expect("Missing split output file");
+        assert_ne!(metadata.len(), 0);
+    }
+
+    let boot = IntelHex::from_hex("build/t10-cli/0_boot.hex").expect("Failed to load boot part");
+    let app = IntelHex::from_hex("build/t10-cli/1_app.hex").expect("Failed to load app part");
+    assert_eq!(boot.size, 4);
+    assert_eq!(app.get_min_addr(), Some(0x1C200));
+    assert_eq!(app.size, 64);
+}
+
+#[test]
+fn test_ihex_split_invalid() {
+    // Arrange
+    let in_path_str = "tests/fixtures/ih_valid_1.hex";
+
+    // Act
+    let output_placeholder = Command::new(HEXCLI_EXE)
+        .args(["split", in_path_str, "build/t11-cli/{id}.hex"])
+        .output()
+        .expect("Failed to run ihex");
+    let output_overlap = Command::new(HEXCLI_EXE)
+        .args([
+            "split",
+            in_path_str,
+            "build/t11-cli/{name}.hex",
+            "--region",
+            "a:0x0:0xFF",
+            "--region",
+            "b:0x80:0x1FF",
+        ])
+        .output()
+        .expect("Failed to run ihex");
+    let output_same_name = Command::new(HEXCLI_EXE)
+        .args([
+            "split",
+            in_path_str,
+            "build/t11-cli/part.hex",
+            "--every",
+            "0x10",
+        ])
+        .output()
+        .expect("Failed to run ihex");
+
+    // Assert
+    assert!(!output_placeholder.status.success());
+    let stderr = String::from_utf8_lossy(&output_placeholder.stderr);
+    assert!(
+        stderr.contains("Unknown placeholder in template: {id}"),
+        "stderr did not contain expected error text:\n{stderr}"
+    );
+
+    assert!(!output_overlap.status.success());
+    let stderr = String::from_utf8_lossy(&output_overlap.stderr);
+    assert!(
+        stderr.contains("Memory region overlaps"),
+        "stderr did not contain expected error text:\n{stderr}"
+    );
+
+    assert!(!output_same_name.status.success());
+    let stderr = String::from_utf8_lossy(&output_same_name.stderr);
+    assert!(
+        stderr.contains("gives the same file name"),
+        "stderr did not contain expected error text:\n{stderr}"
+    );
+    assert!(!std::path::Path::new("build/t11-cli/part.hex").exists());
+}
+
+#[test]
+fn test_ihex_convert_range() {
+    // Arrange
+    let in_path_str = "tests/fixtures/ih_valid_1.hex";
+    let out_path_str = "build/t12-cli/ih.bin";
+
+    // Act
+    let output = Command::new(HEXCLI_EXE)
+        .args([
+            "convert",
+            in_path_str,
+            out_path_str,
+            "--range",
+            "0x0:0xF",
+            "--gap-fill",
+            "0x00",
+        ])
+        .output()
+        .expect("Failed to run ihex");
+    let output_strict = Command::new(HEXCLI_EXE)
+        .args([
+            "convert",
+            in_path_str,
+            out_path_str,
+            "--range",
+            "0x0:0xF",
+            "--strict",
+        ])
+        .output()
+        .expect("Failed to run ihex");
+
+    // Assert
+    assert!(
+        output.status.success(),
+        "command failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let bin = std::fs::read(out_path_str).expect("Failed to read output file");
+    assert_eq!(bin.len(), 16);
+    assert_eq!(bin[..5], [0xFA, 0x00, 0x00, 0x02, 0x00]);
+
+    assert!(!output_strict.status.success());
+    let stderr = String::from_utf8_lossy(&output_strict.stderr);
+    assert!(
+        stderr.contains("Data found outside of the address window at: 0x1C23F"),
+        "stderr did not contain expected error text:\n{stderr}"
+    );
+}
+
+#[test]
+fn test_ihex_scan_valid() {
+    // Arrange
+    let in_path_str = "tests/fixtures/ih_valid_1.hex";
+    let patterns_path_str = "tests/fixtures/scan_patterns.txt";
+
+    // Act
+    let output = Command::new(HEXCLI_EXE)
+        .args(["scan", in_path_str, "--patterns", patterns_path_str])
+        .output()
+        .expect("Failed to run ihex");
+
+    // Assert
+    assert!(
+        output.status.success(),
+        "command failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("0x00000001  00 00 02"),
+        "stdout did not contain expected match:\n{stdout}"
+    );
+    assert!(
+        stdout.contains("0x0001C237  \"H0Y\""),
+        "stdout did not contain expected match:\n{stdout}"
+    );
+    assert!(
+        stdout.contains("Found 2 matches of 3 patterns"),
+        "stdout did not contain expected summary:\n{stdout}"
+    );
+}
+
+#[test]
+fn test_ihex_scan_invalid() {
+    // Arrange
+    let in_path_str = "tests/fixtures/ih_valid_1.hex";
+    let patterns_path_str = "build/t13-cli/patterns.txt";
+    std::fs::create_dir_all("build/t13-cli").expect("Failed to create output directory");
+    std::fs::write(patterns_path_str, "# comment\nDE AD\nDEA\n")
+        .expect("Failed to write pattern file");
+
+    // Act - invalid pattern
+    let output = Command::new(HEXCLI_EXE)
+        .args(["scan", in_path_str, "--patterns", patterns_path_str])
+        .output()
+        .expect("Failed to run ihex");
+
+    // Assert
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("Invalid pattern at line 3: DEA"),
+        "stderr did not contain expected error text:\n{stderr}"
+    );
+
+    // Act - missing pattern file
+    let output = Command::new(HEXCLI_EXE)
+        .args([
+            "scan",
+            in_path_str,
+            "--patterns",
+            "build/t13-cli/missing.txt",
+        ])
+        .output()
+        .expect("Failed to run ihex");
+
+    // Assert
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("File not found: build/t13-cli/missing.txt"),
+        "stderr did not contain expected error text:\n{stderr}"
+    );
+}
+
+#[test]
+fn test_ihex_scan_signatures() {
+    // Arrange
+    let in_path_str = "tests/fixtures/signatures.bin";
+
+    // Act
+    let output = Command::new(HEXCLI_EXE)
+        .args(["scan", in_path_str, "--address", "0x8000"])
+        .output()
+        .expect("Failed to run ihex");
+
+    // Assert
+    assert!(
+        output.status.success(),
+        "command failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    for expected in [
+        "0x00008040  gzip              gzip compressed data, original name: \"config.txt\"",
+        "0x00008100  PNG               PNG image, 2 x 2, 8-bit RGBA (74 bytes)",
+        "0x00008200  Intel HEX         Intel HEX, 4 records, 34 data bytes (120 bytes)",
+        "Found 4 embedded files",
+    ] {
+        assert!(
+            stdout.contains(expected),
+            "stdout did not contain expected line '{expected}':\n{stdout}"
+        );
+    }
+}
+
+#[test]
+fn test_ihex_search_value() {
+    // Arrange
+    let in_path_str = "tests/fixtures/ih_valid_1.hex";
+
+    // Act
+    let output = Command::new(HEXCLI_EXE)
+        .args(["search", in_path_str, "--value", "2"])
+        .output()
+        .expect("Failed to run ihex");
+    let output_invalid = Command::new(HEXCLI_EXE)
+        .args(["search", in_path_str, "--value", "two"])
+        .output()
+        .expect("Failed to run ihex");
+
+    // Assert
+    assert!(
+        output.status.success(),
+        "command failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("0x00000002  u16 BE"),
+        "stdout did not contain expected match:\n{stdout}"
+    );
+
+    assert!(!output_invalid.status.success());
+    let stderr = String::from_utf8_lossy(&output_invalid.stderr);
+    assert!(
+        stderr.contains("Invalid value: two"),
+        "stderr did not contain expected error text:\n{stderr}"
+    );
+}
+
+#[test]
+fn test_ihex_strings() {
+    // Arrange
+    let in_path_str = "tests/fixtures/ih_valid_1.hex";
+
+    // Act
+    let output = Command::new(HEXCLI_EXE)
+        .args([
+            "strings",
+            in_path_str,
+            "--min-len",
+            "3",
+            "--encoding",
+            "ascii",
+        ])
+        .output()
+        .expect("Failed to run ihex");
+    let output_invalid = Command::new(HEXCLI_EXE)
+        .args(["strings", in_path_str, "--encoding", "ascii,ebcdic"])
+        .output()
+        .expect("Failed to run ihex");
+
+    // Assert
+    assert!(
+        output.status.success(),
+        "command failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("0x0001C236  ASCII      H0Y-"),
+        "stdout did not contain expected string:\n{stdout}"
+    );
+    assert!(
+        !stdout.contains("UTF-16"),
+        "stdout contained strings of other encodings:\n{stdout}"
+    );
+
+    assert!(!output_invalid.status.success());
+    let stderr = String::from_utf8_lossy(&output_invalid.stderr);
+    assert!(
+        stderr.contains("Unknown string encoding: ebcdic"),
+        "stderr did not contain expected error text:\n{stderr}"
+    );
+}
+
+#[test]
+fn test_ihex_entropy() {
+    // Arrange
+    let in_path_str = "tests/fixtures/ih_valid_1.hex";
+
+    // Act
+    let output = Command::new(HEXCLI_EXE)
+        .args(["entropy", in_path_str, "--block", "0x20"])
+        .output()
+        .expect("Failed to run ihex");
+    let output_invalid = Command::new(HEXCLI_EXE)
+        .args(["entropy", in_path_str, "--block", "0"])
+        .output()
+        .expect("Failed to run ihex");
+
+    // Assert
+    assert!(
+        output.status.success(),
+        "command failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("0x00000000    1.500  |#########  "),
+        "stdout did not contain expected block:\n{stdout}"
+    );
+    assert!(
+        stdout.contains("Analyzed 3 blocks of 0x20 bytes"),
+        "stdout did not contain expected summary:\n{stdout}"
+    );
+
+    assert!(!output_invalid.status.success());
+    let stderr = String::from_utf8_lossy(&output_invalid.stderr);
+    assert!(
+        stderr.contains("Invalid block size: 0 bytes"),
+        "stderr did not contain expected error text:\n{stderr}"
+    );
+}
@@ -115,6 +115,121 @@ fn test_load_hex_and_write_bin() {
     assert!(res.is_ok());
 }
 
+#[test]
+fn test_write_bin_range() {
+    // Define in/out paths
+    let input_path = "tests/fixtures/ih_valid_1.hex";
+    let output_path_pad = "build/t7/ih_pad.bin";
+    let output_path_cut = "build/t7/ih_cut.bin";
+
+    // Load hex and check the result
+    let res = IntelHex::from_hex(input_path);
+    assert!(res.is_ok());
+    let mut ih = res.unwrap_or_default();
+
+    // Window starting before and ending after the first chunk - padded with gap fill
+    let res = ih.write_bin_range(output_path_pad, 0x1C1FE, 0x1C241, 0xAA);
+    assert!(res.is_ok());
+
+    let bin = fs::read(output_path_pad).unwrap_or_default();
+    assert_eq!(bin.len(), 0x44);
+    assert_eq!(bin[..2], [0xAA, 0xAA]);
+    assert_eq!(bin[0x42..], [0xAA, 0xAA]);
+    assert_eq!(
+        bin[2..0x42],
+        ih.read_range(0x1C200, 0x40).unwrap_or_default()
+    );
+
+    // Window cutting into the data - truncated
+    let res = ih.write_bin_range(output_path_cut, 0x1, 0x2, 0xAA);
+    assert!(res.is_ok());
+    assert_eq!(
+        fs::read(output_path_cut).unwrap_or_default(),
+        vec![0x00, 0x00]
+    );
+
+    // Same window, but data outside of it is an error
+    let res = ih.write_bin_range_safe(output_path_cut, 0x1, 0x2, 0xAA);
+    assert_eq!(
+        res,
+        Err(IntelHexError::UpdateError(
+            IntelHexErrorKind::DataOutsideRange(0x0)
+        ))
+    );
+
+    // Window covering all data
+    let res = ih.write_bin_range_safe(output_path_cut, 0x0, 0x1FFFF, 0xAA);
+    assert!(res.is_ok());
+    assert_eq!(
+        fs::metadata(output_path_cut).map(|m| m.len()).ok(),
+        Some(0x20000)
+    );
+}
+
+#[test]
+fn test_load_bin_mapped() {
+    // Define in/out paths (the mapped file is a copy, so the fixture stays intact)
+    let fixture_path = "tests/fixtures/ih_valid_1.bin";
+    let input_path = "build/t8/ih_mapped.bin";
+    let output_path = "build/t8/ih_patched.bin";
+    let _ = fs::create_dir_all("build/t8");
+    assert!(fs::copy(fixture_path, input_path).is_ok());
+
+    // Mapped data matches data read into memory
+    let base_addr = 0x1000;
+    let res = IntelHex::from_bin_mapped(input_path, base_addr);
+    assert!(res.is_ok());
+    let mut ih = res.unwrap_or_default();
+    let ih_read = IntelHex::from_bin(fixture_path, base_addr).unwrap_or_default();
+    assert!(ih.is_mapped());
+    assert_eq!(ih.size, ih_read.size);
+    assert!(ih.iter().eq(ih_read.iter()));
+
+    // Searching works against the mapping
+    let pattern = ih_read.read_range(0x2000, 4).unwrap_or_default();
+    assert_eq!(ih.search_bytes(&pattern), ih_read.search_bytes(&pattern));
+
+    // Edits go to the overlay and are undone in place, the file is never modified
+    ih.enable_journal();
+    let old_byte = ih.read_byte(base_addr).unwrap_or_default();
+    assert!(ih.update_byte(base_addr, !old_byte).is_ok());
+    assert_eq!(ih.read_byte(base_addr), Some(!old_byte));
+    assert!(compare_files(fixture_path, input_path));
+
+    assert!(ih.write_bin(output_path, 0x00).is_ok());
+    let patched = fs::read(output_path).unwrap_or_default();
+    assert_eq!(patched.first(), Some(&!old_byte));
+
+    assert!(ih.undo());
+    assert!(ih.is_mapped());
+    assert_eq!(ih.read_byte(base_addr), Some(old_byte));
+
+    // Edits of a snapshotted image, removing a range and merging a patch keep the data mapped
+    let original = ih.snapshot();
+    assert!(ih.update_byte(base_addr + 0x10, 0xAB).is_ok());
+    assert!(
+        ih.remove_range(base_addr + 0x2000, base_addr + 0x4FFF)
+            .is_ok()
+    );
+    let mut patch = IntelHex::new();
+    assert!(
+        patch
+            .write_range(base_addr + 0x100, base_addr + 0x10F)
+            .is_ok()
+    );
+    ih.merge(&patch);
+    assert!(ih.is_mapped());
+    assert_eq!(ih.iter().count(), 2);
+    assert_eq!(ih.read_byte(base_addr + 0x10), Some(0xAB));
+    assert_eq!(ih.read_byte(base_addr + 0x100), Some(0x00));
+    assert_eq!(ih.read_byte(base_addr + 0x2000), None);
+
+    while ih.undo() {}
+    assert!(ih.is_mapped());
+    assert_eq!(ih.snapshot(), original);
+    assert!(ih.iter().eq(ih_read.iter()));
+}
+
 #[test]
 #[allow(clippy::panic)]
 fn test_hex_parsing_returns_error() {